@@ -0,0 +1,3 @@
+-- Бот, через которого привязан Telegram (telegram_link_scope = "per_bot").
+-- Старые записи остаются с NULL и считаются записями бота по умолчанию
+ALTER TABLE users.auth_method ADD COLUMN provider text;
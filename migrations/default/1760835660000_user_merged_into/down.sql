@@ -0,0 +1,3 @@
+ALTER TABLE users."user"
+    DROP COLUMN merged_into,
+    DROP COLUMN deleted_at;
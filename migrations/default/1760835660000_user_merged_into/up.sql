@@ -0,0 +1,4 @@
+-- Пользователь, поглощённый слиянием аккаунтов (MergeUsers)
+ALTER TABLE users."user"
+    ADD COLUMN merged_into uuid REFERENCES users."user" (id) ON DELETE SET NULL,
+    ADD COLUMN deleted_at timestamptz;
@@ -0,0 +1,5 @@
+ALTER TABLE users.auth_method
+    DROP COLUMN name,
+    DROP COLUMN expires_at,
+    DROP COLUMN last_used_at,
+    DROP COLUMN revoked_at;
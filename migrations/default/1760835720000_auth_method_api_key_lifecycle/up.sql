@@ -0,0 +1,6 @@
+-- Имя, срок действия, последнее использование и отзыв API ключей
+ALTER TABLE users.auth_method
+    ADD COLUMN name text,
+    ADD COLUMN expires_at timestamptz,
+    ADD COLUMN last_used_at timestamptz,
+    ADD COLUMN revoked_at timestamptz;
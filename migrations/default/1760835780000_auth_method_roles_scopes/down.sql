@@ -0,0 +1,3 @@
+ALTER TABLE users.auth_method
+    DROP COLUMN roles,
+    DROP COLUMN scopes;
@@ -0,0 +1,4 @@
+-- Ограничение API ключа подмножеством ролей пользователя и scopes, NULL - без ограничений
+ALTER TABLE users.auth_method
+    ADD COLUMN roles jsonb,
+    ADD COLUMN scopes jsonb;
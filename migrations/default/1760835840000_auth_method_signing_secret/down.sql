@@ -0,0 +1 @@
+ALTER TABLE users.auth_method DROP COLUMN signing_secret;
@@ -0,0 +1,2 @@
+-- Зашифрованный ключ подписи запросов API ключа (AUTH_REQUEST_SIGNING_KEY)
+ALTER TABLE users.auth_method ADD COLUMN signing_secret text;
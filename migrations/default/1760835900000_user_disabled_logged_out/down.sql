@@ -0,0 +1,3 @@
+ALTER TABLE users."user"
+    DROP COLUMN disabled_at,
+    DROP COLUMN logged_out_at;
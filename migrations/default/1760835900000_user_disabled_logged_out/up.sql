@@ -0,0 +1,5 @@
+-- Отключение пользователя администратором и принудительный выход:
+-- токены, выпущенные до logged_out_at, не принимаются
+ALTER TABLE users."user"
+    ADD COLUMN disabled_at timestamptz,
+    ADD COLUMN logged_out_at timestamptz;
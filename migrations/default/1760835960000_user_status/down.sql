@@ -0,0 +1,6 @@
+ALTER TABLE users."user"
+    DROP COLUMN status,
+    DROP COLUMN status_reason,
+    DROP COLUMN locked_until;
+
+DROP TABLE users.user_status;
@@ -0,0 +1,17 @@
+-- Статус пользователя, проверяется перед выпуском токенов.
+-- users.user_status отслеживается Hasura как enum (users_user_status_enum)
+CREATE TABLE users.user_status (
+    value text PRIMARY KEY,
+    comment text
+);
+
+INSERT INTO users.user_status (value, comment) VALUES
+    ('active', 'Активен'),
+    ('disabled', 'Отключён администратором'),
+    ('locked', 'Заблокирован до locked_until'),
+    ('pending_verification', 'Не подтвердил email или приглашение');
+
+ALTER TABLE users."user"
+    ADD COLUMN status text NOT NULL DEFAULT 'active' REFERENCES users.user_status (value) ON UPDATE CASCADE,
+    ADD COLUMN status_reason text,
+    ADD COLUMN locked_until timestamptz;
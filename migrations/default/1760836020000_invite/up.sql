@@ -0,0 +1,12 @@
+-- Приглашения для регистрации при signup_mode = "invite_only"
+CREATE TABLE users.invite (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    code text NOT NULL UNIQUE,
+    role text REFERENCES users.role (value) ON UPDATE CASCADE ON DELETE SET NULL,
+    max_uses integer CHECK (max_uses > 0),
+    uses_left integer CHECK (uses_left >= 0),
+    used_count integer NOT NULL DEFAULT 0,
+    expires_at timestamptz,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    comment text
+);
@@ -0,0 +1,3 @@
+DROP INDEX users.user_delete_after_idx;
+
+ALTER TABLE users."user" DROP COLUMN delete_after;
@@ -0,0 +1,4 @@
+-- Когда удалить аккаунт по запросу владельца (DELETE /auth/me)
+ALTER TABLE users."user" ADD COLUMN delete_after timestamptz;
+
+CREATE INDEX user_delete_after_idx ON users."user" (delete_after) WHERE deleted_at IS NULL;
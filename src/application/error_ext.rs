@@ -1,8 +1,10 @@
-use crate::domain::errors::service::AppErrorInfo;
+use crate::domain::errors::locale::Locale;
+use crate::domain::errors::service::{AppError, AppErrorInfo};
 
 pub trait ServiceErrorExt {
-    fn map_service_error<E: AppErrorInfo>(&self, err: E) -> String {
+    /// Логирует ошибку и отдаёт её клиенту на языке текущего запроса
+    fn map_service_error<E: AppErrorInfo>(&self, err: E) -> AppError {
         tracing::error!("{} | Level: {:?}", err.log_message(), err.level());
-        err.client_message()
+        AppError::localized(&err, Locale::current())
     }
 }
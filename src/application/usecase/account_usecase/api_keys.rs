@@ -0,0 +1,97 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{ApiKeyInfoDto, ApiKeysResponseDto};
+use super::error::AccountError;
+
+const API_KEY_AUTH_TYPE: &str = "apikey";
+
+/// API ключи текущего пользователя, включая отозванные и истёкшие
+pub struct ListApiKeysUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ListApiKeysUseCase<QUS> {}
+
+impl<QUS> ListApiKeysUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, claims: Claims) -> Result<ApiKeysResponseDto, AppError> {
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let api_keys = auth_methods
+            .iter()
+            .filter(|v| v.auth_type() == API_KEY_AUTH_TYPE)
+            .map(ApiKeyInfoDto::from)
+            .collect();
+
+        Ok(ApiKeysResponseDto::Success { api_keys })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<ApiKeysResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn only_api_keys_are_listed() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .with_user_api_keys()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+        let claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+
+        let action = ListApiKeysUseCase::new(&user_provider_factory);
+        let result = action.execute(claims).await;
+
+        let Ok(ApiKeysResponseDto::Success { api_keys }) = result else {
+            panic!("api keys are not listed");
+        };
+        assert_eq!(api_keys.len(), 2);
+        assert_eq!(api_keys[0].name.as_deref(), Some("ci"));
+        assert!(api_keys[1].revoked_at.is_some());
+        let body = serde_json::to_string(&api_keys).unwrap();
+        assert!(!body.contains("$2b$"));
+    }
+}
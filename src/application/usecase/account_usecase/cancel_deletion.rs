@@ -0,0 +1,98 @@
+use chrono::Utc;
+
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::models::status::UserStatusChange;
+use crate::domain::user::service::CommandUserService;
+
+/// Отмена удаления аккаунта (`DELETE /auth/me`) при входе до `delete_after`.
+/// Вход паролем или через Telegram и есть повторная аутентификация владельца,
+/// поэтому отдельного запроса на отмену нет
+pub struct CancelAccountDeletionUseCase<CUS> {
+    command_user_service: CUS,
+}
+
+impl<CUS> CancelAccountDeletionUseCase<CUS>
+where
+    CUS: CommandUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        Self { command_user_service }
+    }
+
+    /// Возвращает пользователя в том состоянии, в котором ему выдаются токены
+    pub async fn execute(&self, mut auth_method: ExtendedAuthMethod) -> Result<ExtendedAuthMethod, CUS::Error> {
+        if !auth_method.user().is_deletion_pending(Utc::now()) {
+            return Ok(auth_method);
+        }
+
+        self.command_user_service
+            .set_user_status(*auth_method.user_id(), UserStatusChange::active())
+            .await?;
+        auth_method.cancel_user_deletion();
+        Ok(auth_method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn pending_deletion() -> ExtendedAuthMethod {
+        let mut auth_method = serde_json::to_value(ExtendedAuthMethod::mock()).unwrap();
+        auth_method["user"]["status"] = serde_json::json!("disabled");
+        auth_method["user"]["status_reason"] = serde_json::json!("deletion_requested");
+        auth_method["user"]["delete_after"] = serde_json::json!((Utc::now() + chrono::Duration::days(1)).to_rfc3339());
+        serde_json::from_value(auth_method).unwrap()
+    }
+
+    #[tokio::test]
+    async fn login_cancels_pending_deletion() {
+        let mut builder = MockHasuraClientBuilder::new();
+        builder.with_user_management();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), builder.build());
+        let action = CancelAccountDeletionUseCase::new(&user_provider_factory);
+
+        let result = action.execute(pending_deletion()).await.unwrap();
+
+        assert!(result.user().is_active(Utc::now()));
+        assert!(result.user().delete_after().is_none());
+        let body: serde_json::Value = serde_json::from_str(&builder.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "UpdateUserStatus");
+        assert_eq!(body["variables"]["status"], "active");
+    }
+
+    #[tokio::test]
+    async fn active_user_is_not_updated() {
+        // Ответ на UpdateUserStatus не задан: лишний запрос вернёт ошибку
+        let builder = MockHasuraClientBuilder::new();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), builder.build());
+        let action = CancelAccountDeletionUseCase::new(&user_provider_factory);
+
+        let result = action.execute(ExtendedAuthMethod::mock()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn disabled_by_admin_stays_disabled() {
+        let mut auth_method = serde_json::to_value(pending_deletion()).unwrap();
+        auth_method["user"]["delete_after"] = serde_json::Value::Null;
+        let auth_method: ExtendedAuthMethod = serde_json::from_value(auth_method).unwrap();
+        let builder = MockHasuraClientBuilder::new();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), builder.build());
+        let action = CancelAccountDeletionUseCase::new(&user_provider_factory);
+
+        let result = action.execute(auth_method).await.unwrap();
+
+        assert!(!result.user().is_active(Utc::now()));
+    }
+}
@@ -0,0 +1,262 @@
+use std::str::FromStr;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::{ApiKeyVerifierService, PasswordVerifierService};
+
+use super::dto::{DeleteAccountRequestDto, DeleteAccountResponseDto};
+use super::error::AccountError;
+
+const PASSWORD_AUTH_TYPE: &str = "email";
+/// Удаление и слияние аккаунтов необратимы, поэтому свежий вход нужен и без `reauth_max_age_minutes`
+pub const DEFAULT_REAUTH_MAX_AGE_MINUTES: i64 = 15;
+
+/// Удаление аккаунта текущим пользователем (`DELETE /auth/me`).
+/// Аккаунт сразу отключается, а удаляется через `account_deletion_grace_days`
+/// (`PurgeDeletedUsersUseCase`). До этого удаление отменяет вход паролем или через Telegram
+/// (`CancelAccountDeletionUseCase`), а также администратор, включив аккаунт обратно
+pub struct DeleteAccountUseCase<CUS, QUS, V, AKV> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    query_user_service: QUS,
+    password_verifier: V,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, V, AKV> ServiceErrorExt for DeleteAccountUseCase<CUS, QUS, V, AKV> {}
+
+impl<CUS, QUS, V, AKV> DeleteAccountUseCase<CUS, QUS, V, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    V: PasswordVerifierService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(
+        credentials: Credentials,
+        user_provider_factory: &U,
+        verifies_provider_factory: &P,
+    ) -> Self
+    where
+        P: VerifiesProviderFactory<PasswordVerifier = V, ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let password_verifier = verifies_provider_factory.password_verifier();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { credentials, command_user_service, query_user_service, password_verifier, api_key_verifier }
+    }
+
+    pub async fn execute(&self, claims: Claims, dto: DeleteAccountRequestDto) -> Result<DeleteAccountResponseDto, AppError> {
+        // API ключ не может удалить своего владельца
+        if claims.is_api_key() {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        }
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+        if auth_methods.is_empty() {
+            return self.handler_error(AccountError::UserNotFound(user_id.to_string()));
+        }
+
+        if let Err(e) = self.reauthenticate(&claims, &auth_methods, dto.password.as_deref()) {
+            return self.handler_error(e);
+        }
+
+        let now = Utc::now();
+        let grace_days = *self.credentials.account_deletion_grace_days();
+        if grace_days <= 0 {
+            if let Err(e) = self.command_user_service.delete_user(user_id, now).await {
+                return self.handler_error(e);
+            }
+            self.api_key_verifier.forget_user(user_id);
+            return Ok(DeleteAccountResponseDto::Deleted);
+        }
+
+        let delete_after = now + Duration::days(grace_days);
+        if let Err(e) = self.command_user_service.schedule_user_deletion(user_id, delete_after, now).await {
+            return self.handler_error(e);
+        }
+        // Аккаунт уже отключён, ключи из кэша больше не принимаем
+        self.api_key_verifier.forget_user(user_id);
+        Ok(DeleteAccountResponseDto::Scheduled { delete_after })
+    }
+
+    /// Пароль, если он передан, иначе недавний вход по `reauth_max_age_minutes`
+    /// (по умолчанию `DEFAULT_REAUTH_MAX_AGE_MINUTES`)
+    fn reauthenticate(
+        &self,
+        claims: &Claims,
+        auth_methods: &[ExtendedAuthMethod],
+        password: Option<&str>,
+    ) -> Result<(), AccountError> {
+        if let Some(password) = password {
+            let password_hash = auth_methods
+                .iter()
+                .filter(|v| v.auth_type() == PASSWORD_AUTH_TYPE)
+                .find_map(|v| v.secret().clone())
+                .ok_or(AccountError::NotCorrectPassword)?;
+            return match self.password_verifier.is_verified(&password_hash, password) {
+                Ok(true) => Ok(()),
+                _ => Err(AccountError::NotCorrectPassword),
+            };
+        }
+
+        let max_age_minutes = self
+            .credentials
+            .reauth_max_age_minutes()
+            .unwrap_or(DEFAULT_REAUTH_MAX_AGE_MINUTES);
+        let now = Utc::now().timestamp();
+        let is_recent = claims
+            .auth_time
+            .is_some_and(|v| now - (v as i64) <= max_age_minutes * 60);
+        if !is_recent {
+            return Err(AccountError::ReauthenticationRequired { auth_time: claims.auth_time, max_age_minutes });
+        }
+        Ok(())
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<DeleteAccountResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user::MockUser;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn access_claims(jwtprovider_factory: &JWTProvider, auth_time: Option<usize>) -> Claims {
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        if auth_time.is_some() {
+            claims.auth_time = auth_time;
+        }
+        claims
+    }
+
+    async fn delete(
+        credentials: Credentials,
+        hasura_client: &MockHasuraClientBuilder,
+        auth_time: Option<usize>,
+        password: Option<&str>,
+    ) -> Result<DeleteAccountResponseDto, AppError> {
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = DeleteAccountUseCase::new(
+            credentials,
+            &user_provider_factory,
+            &verifies_provider_factory,
+        );
+        let dto = DeleteAccountRequestDto { password: password.map(str::to_string) };
+        action.execute(access_claims(&jwtprovider_factory, auth_time), dto).await
+    }
+
+    fn deletion() -> MockHasuraClientBuilder {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method().with_user_deletion();
+        hasura_client
+    }
+
+    #[tokio::test]
+    async fn deletion_is_scheduled_after_grace_period() {
+        let hasura_client = deletion();
+
+        let result = delete(Credentials::mock(), &hasura_client, None, None).await;
+
+        let Ok(DeleteAccountResponseDto::Scheduled { delete_after }) = result else {
+            panic!("deletion is not scheduled");
+        };
+        assert!(delete_after > Utc::now() + Duration::days(29));
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "ScheduleUserDeletion");
+        assert_eq!(body["variables"]["status"], "disabled");
+    }
+
+    #[tokio::test]
+    async fn account_is_deleted_without_grace_period() {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["account_deletion_grace_days"] = serde_json::json!(0);
+        let credentials: Credentials = serde_json::from_value(credentials).unwrap();
+        let hasura_client = deletion();
+
+        let result = delete(credentials, &hasura_client, None, None).await;
+
+        assert!(matches!(result, Ok(DeleteAccountResponseDto::Deleted)));
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "DeleteUser");
+    }
+
+    #[tokio::test]
+    async fn stale_session_or_wrong_password_is_rejected() {
+        let stale = (Utc::now() - Duration::hours(1)).timestamp() as usize;
+
+        let without_password = delete(Credentials::mock(), &deletion(), Some(stale), None).await;
+        let wrong_password = delete(Credentials::mock(), &deletion(), None, Some("wrong")).await;
+
+        assert!(matches!(without_password, Err(e) if e.status == 401 && e.code == "reauthentication_required"));
+        assert!(matches!(wrong_password, Err(e) if e.status == 401 && e.code == "invalid_credentials"));
+    }
+
+    #[tokio::test]
+    async fn stale_session_is_rejected_without_reauth_setting() {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["reauth_max_age_minutes"] = serde_json::Value::Null;
+        let credentials: Credentials = serde_json::from_value(credentials).unwrap();
+        let stale = (Utc::now() - Duration::hours(1)).timestamp() as usize;
+
+        let result = delete(credentials, &deletion(), Some(stale), None).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code == "reauthentication_required"));
+    }
+
+    #[tokio::test]
+    async fn api_key_token_is_rejected() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), deletion().build());
+        let mut auth_method = serde_json::to_value(ExtendedAuthMethod::mock()).unwrap();
+        auth_method["auth_type"] = serde_json::json!("apikey");
+        let auth_method: ExtendedAuthMethod = serde_json::from_value(auth_method).unwrap();
+        let claims = jwtprovider_factory.claims_service().access_claims(&auth_method).unwrap();
+
+        let action = DeleteAccountUseCase::new(
+            credentials,
+            &user_provider_factory,
+            &verifies_provider_factory,
+        );
+        let result = action.execute(claims, DeleteAccountRequestDto { password: Some(MockUser::password()) }).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code != "invalid_credentials"));
+    }
+}
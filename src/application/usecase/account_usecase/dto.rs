@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use uuid::Uuid;
+
+use crate::application::usecase::admin_usecase::dto::AuthMethodInfoDto;
+use crate::domain::user::models::base::AuthMethod;
+use crate::domain::user::models::extended::{ExtendedAuthMethod, ExtendedUser};
+
+/// Способ входа без секрета
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct IdentityDto {
+    pub id: Uuid,
+    pub auth_type: String,
+    pub identifier: String,
+    pub provider: Option<String>,
+    pub created_at: Option<DateTime<FixedOffset>>,
+}
+
+impl From<&ExtendedAuthMethod> for IdentityDto {
+    fn from(value: &ExtendedAuthMethod) -> Self {
+        Self {
+            id: *value.id(),
+            auth_type: value.auth_type().clone(),
+            identifier: value.identifier().clone(),
+            provider: value.provider().clone(),
+            created_at: *value.created_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum IdentitiesResponseDto {
+    Success { identities: Vec<IdentityDto> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum UnlinkIdentityResponseDto {
+    Success { removed: Vec<IdentityDto> },
+}
+
+/// API ключ без секрета
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ApiKeyInfoDto {
+    pub identifier: String,
+    pub name: Option<String>,
+    pub created_at: Option<DateTime<FixedOffset>>,
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    pub last_used_at: Option<DateTime<FixedOffset>>,
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+    pub roles: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+}
+
+impl From<&AuthMethod> for ApiKeyInfoDto {
+    fn from(value: &AuthMethod) -> Self {
+        Self {
+            identifier: value.identifier().clone(),
+            name: value.name().clone(),
+            created_at: *value.created_at(),
+            expires_at: *value.expires_at(),
+            last_used_at: *value.last_used_at(),
+            revoked_at: *value.revoked_at(),
+            roles: value.roles().clone(),
+            scopes: value.scopes().clone(),
+        }
+    }
+}
+
+impl From<&ExtendedAuthMethod> for ApiKeyInfoDto {
+    fn from(value: &ExtendedAuthMethod) -> Self {
+        Self {
+            identifier: value.identifier().clone(),
+            name: value.name().clone(),
+            created_at: *value.created_at(),
+            expires_at: *value.expires_at(),
+            last_used_at: *value.last_used_at(),
+            revoked_at: *value.revoked_at(),
+            roles: value.roles().clone(),
+            scopes: value.scopes().clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum ApiKeysResponseDto {
+    Success { api_keys: Vec<ApiKeyInfoDto> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum RevokeApiKeyResponseDto {
+    Success { revoked: ApiKeyInfoDto },
+}
+
+/// Текущий пользователь: роли, атрибуты и способы входа без секретов
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ProfileDto {
+    pub user: ExtendedUser,
+    pub identities: Vec<IdentityDto>,
+}
+
+impl ProfileDto {
+    /// Пользователь один у всех способов входа, `None` если их нет
+    pub fn from_auth_methods(auth_methods: &[ExtendedAuthMethod]) -> Option<Self> {
+        let user = auth_methods.first()?.user().clone();
+        let identities = auth_methods.iter().map(IdentityDto::from).collect();
+        Some(Self { user, identities })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum ProfileResponseDto {
+    Success { profile: ProfileDto },
+}
+
+/// Новые значения атрибутов, имя -> значение
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct UpdateProfileRequestDto {
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// Повторная проверка перед удалением: пароль или недавний вход (`reauth_max_age_minutes`)
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct DeleteAccountRequestDto {
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "scheduled", "deleted", ошибки - problem+json
+pub enum DeleteAccountResponseDto {
+    /// Аккаунт отключён и будет удалён после `delete_after`
+    Scheduled { delete_after: DateTime<Utc> },
+    Deleted,
+}
+
+/// Всё, что сервис хранит о пользователе. Сессии не хранятся (токены без состояния):
+/// от них остаются `logged_out_at` пользователя и `last_used_at` способов входа.
+/// Журнала аудита в сервисе нет, поэтому и в выгрузке его нет: появится журнал -
+/// его записи добавляются сюда же
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct AccountExportDto {
+    pub exported_at: DateTime<Utc>,
+    pub user: ExtendedUser,
+    pub auth_methods: Vec<AuthMethodInfoDto>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum AccountExportResponseDto {
+    Success { export: AccountExportDto },
+}
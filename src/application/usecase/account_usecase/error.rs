@@ -0,0 +1,85 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AccountError {
+    #[error("Access token is not verified")]
+    NotCorrectAccessToken,
+    #[error("Identity {0} not found")]
+    IdentityNotFound(String),
+    #[error("Identity {0} is the last login method")]
+    LastLoginMethod(String),
+    #[error("Api key {0} not found or already revoked")]
+    ApiKeyNotFound(String),
+    #[error("User {0} has no login methods")]
+    UserNotFound(String),
+    #[error("Attribute {0} is not user editable")]
+    AttributeNotEditable(String),
+    #[error("Attribute {0} has invalid value")]
+    InvalidAttributeValue(String),
+    #[error("Password is not correct")]
+    NotCorrectPassword,
+    #[error("Session is older than {max_age_minutes} minutes (auth_time: {auth_time:?})")]
+    ReauthenticationRequired { auth_time: Option<usize>, max_age_minutes: i64 },
+}
+
+impl AppErrorInfo for AccountError {
+    fn client_message(&self) -> String {
+        match self {
+            AccountError::NotCorrectAccessToken => "Unauthorized".to_string(),
+            AccountError::IdentityNotFound(_) => "Identity not found".to_string(),
+            AccountError::LastLoginMethod(_) => "Cannot remove the last login method".to_string(),
+            AccountError::ApiKeyNotFound(_) => "Api key not found".to_string(),
+            AccountError::UserNotFound(_) => "User not found".to_string(),
+            AccountError::AttributeNotEditable(v) => format!("Attribute {} cannot be changed", v),
+            AccountError::InvalidAttributeValue(v) => format!("Invalid value of attribute {}", v),
+            AccountError::NotCorrectPassword => "Not correct credentials".to_string(),
+            AccountError::ReauthenticationRequired { .. } => "Recent authentication required".to_string(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        ErrorLevel::Info
+    }
+
+    fn log_message(&self) -> String {
+        format!("AccountError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            AccountError::NotCorrectAccessToken
+            | AccountError::NotCorrectPassword
+            | AccountError::ReauthenticationRequired { .. } => 401,
+            AccountError::IdentityNotFound(_)
+            | AccountError::ApiKeyNotFound(_)
+            | AccountError::UserNotFound(_) => 404,
+            AccountError::LastLoginMethod(_) => 409,
+            AccountError::AttributeNotEditable(_) => 403,
+            AccountError::InvalidAttributeValue(_) => 422,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AccountError::NotCorrectAccessToken => "invalid_token",
+            AccountError::IdentityNotFound(_) => "identity_not_found",
+            AccountError::LastLoginMethod(_) => "last_login_method",
+            AccountError::ApiKeyNotFound(_) => "api_key_not_found",
+            AccountError::UserNotFound(_) => "user_not_found",
+            AccountError::AttributeNotEditable(_) => "attribute_not_editable",
+            AccountError::InvalidAttributeValue(_) => "invalid_attribute_value",
+            AccountError::NotCorrectPassword => "invalid_credentials",
+            AccountError::ReauthenticationRequired { .. } => "reauthentication_required",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AccountError::AttributeNotEditable(v) | AccountError::InvalidAttributeValue(v) => {
+                vec![("attribute", v.clone())]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
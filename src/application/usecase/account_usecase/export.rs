@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::admin_usecase::dto::AuthMethodInfoDto;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{AccountExportDto, AccountExportResponseDto};
+use super::error::AccountError;
+
+/// Выгрузка персональных данных текущего пользователя (`GET /auth/me/export`):
+/// пользователь с ролями и атрибутами и все способы входа, включая отозванные, без секретов.
+/// Записей журнала аудита нет: сервис его не ведёт
+pub struct ExportAccountUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ExportAccountUseCase<QUS> {}
+
+impl<QUS> ExportAccountUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, claims: Claims) -> Result<AccountExportResponseDto, AppError> {
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let user = match self.query_user_service.get_user(user_id).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(AccountError::UserNotFound(user_id.to_string())),
+            Err(e) => return self.handler_error(e),
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let export = AccountExportDto {
+            exported_at: Utc::now(),
+            user,
+            auth_methods: auth_methods.iter().map(AuthMethodInfoDto::from).collect(),
+        };
+
+        Ok(AccountExportResponseDto::Success { export })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<AccountExportResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn export_has_no_secrets() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user().with_email_auth_method();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+
+        let action = ExportAccountUseCase::new(&user_provider_factory);
+        let result = action.execute(claims).await;
+
+        let Ok(AccountExportResponseDto::Success { export }) = result else {
+            panic!("account is not exported");
+        };
+        assert!(!export.auth_methods.is_empty());
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(!json.contains("secret"));
+        assert!(!json.contains("$2b$"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{IdentitiesResponseDto, IdentityDto};
+use super::error::AccountError;
+
+/// Способы входа текущего пользователя
+pub struct ListIdentitiesUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ListIdentitiesUseCase<QUS> {}
+
+impl<QUS> ListIdentitiesUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, claims: Claims) -> Result<IdentitiesResponseDto, AppError> {
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let identities = auth_methods.iter().map(IdentityDto::from).collect();
+
+        Ok(IdentitiesResponseDto::Success { identities })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<IdentitiesResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn identities_have_no_secrets() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .with_linked_telegram()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+        let claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+
+        let action = ListIdentitiesUseCase::new(&user_provider_factory);
+        let result = action.execute(claims).await;
+
+        let Ok(IdentitiesResponseDto::Success { identities }) = result else {
+            panic!("identities are not listed");
+        };
+        assert_eq!(identities.len(), 3);
+        let body = serde_json::to_string(&identities).unwrap();
+        assert!(!body.contains("secret"));
+        assert!(!body.contains("$2b$"));
+    }
+
+}
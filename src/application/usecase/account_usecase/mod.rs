@@ -0,0 +1,11 @@
+pub mod api_keys;
+pub mod cancel_deletion;
+pub mod delete_account;
+pub mod dto;
+pub mod error;
+pub mod export;
+pub mod identities;
+pub mod profile;
+pub mod revoke_api_key;
+pub mod unlink;
+pub mod update_profile;
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{ProfileDto, ProfileResponseDto};
+use super::error::AccountError;
+
+/// Профиль текущего пользователя (`GET /auth/me`)
+pub struct GetProfileUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for GetProfileUseCase<QUS> {}
+
+impl<QUS> GetProfileUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, claims: Claims) -> Result<ProfileResponseDto, AppError> {
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        match ProfileDto::from_auth_methods(&auth_methods) {
+            Some(profile) => Ok(ProfileResponseDto::Success { profile }),
+            None => self.handler_error(AccountError::UserNotFound(user_id.to_string())),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<ProfileResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn access_claims(jwtprovider_factory: &JWTProvider) -> Claims {
+        jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn profile_has_roles_and_no_secrets() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = GetProfileUseCase::new(&user_provider_factory);
+        let result = action.execute(access_claims(&jwtprovider_factory)).await;
+
+        let Ok(ProfileResponseDto::Success { profile }) = result else {
+            panic!("profile is not returned");
+        };
+        assert_eq!(profile.user.user_roles()[0].role(), "test");
+        assert_eq!(profile.identities[0].auth_type, "email");
+        let body = serde_json::to_string(&profile).unwrap();
+        assert!(!body.contains("$2b$"));
+    }
+
+    #[tokio::test]
+    async fn user_without_auth_methods() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_auth_method_not_found()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = GetProfileUseCase::new(&user_provider_factory);
+        let result = action.execute(access_claims(&jwtprovider_factory)).await;
+
+        assert!(matches!(result, Err(e) if e.status == 404));
+    }
+}
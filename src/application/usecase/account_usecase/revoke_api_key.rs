@@ -0,0 +1,131 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::CommandUserService;
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::{ApiKeyInfoDto, RevokeApiKeyResponseDto};
+use super::error::AccountError;
+
+const API_KEY_AUTH_TYPE: &str = "apikey";
+
+/// Отзыв API ключа текущего пользователя.
+/// Запись не удаляется - остаётся в списке с revoked_at
+pub struct RevokeApiKeyUseCase<CUS, AKV> {
+    command_user_service: CUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, AKV> ServiceErrorExt for RevokeApiKeyUseCase<CUS, AKV> {}
+
+impl<CUS, AKV> RevokeApiKeyUseCase<CUS, AKV>
+where
+    CUS: CommandUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, claims: Claims, identifier: String) -> Result<RevokeApiKeyResponseDto, AppError> {
+        // Ключ, в том числе только на чтение, не отзывает ключи владельца
+        if claims.is_api_key() {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        }
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        // user_id в условии мутации: чужой ключ отозвать нельзя
+        let revoked = match self
+            .command_user_service
+            .revoke_auth_method(&identifier, API_KEY_AUTH_TYPE, user_id)
+            .await
+        {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(AccountError::ApiKeyNotFound(identifier)),
+            Err(e) => return self.handler_error(e),
+        };
+        // Проверенный ранее ключ иначе принимался бы из кэша до конца TTL
+        self.api_key_verifier.forget_user(user_id);
+
+        Ok(RevokeApiKeyResponseDto::Success { revoked: ApiKeyInfoDto::from(&revoked) })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<RevokeApiKeyResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn access_claims(jwtprovider_factory: &JWTProvider) -> Claims {
+        jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn api_key_is_revoked() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new().with_auth_method_revoke().build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = RevokeApiKeyUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action
+            .execute(access_claims(&jwtprovider_factory), "FNGFb2Px6cox1wvR98KmU6Fl8IXkBU1x".to_string())
+            .await;
+
+        let Ok(RevokeApiKeyResponseDto::Success { revoked }) = result else {
+            panic!("api key is not revoked");
+        };
+        assert!(revoked.revoked_at.is_some());
+    }
+
+
+    #[tokio::test]
+    async fn api_key_token_is_rejected() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        // Ответ на отзыв не задан: до мутации дойти не должно
+        let hasura_client = MockHasuraClientBuilder::new().build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        claims.scopes = Some(vec!["read".to_string()]);
+
+        let action = RevokeApiKeyUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action.execute(claims, "FNGFb2Px6cox1wvR98KmU6Fl8IXkBU1x".to_string()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+}
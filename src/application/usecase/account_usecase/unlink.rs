@@ -0,0 +1,243 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::{Claims, API_KEY_AMR};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::{IdentityDto, UnlinkIdentityResponseDto};
+use super::error::AccountError;
+
+/// Telegram хранит два способа входа (id и username) - отвязываются вместе
+const TELEGRAM_AUTH_TYPE: &str = "telegram";
+/// Способы, которыми входит сам пользователь; API ключи и гостевой вход не в счёт
+const INTERACTIVE_AUTH_TYPES: [&str; 2] = ["email", TELEGRAM_AUTH_TYPE];
+
+/// Отвязка способа входа текущего пользователя.
+/// Последний действующий способ входа (email или Telegram) удалить нельзя, иначе аккаунт станет недоступен
+pub struct UnlinkIdentityUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for UnlinkIdentityUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> UnlinkIdentityUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, claims: Claims, identity_id: Uuid) -> Result<UnlinkIdentityResponseDto, AppError> {
+        // API ключ не управляет способами входа своего владельца
+        if claims.is_api_key() {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        }
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let Some(target) = auth_methods.iter().find(|v| *v.id() == identity_id) else {
+            return self.handler_error(AccountError::IdentityNotFound(identity_id.to_string()));
+        };
+
+        let (removing, remaining): (Vec<&ExtendedAuthMethod>, Vec<&ExtendedAuthMethod>) =
+            auth_methods.iter().partition(|v| Self::unlinked_together(target, v));
+
+        let now = chrono::Utc::now();
+        let can_login = remaining
+            .iter()
+            .any(|v| INTERACTIVE_AUTH_TYPES.contains(&v.auth_type().as_str()) && v.is_active(now));
+        if !can_login {
+            return self.handler_error(AccountError::LastLoginMethod(identity_id.to_string()));
+        }
+
+        // id и username Telegram удаляются одной мутацией
+        let ids = removing.iter().map(|v| *v.id()).collect();
+        let deleted = match self.command_user_service.remove_auth_methods(ids, user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+        if deleted.is_empty() {
+            return self.handler_error(AccountError::IdentityNotFound(identity_id.to_string()));
+        }
+
+        // Удалённый ключ не должен работать из кэша до истечения TTL
+        if deleted.iter().any(|v| v.auth_type() == API_KEY_AMR) {
+            self.api_key_verifier.forget_user(user_id);
+        }
+
+        let removed = removing
+            .into_iter()
+            .filter(|v| deleted.iter().any(|d| *d.id() == Some(*v.id())))
+            .map(IdentityDto::from)
+            .collect();
+
+        Ok(UnlinkIdentityResponseDto::Success { removed })
+    }
+
+    fn unlinked_together(target: &ExtendedAuthMethod, other: &ExtendedAuthMethod) -> bool {
+        if other.id() == target.id() {
+            return true;
+        }
+        target.auth_type() == TELEGRAM_AUTH_TYPE
+            && other.auth_type() == TELEGRAM_AUTH_TYPE
+            && other.provider() == target.provider()
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UnlinkIdentityResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    const EMAIL_IDENTITY: &str = "801bd045-a367-4683-9234-293580264e39";
+    const TELEGRAM_IDENTITY: &str = "901bd045-a367-4683-9234-293580264e39";
+
+    fn access_claims(jwtprovider_factory: &JWTProvider) -> Claims {
+        jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn unlink_telegram() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_linked_telegram()
+            .with_auth_method_removal()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action
+            .execute(access_claims(&jwtprovider_factory), Uuid::from_str(TELEGRAM_IDENTITY).unwrap())
+            .await;
+
+        let Ok(UnlinkIdentityResponseDto::Success { removed }) = result else {
+            panic!("identity is not removed");
+        };
+        // id и username одного бота
+        assert_eq!(removed.len(), 2);
+        assert!(removed.iter().all(|v| v.auth_type == TELEGRAM_AUTH_TYPE));
+    }
+
+    #[tokio::test]
+    async fn last_login_method_is_kept() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        // Ответ на DeleteUsersAuthMethod не задан: попытка удаления вернёт Err
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action
+            .execute(access_claims(&jwtprovider_factory), Uuid::from_str(EMAIL_IDENTITY).unwrap())
+            .await;
+
+        let Err(e) = result else {
+            panic!("last login method is removed");
+        };
+        assert_eq!(e.status, 409);
+        assert_eq!(e.message, "Cannot remove the last login method");
+    }
+
+    #[tokio::test]
+    async fn api_keys_do_not_count_as_login_method() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user_api_keys()
+            .with_auth_method_removal()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action
+            .execute(access_claims(&jwtprovider_factory), Uuid::from_str(EMAIL_IDENTITY).unwrap())
+            .await;
+
+        assert!(matches!(result, Err(e) if e.status == 409));
+    }
+
+    #[tokio::test]
+    async fn foreign_identity_is_not_found() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_linked_telegram()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action
+            .execute(access_claims(&jwtprovider_factory), Uuid::new_v4())
+            .await;
+
+        assert!(matches!(result, Err(e) if e.status == 404));
+    }
+
+    #[tokio::test]
+    async fn api_key_token_is_rejected() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        // Ответ на DeleteUsersAuthMethod не задан: до удаления дойти не должно
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_linked_telegram()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+        let claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap()
+            .with_amr(API_KEY_AMR);
+
+        let action = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action.execute(claims, Uuid::from_str(TELEGRAM_IDENTITY).unwrap()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+}
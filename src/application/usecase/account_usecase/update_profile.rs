@@ -0,0 +1,185 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::attribute_usecase::validate::ValidateAttributesUseCase;
+
+use crate::domain::errors::locale::{Locale, LOCALE_ATTRIBUTE};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::base::UserAttribute;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::dto::{ProfileDto, ProfileResponseDto, UpdateProfileRequestDto};
+use super::error::AccountError;
+
+/// Изменение атрибутов текущего пользователя (`PATCH /auth/me`).
+/// Менять можно только атрибуты из `user_editable_attributes`
+pub struct UpdateProfileUseCase<CUS, QUS> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    query_user_service: QUS,
+    validate_attributes_use_case: ValidateAttributesUseCase<QUS>,
+}
+
+impl<CUS, QUS> ServiceErrorExt for UpdateProfileUseCase<CUS, QUS> {}
+
+impl<CUS, QUS> UpdateProfileUseCase<CUS, QUS>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let validate_attributes_use_case = ValidateAttributesUseCase::new(user_provider_factory);
+        Self { credentials, command_user_service, query_user_service, validate_attributes_use_case }
+    }
+
+    pub async fn execute(&self, claims: Claims, dto: UpdateProfileRequestDto) -> Result<ProfileResponseDto, AppError> {
+        // Профиль меняет только сам пользователь, не его API ключ
+        if claims.is_api_key() {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        }
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(AccountError::NotCorrectAccessToken);
+        };
+
+        // Проверяется и записывается одно и то же значение
+        let attributes = dto
+            .attributes
+            .into_iter()
+            .map(|(attribute, value)| (attribute, value.trim().to_string()))
+            .collect::<Vec<_>>();
+
+        // Сначала проверяем все атрибуты, чтобы не применить запрос частично
+        for (attribute, value) in attributes.iter() {
+            if !self.credentials.user_editable_attributes().contains(attribute) {
+                return self.handler_error(AccountError::AttributeNotEditable(attribute.clone()));
+            }
+            if attribute == LOCALE_ATTRIBUTE && Locale::from_tag(value).is_none() {
+                return self.handler_error(AccountError::InvalidAttributeValue(attribute.clone()));
+            }
+        }
+        let checked = attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>();
+        if let Err(e) = self.validate_attributes_use_case.execute(Some(user_id), &checked).await {
+            return self.handler_error(e);
+        }
+
+        // Все атрибуты одной мутацией: при ошибке профиль не меняется
+        let attributes = attributes
+            .into_iter()
+            .map(|(attribute, value)| UserAttribute::new(user_id, attribute, value))
+            .collect();
+        if let Err(e) = self.command_user_service.update_user_attributes(user_id, attributes).await {
+            return self.handler_error(e);
+        }
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        match ProfileDto::from_auth_methods(&auth_methods) {
+            Some(profile) => Ok(ProfileResponseDto::Success { profile }),
+            None => self.handler_error(AccountError::UserNotFound(user_id.to_string())),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<ProfileResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn update(hasura_client: &mut MockHasuraClientBuilder, attributes: &[(&str, &str)]) -> Result<ProfileResponseDto, AppError> {
+        update_with_claims(hasura_client, attributes, |_| {}).await
+    }
+
+    async fn update_with_claims(
+        hasura_client: &mut MockHasuraClientBuilder,
+        attributes: &[(&str, &str)],
+        tweak: impl FnOnce(&mut Claims),
+    ) -> Result<ProfileResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        tweak(&mut claims);
+        hasura_client.with_attribute_schema();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let dto = UpdateProfileRequestDto {
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+        };
+        let action = UpdateProfileUseCase::new(credentials, &user_provider_factory);
+        action.execute(claims, dto).await
+    }
+
+    #[tokio::test]
+    async fn editable_attribute_is_updated() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method().with_attribute_update();
+
+        let result = update(&mut hasura_client, &[("locale", "ru")]).await;
+
+        assert!(matches!(result, Ok(ProfileResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn not_editable_attribute_is_rejected() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method().with_attribute_update();
+
+        let result = update(&mut hasura_client, &[("locale", "ru"), ("role", "admin")]).await;
+
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "attribute_not_editable"));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn unknown_locale_is_rejected() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method().with_attribute_update();
+
+        let result = update(&mut hasura_client, &[("locale", "xx")]).await;
+
+        assert!(matches!(result, Err(e) if e.status == 422));
+    }
+
+    #[tokio::test]
+    async fn api_key_token_is_rejected() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method().with_attribute_update();
+
+        let result = update_with_claims(&mut hasura_client, &[("locale", "ru")], |claims| {
+            claims.scopes = Some(vec!["read".to_string()]);
+        })
+        .await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::UserResponseDto;
+use super::error::AdminError;
+
+/// Удаление пользователя администратором (`DELETE /auth/admin/users/{id}`) - сразу,
+/// без срока `account_deletion_grace_days`. Возвращает оставшуюся строку `users.user`
+pub struct DeleteUserUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for DeleteUserUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> DeleteUserUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<UserResponseDto, AppError> {
+        match self.query_user_service.get_user(user_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => return self.handler_error(e),
+        }
+
+        if let Err(e) = self.command_user_service.delete_user(user_id, Utc::now()).await {
+            return self.handler_error(e);
+        }
+        self.api_key_verifier.forget_user(user_id);
+
+        match self.query_user_service.get_user(user_id).await {
+            Ok(Some(user)) => Ok(UserResponseDto::Success { user }),
+            Ok(None) => self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn user_is_deleted_by_one_mutation() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user().with_user_deletion();
+        let user_provider_factory = MockUserProvider::new(credentials, hasura_client.build());
+
+        let action = DeleteUserUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Ok(UserResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn unknown_user_is_not_found() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user_not_found().with_user_deletion();
+        let user_provider_factory = MockUserProvider::new(credentials, hasura_client.build());
+
+        let action = DeleteUserUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 404));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_ne!(body["operationName"], "DeleteUser");
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, FixedOffset};
+use uuid::Uuid;
+
+use crate::domain::user::models::extended::{ExtendedAuthMethod, ExtendedUser};
+
+/// Размер страницы `GET /admin/users` по умолчанию и максимальный
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct ListUsersRequestDto {
+    /// Подстрока идентификатора способа входа (email, telegram id) или значения атрибута
+    pub search: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct RoleRequestDto {
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct DisableUserRequestDto {
+    pub reason: Option<String>,
+}
+
+/// Способ входа пользователя для администратора, без секретов
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct AuthMethodInfoDto {
+    pub id: Uuid,
+    pub auth_type: String,
+    pub identifier: String,
+    pub provider: Option<String>,
+    pub name: Option<String>,
+    pub created_at: Option<DateTime<FixedOffset>>,
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    pub last_used_at: Option<DateTime<FixedOffset>>,
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+    pub roles: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+}
+
+impl From<&ExtendedAuthMethod> for AuthMethodInfoDto {
+    fn from(value: &ExtendedAuthMethod) -> Self {
+        Self {
+            id: *value.id(),
+            auth_type: value.auth_type().clone(),
+            identifier: value.identifier().clone(),
+            provider: value.provider().clone(),
+            name: value.name().clone(),
+            created_at: *value.created_at(),
+            expires_at: *value.expires_at(),
+            last_used_at: *value.last_used_at(),
+            revoked_at: *value.revoked_at(),
+            roles: value.roles().clone(),
+            scopes: value.scopes().clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum UsersResponseDto {
+    Success { users: Vec<ExtendedUser>, total: i64, limit: i64, offset: i64 },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum UserResponseDto {
+    Success { user: ExtendedUser },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum UserDetailsResponseDto {
+    Success { user: ExtendedUser, auth_methods: Vec<AuthMethodInfoDto> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum PurgedUsersResponseDto {
+    Success { deleted: Vec<Uuid> },
+}
@@ -0,0 +1,60 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("User {0} not found")]
+    UserNotFound(String),
+    #[error("Role {0} is not assigned")]
+    RoleNotAssigned(String),
+    #[error("Role {0} is already assigned")]
+    RoleAlreadyAssigned(String),
+    #[error("Role {0} is the default role")]
+    DefaultRoleRevoke(String),
+}
+
+impl AppErrorInfo for AdminError {
+    fn client_message(&self) -> String {
+        match self {
+            AdminError::UserNotFound(_) => "User not found".to_string(),
+            AdminError::RoleNotAssigned(v) => format!("Role {} is not assigned to the user", v),
+            AdminError::RoleAlreadyAssigned(v) => format!("Role {} is already assigned to the user", v),
+            AdminError::DefaultRoleRevoke(v) => {
+                format!("Role {} is the default role, set another default role first", v)
+            }
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        ErrorLevel::Info
+    }
+
+    fn log_message(&self) -> String {
+        format!("AdminError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            AdminError::UserNotFound(_) | AdminError::RoleNotAssigned(_) => 404,
+            AdminError::RoleAlreadyAssigned(_) | AdminError::DefaultRoleRevoke(_) => 409,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AdminError::UserNotFound(_) => "user_not_found",
+            AdminError::RoleNotAssigned(_) => "role_not_assigned",
+            AdminError::RoleAlreadyAssigned(_) => "role_already_assigned",
+            AdminError::DefaultRoleRevoke(_) => "default_role_revoke",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AdminError::RoleNotAssigned(v)
+            | AdminError::RoleAlreadyAssigned(v)
+            | AdminError::DefaultRoleRevoke(v) => vec![("role", v.clone())],
+            AdminError::UserNotFound(_) => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{AuthMethodInfoDto, UserDetailsResponseDto};
+use super::error::AdminError;
+
+/// Пользователь и его способы входа без секретов (`GET /auth/admin/users/{id}`)
+pub struct GetUserUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for GetUserUseCase<QUS> {}
+
+impl<QUS> GetUserUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<UserDetailsResponseDto, AppError> {
+        let user = match self.query_user_service.get_user(user_id).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => return self.handler_error(e),
+        };
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v.iter().map(AuthMethodInfoDto::from).collect(),
+            Err(e) => return self.handler_error(e),
+        };
+
+        Ok(UserDetailsResponseDto::Success { user, auth_methods })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserDetailsResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn auth_methods_without_secrets() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = GetUserUseCase::new(&user_provider_factory);
+        let result = action.execute(Uuid::new_v4()).await;
+
+        let Ok(UserDetailsResponseDto::Success { auth_methods, .. }) = result else {
+            panic!("user is not returned");
+        };
+        assert_eq!(auth_methods[0].auth_type, "email");
+        let body = serde_json::to_string(&auth_methods).unwrap();
+        assert!(!body.contains("$2b$"));
+    }
+
+    #[tokio::test]
+    async fn user_not_found() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user_not_found()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = GetUserUseCase::new(&user_provider_factory);
+        let result = action.execute(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 404 && e.code == "user_not_found"));
+    }
+}
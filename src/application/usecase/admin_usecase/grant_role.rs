@@ -0,0 +1,106 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::base::UserRole;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::UserResponseDto;
+use super::error::AdminError;
+
+/// Назначает роль пользователю (`POST /auth/admin/users/{id}/roles`).
+/// Первая роль пользователя становится ролью по умолчанию
+pub struct GrantRoleUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for GrantRoleUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> GrantRoleUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, role: String) -> Result<UserResponseDto, AppError> {
+        let mut user = match self.query_user_service.get_user(user_id).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => return self.handler_error(e),
+        };
+        if user.user_roles().iter().any(|v| v.role() == &role) {
+            return self.handler_error(AdminError::RoleAlreadyAssigned(role));
+        }
+
+        let is_default = user.user_roles().is_empty();
+        match self.command_user_service.add_role(UserRole::new(is_default, role, user_id)).await {
+            Ok(v) => user.add_role(v),
+            Err(e) => return self.handler_error(e),
+        }
+        // В кэше API ключей лежат прежние роли владельца
+        self.api_key_verifier.forget_user(user_id);
+
+        Ok(UserResponseDto::Success { user })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn assigned_role_is_rejected() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user().with_user_management();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+
+        let action = GrantRoleUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4(), "user".to_string()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 409 && e.code == "role_already_assigned"));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "GetUserById");
+    }
+
+    #[tokio::test]
+    async fn role_is_granted() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user().with_user_management();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+
+        let action = GrantRoleUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4(), "manager".to_string()).await;
+
+        assert!(matches!(result, Ok(UserResponseDto::Success { user }) if user.user_roles().len() == 3));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "InsertUsersRole");
+        assert_eq!(body["variables"]["role"], "manager");
+        assert_eq!(body["variables"]["is_default"], false);
+    }
+}
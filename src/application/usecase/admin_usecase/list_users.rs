@@ -0,0 +1,76 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::page::UserFilter;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{ListUsersRequestDto, UsersResponseDto, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+
+/// Список пользователей с поиском и пагинацией (`GET /auth/admin/users`)
+pub struct ListUsersUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ListUsersUseCase<QUS> {}
+
+impl<QUS> ListUsersUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self, dto: ListUsersRequestDto) -> Result<UsersResponseDto, AppError> {
+        let limit = dto.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        let offset = dto.offset.unwrap_or(0).max(0);
+        let search = dto.search.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let filter = UserFilter { search, limit, offset };
+        match self.query_user_service.list_users(filter).await {
+            Ok(page) => Ok(UsersResponseDto::Success {
+                users: page.users().clone(),
+                total: *page.total(),
+                limit,
+                offset,
+            }),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UsersResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn limit_is_clamped() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+
+        let action = ListUsersUseCase::new(&user_provider_factory);
+        let dto = ListUsersRequestDto { search: Some("  ".to_string()), limit: Some(1000), offset: Some(-5) };
+        let result = action.execute(dto).await;
+
+        let Ok(UsersResponseDto::Success { users, total, limit, offset }) = result else {
+            panic!("users are not returned");
+        };
+        assert_eq!((users.len(), total, limit, offset), (2, 12, MAX_PAGE_LIMIT, 0));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert!(body["variables"]["where"].get("_or").is_none());
+    }
+}
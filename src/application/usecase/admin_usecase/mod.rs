@@ -0,0 +1,11 @@
+pub mod delete_user;
+pub mod dto;
+pub mod error;
+pub mod get_user;
+pub mod grant_role;
+pub mod list_users;
+pub mod logout;
+pub mod purge_users;
+pub mod revoke_role;
+pub mod set_default_role;
+pub mod set_disabled;
@@ -0,0 +1,85 @@
+use chrono::Utc;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::dto::PurgedUsersResponseDto;
+
+/// Удаляет аккаунты, у которых закончился срок после `DELETE /auth/me`
+/// (`POST /auth/admin/users/purge`). Запускается каждые `account_purge_interval_minutes`
+/// или по cron, если интервал 0
+pub struct PurgeDeletedUsersUseCase<CUS, QUS> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+}
+
+impl<CUS, QUS> ServiceErrorExt for PurgeDeletedUsersUseCase<CUS, QUS> {}
+
+impl<CUS, QUS> PurgeDeletedUsersUseCase<CUS, QUS>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        Self { command_user_service, query_user_service }
+    }
+
+    pub async fn execute(&self) -> Result<PurgedUsersResponseDto, AppError> {
+        let now = Utc::now();
+        let user_ids = match self.query_user_service.list_users_to_delete(now).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        // Каждый пользователь удаляется своей транзакцией: при ошибке удалённые раньше остаются удалёнными
+        let mut deleted = Vec::new();
+        for user_id in user_ids {
+            if let Err(e) = self.command_user_service.delete_user(user_id, now).await {
+                return self.handler_error(e);
+            }
+            deleted.push(user_id);
+        }
+
+        Ok(PurgedUsersResponseDto::Success { deleted })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<PurgedUsersResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn due_users_are_deleted() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user_deletion();
+        let user_provider_factory = MockUserProvider::new(credentials, hasura_client.build());
+
+        let action = PurgeDeletedUsersUseCase::new(&user_provider_factory);
+        let result = action.execute().await;
+
+        let Ok(PurgedUsersResponseDto::Success { deleted }) = result else {
+            panic!("users are not purged");
+        };
+        assert_eq!(deleted.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "DeleteUser");
+        assert_eq!(body["variables"]["id"], deleted[0].to_string());
+    }
+}
@@ -0,0 +1,130 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::UserResponseDto;
+use super::error::AdminError;
+
+/// Снимает роль с пользователя (`DELETE /auth/admin/users/{id}/roles/{role}`).
+/// Роль по умолчанию не снимается, сначала нужно назначить другую
+pub struct RevokeRoleUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for RevokeRoleUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> RevokeRoleUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, role: String) -> Result<UserResponseDto, AppError> {
+        let user = match self.query_user_service.get_user(user_id).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => return self.handler_error(e),
+        };
+        match user.user_roles().iter().find(|v| v.role() == &role) {
+            Some(v) if *v.is_default() => return self.handler_error(AdminError::DefaultRoleRevoke(role)),
+            Some(_) => {}
+            None => return self.handler_error(AdminError::RoleNotAssigned(role)),
+        }
+
+        match self.command_user_service.remove_role(user_id, &role).await {
+            Ok(Some(_)) => {}
+            // Роль успели снять параллельным запросом
+            Ok(None) => return self.handler_error(AdminError::RoleNotAssigned(role)),
+            Err(e) => return self.handler_error(e),
+        }
+        // В кэше API ключей лежат прежние роли владельца
+        self.api_key_verifier.forget_user(user_id);
+
+        match self.query_user_service.get_user(user_id).await {
+            Ok(Some(user)) => Ok(UserResponseDto::Success { user }),
+            Ok(None) => self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn revoke(role: &str) -> Result<UserResponseDto, AppError> {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user()
+            .with_user_management()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = RevokeRoleUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        action.execute(Uuid::new_v4(), role.to_string()).await
+    }
+
+    #[tokio::test]
+    async fn role_is_revoked() {
+        assert!(revoke("user").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn default_role_is_not_revoked() {
+        let result = revoke("test").await;
+        assert!(matches!(result, Err(e) if e.status == 409 && e.code == "default_role_revoke"));
+    }
+
+    #[tokio::test]
+    async fn not_assigned_role() {
+        let result = revoke("manager").await;
+        assert!(matches!(result, Err(e) if e.status == 404 && e.code == "role_not_assigned"));
+    }
+
+    #[tokio::test]
+    async fn revoked_role_evicts_cached_api_keys() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user()
+            .with_user_management()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+        let verifies_provider_factory = VerifiesProvider::new(Credentials::mock());
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        let auth_method = ExtendedAuthMethod::mock();
+        api_key_verifier.remember("awr_test_key", &auth_method);
+
+        let action = RevokeRoleUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = action.execute(*auth_method.user_id(), "user".to_string()).await;
+
+        assert!(result.is_ok());
+        assert!(api_key_verifier.cached("awr_test_key").is_none());
+    }
+}
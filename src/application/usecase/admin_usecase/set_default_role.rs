@@ -0,0 +1,84 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::UserResponseDto;
+use super::error::AdminError;
+
+/// Меняет роль по умолчанию (`PUT /auth/admin/users/{id}/default-role`).
+/// Роль уже должна быть назначена пользователю
+pub struct SetDefaultRoleUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for SetDefaultRoleUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> SetDefaultRoleUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, role: String) -> Result<UserResponseDto, AppError> {
+        match self.command_user_service.set_default_role(user_id, &role).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return self.handler_error(AdminError::RoleNotAssigned(role)),
+            Err(e) => return self.handler_error(e),
+        }
+        // В кэше API ключей лежит прежняя роль по умолчанию
+        self.api_key_verifier.forget_user(user_id);
+
+        match self.query_user_service.get_user(user_id).await {
+            Ok(Some(user)) => Ok(UserResponseDto::Success { user }),
+            Ok(None) => self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn default_role_is_changed() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user()
+            .with_user_management()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = SetDefaultRoleUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4(), "user".to_string()).await;
+
+        assert!(matches!(result, Ok(UserResponseDto::Success { .. })));
+    }
+}
@@ -0,0 +1,102 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::status::UserStatusChange;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::UserResponseDto;
+use super::error::AdminError;
+
+/// Отключает и включает пользователя (`POST /auth/admin/users/{id}/disable|enable`).
+/// Отключённому не выдаются новые токены, в том числе по refresh токену;
+/// включение снимает и блокировку
+pub struct SetUserDisabledUseCase<CUS, QUS, AKV> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<CUS, QUS, AKV> ServiceErrorExt for SetUserDisabledUseCase<CUS, QUS, AKV> {}
+
+impl<CUS, QUS, AKV> SetUserDisabledUseCase<CUS, QUS, AKV>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { command_user_service, query_user_service, api_key_verifier }
+    }
+
+    /// `reason` сохраняется только при отключении
+    pub async fn execute(&self, user_id: Uuid, disabled: bool, reason: Option<String>) -> Result<UserResponseDto, AppError> {
+        let change = match disabled {
+            true => UserStatusChange::disabled(reason),
+            false => UserStatusChange::active(),
+        };
+        if let Err(e) = self.command_user_service.set_user_status(user_id, change).await {
+            return self.handler_error(e);
+        }
+        // В кэше API ключей лежит прежний статус владельца
+        self.api_key_verifier.forget_user(user_id);
+
+        match self.query_user_service.get_user(user_id).await {
+            Ok(Some(user)) => Ok(UserResponseDto::Success { user }),
+            Ok(None) => self.handler_error(AdminError::UserNotFound(user_id.to_string())),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<UserResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn user_is_disabled() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user()
+            .with_user_management()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = SetUserDisabledUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4(), true, Some("spam".to_string())).await;
+
+        assert!(matches!(result, Ok(UserResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn user_not_found() {
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_user_not_found()
+            .build();
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client);
+
+        let action = SetUserDisabledUseCase::new(&user_provider_factory, &VerifiesProvider::new(Credentials::mock()));
+        let result = action.execute(Uuid::new_v4(), false, None).await;
+
+        assert!(matches!(result, Err(e) if e.status == 404));
+    }
+}
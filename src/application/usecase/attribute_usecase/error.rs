@@ -0,0 +1,100 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel, FieldError};
+use thiserror::Error;
+
+/// Код поля: обязательный атрибут не заполнен
+pub const FIELD_REQUIRED: &str = "required";
+/// Код поля: значение уникального атрибута уже у другого пользователя
+pub const FIELD_NOT_UNIQUE: &str = "not_unique";
+/// Код поля: атрибута нет в `users.attribute`
+pub const FIELD_UNKNOWN: &str = "unknown";
+/// Код поля: значение не соответствует `value_type`
+pub const FIELD_INVALID_TYPE: &str = "invalid_type";
+/// Код поля: значение не соответствует `pattern`
+pub const FIELD_INVALID_FORMAT: &str = "invalid_format";
+/// Код поля: атрибут задаётся отдельным полем запроса, например `email`
+pub const FIELD_RESERVED: &str = "reserved";
+
+#[derive(Debug, Error, Clone)]
+pub enum AttributeError {
+    #[error("Attributes are not valid: {}", fields(.0))]
+    Invalid(Vec<FieldError>),
+    #[error("Attribute schema is not available: {0}")]
+    SchemaUnavailable(String),
+}
+
+fn fields(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{} ({})", e.field, e.code))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl AttributeError {
+    /// Только конфликты уникальности, без незаполненных атрибутов
+    fn is_conflict(&self) -> bool {
+        match self {
+            AttributeError::Invalid(errors) => errors.iter().all(|e| e.code == FIELD_NOT_UNIQUE),
+            AttributeError::SchemaUnavailable(_) => false,
+        }
+    }
+}
+
+impl AppErrorInfo for AttributeError {
+    fn client_message(&self) -> String {
+        match self {
+            AttributeError::Invalid(_) if self.is_conflict() => {
+                format!("Attribute value is already taken: {}", self.message_args()[0].1)
+            }
+            AttributeError::Invalid(_) => {
+                format!("Attributes are not valid: {}", self.message_args()[0].1)
+            }
+            AttributeError::SchemaUnavailable(_) => self.internal_error(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            AttributeError::Invalid(_) => ErrorLevel::Info,
+            AttributeError::SchemaUnavailable(_) => ErrorLevel::Error,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("AttributeError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            AttributeError::Invalid(_) if self.is_conflict() => 409,
+            AttributeError::Invalid(_) => 422,
+            AttributeError::SchemaUnavailable(_) => 500,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AttributeError::Invalid(_) if self.is_conflict() => "attribute_taken",
+            AttributeError::Invalid(_) => "invalid_attributes",
+            AttributeError::SchemaUnavailable(_) => "internal_error",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AttributeError::Invalid(errors) => {
+                let mut attributes = errors.iter().map(|e| e.field.clone()).collect::<Vec<_>>();
+                attributes.dedup();
+                vec![("attributes", attributes.join(", "))]
+            }
+            AttributeError::SchemaUnavailable(_) => Vec::new(),
+        }
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            AttributeError::Invalid(errors) => errors.clone(),
+            AttributeError::SchemaUnavailable(_) => Vec::new(),
+        }
+    }
+}
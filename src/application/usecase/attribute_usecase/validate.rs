@@ -0,0 +1,197 @@
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::domain::errors::service::FieldError;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use crate::domain::user::models::base::Attribute;
+
+use super::error::{
+    AttributeError, FIELD_INVALID_FORMAT, FIELD_INVALID_TYPE, FIELD_NOT_UNIQUE, FIELD_REQUIRED, FIELD_UNKNOWN,
+};
+
+/// Проверка атрибутов по схеме `users.attribute` перед записью.
+/// Ошибки собираются по всем полям сразу, а не до первой
+pub struct ValidateAttributesUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ValidateAttributesUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    /// Атрибуты нового пользователя: все обязательные должны быть заполнены,
+    /// а атрибутов вне схемы быть не должно. `user_id` - гость, который регистрируется
+    pub async fn execute_new_user(&self, user_id: Option<Uuid>, attributes: &[(&str, &str)]) -> Result<(), AttributeError> {
+        self.check(user_id, attributes, true).await
+    }
+
+    /// Запись атрибутов пользователю `user_id` (или будущему, если `None`):
+    /// проверяются только записываемые значения, свои значения конфликтом не считаются.
+    /// Значения ожидаются уже обрезанными: записываться должно то, что проверено
+    pub async fn execute(&self, user_id: Option<Uuid>, attributes: &[(&str, &str)]) -> Result<(), AttributeError> {
+        self.check(user_id, attributes, false).await
+    }
+
+    async fn check(
+        &self,
+        user_id: Option<Uuid>,
+        attributes: &[(&str, &str)],
+        require_all: bool,
+    ) -> Result<(), AttributeError> {
+        let schema = self
+            .query_user_service
+            .get_attribute_schema()
+            .await
+            .map_err(|e| AttributeError::SchemaUnavailable(e.to_string()))?;
+
+        let value_of = |name: &str| {
+            attributes
+                .iter()
+                .find(|(attribute, _)| *attribute == name)
+                .map(|(_, value)| value.trim())
+        };
+
+        let mut errors = Vec::new();
+        if require_all {
+            for (name, _) in attributes.iter() {
+                if !schema.iter().any(|attribute| attribute.value() == name) {
+                    errors.push(FieldError::new(name, FIELD_UNKNOWN));
+                }
+            }
+        }
+
+        // Ошибки в порядке схемы, чтобы ответ не зависел от порядка полей запроса
+        for attribute in schema.iter() {
+            let name = attribute.value().as_str();
+            let value = value_of(name);
+            if *attribute.is_required() && value.map_or(require_all, str::is_empty) {
+                errors.push(FieldError::new(name, FIELD_REQUIRED));
+                continue;
+            }
+            let Some(value) = value.filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            if let Some(code) = Self::format_error(attribute, value)? {
+                errors.push(FieldError::new(name, code));
+                continue;
+            }
+            if !*attribute.is_unique() {
+                continue;
+            }
+            let is_free = self
+                .query_user_service
+                .attribute_value_is_free(name, value, user_id)
+                .await
+                .map_err(|e| AttributeError::SchemaUnavailable(e.to_string()))?;
+            if !is_free {
+                errors.push(FieldError::new(name, FIELD_NOT_UNIQUE));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AttributeError::Invalid(errors))
+        }
+    }
+
+    /// Тип и `pattern` (целиком, как `^(?:pattern)$`); ошибка в самом pattern - ошибка схемы
+    fn format_error(attribute: &Attribute, value: &str) -> Result<Option<&'static str>, AttributeError> {
+        if !attribute.value_type().accepts(value) {
+            return Ok(Some(FIELD_INVALID_TYPE));
+        }
+        let Some(pattern) = attribute.pattern() else {
+            return Ok(None);
+        };
+        let regex = Regex::new(&format!("^(?:{})$", pattern))
+            .map_err(|e| AttributeError::SchemaUnavailable(format!("{}: {}", attribute.value(), e)))?;
+        Ok((!regex.is_match(value)).then_some(FIELD_INVALID_FORMAT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::errors::service::AppErrorInfo;
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn validator(hasura_client: &mut MockHasuraClientBuilder) -> ValidateAttributesUseCase<impl QueryUserService> {
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+        ValidateAttributesUseCase::new(&user_provider_factory)
+    }
+
+    #[tokio::test]
+    async fn valid_new_user() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema();
+
+        let result = validator(&mut hasura_client)
+            .execute_new_user(None, &[("username", "test"), ("email", "test@test.com")])
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_required_attribute() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema();
+
+        let result = validator(&mut hasura_client)
+            .execute_new_user(None, &[("username", "test"), ("email", " ")])
+            .await;
+
+        let Err(e) = result else {
+            panic!("empty email is accepted");
+        };
+        assert_eq!(e.status_code(), 422);
+        assert_eq!(e.field_errors(), vec![FieldError::new("email", FIELD_REQUIRED)]);
+    }
+
+    #[tokio::test]
+    async fn taken_unique_attribute() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema().with_taken_attribute_value();
+
+        let result = validator(&mut hasura_client)
+            .execute_new_user(None, &[("username", "test"), ("email", "test@test.com")])
+            .await;
+
+        let Err(e) = result else {
+            panic!("taken username is accepted");
+        };
+        assert_eq!(e.status_code(), 409);
+        assert_eq!(e.error_code(), "attribute_taken");
+        assert_eq!(
+            e.field_errors(),
+            vec![FieldError::new("email", FIELD_NOT_UNIQUE), FieldError::new("username", FIELD_NOT_UNIQUE)]
+        );
+    }
+
+    #[tokio::test]
+    async fn existing_user_writes_only_given_attributes() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema();
+        let user_id = Uuid::new_v4();
+
+        let result = validator(&mut hasura_client).execute(Some(user_id), &[("username", "test")]).await;
+
+        assert!(result.is_ok());
+        let request: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(request["variables"]["except_user"], serde_json::json!(user_id));
+    }
+}
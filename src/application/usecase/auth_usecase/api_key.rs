@@ -1,8 +1,9 @@
 use crate::application::error_ext::ServiceErrorExt;
 use crate::application::usecase::auth_usecase::dto::{LoginApiKeyRequestDto, LoginApiKeyResponseDto};
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
 use crate::domain::jwt::service::{JwtClaimsService, TokenService};
-use crate::domain::user::service::QueryUserService;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
 use crate::domain::verifies::service::ApiKeyVerifierService;
 
 use crate::domain::jwt::factories::JWTProviderFactory;
@@ -14,7 +15,8 @@ use super::error::AuthenticatorError;
 
 const AUTH_TYPE: &str = "apikey";
 
-pub struct LoginWithApiKeyUseCase<Q, A, CP, TP> {
+pub struct LoginWithApiKeyUseCase<C, Q, A, CP, TP> {
+    command_user_service: C,
     query_user_service: Q,
     api_key_verifier: A,
     claims_provider: CP,
@@ -22,11 +24,12 @@ pub struct LoginWithApiKeyUseCase<Q, A, CP, TP> {
 }
 
 
-impl<Q, A, CP, TP> ServiceErrorExt for LoginWithApiKeyUseCase<Q, A, CP, TP> {}
+impl<C, Q, A, CP, TP> ServiceErrorExt for LoginWithApiKeyUseCase<C, Q, A, CP, TP> {}
 
 
-impl<Q, A, CP, TP> LoginWithApiKeyUseCase<Q, A, CP, TP>
+impl<C, Q, A, CP, TP> LoginWithApiKeyUseCase<C, Q, A, CP, TP>
 where
+    C: CommandUserService,
     Q: QueryUserService,
     A: ApiKeyVerifierService,
     CP: JwtClaimsService,
@@ -40,13 +43,15 @@ where
     where
         T: JWTProviderFactory<Claims = CP, Tokens = TP>,
         P: VerifiesProviderFactory<ApiKeyVerifier = A>,
-        U: UserProviderFactory<QueryUser = Q>,
+        U: UserProviderFactory<QueryUser = Q, CommandUser = C>,
     {
         let claims_provider = jwtprovider_factory.claims_service();
         let token_provider = jwtprovider_factory.token_service();
         let api_key_verifier = verifies_provider_factory.api_key_verifier();
         let query_user_service = user_provider_factory.query_user();
+        let command_user_service = user_provider_factory.command_user();
         Self {
+            command_user_service,
             query_user_service,
             api_key_verifier,
             claims_provider,
@@ -54,36 +59,63 @@ where
         }
     }
 
-    pub async fn execute(&self, dto: LoginApiKeyRequestDto) -> Result<LoginApiKeyResponseDto, String> {
+    pub async fn execute(&self, dto: LoginApiKeyRequestDto) -> Result<LoginApiKeyResponseDto, AppError> {
         let identifier = match self.api_key_verifier.extract_identifier(&dto.api_key) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
         };
 
         println!("identifier {}", identifier);
-        let user = match self
-            .query_user_service
-            .get_user_by_identifier(&identifier, AUTH_TYPE)
-            .await
-        {
-            Ok(Some(v)) => v,
-            Ok(None) => return self.handler_error(AuthenticatorError::UserNotFound(dto.api_key)),
-            Err(e) => return self.handler_error(e),
-        };
+        let user = match self.api_key_verifier.cached(&dto.api_key) {
+            Some(v) => v,
+            None => {
+                let user = match self
+                    .query_user_service
+                    .get_user_by_identifier(&identifier, AUTH_TYPE)
+                    .await
+                {
+                    Ok(Some(v)) => v,
+                    Ok(None) => return self.handler_error(AuthenticatorError::UserNotFound(dto.api_key)),
+                    Err(e) => return self.handler_error(e),
+                };
 
-        let Some(api_key_hash) = user.secret() else {
-            return self.handler_error(AuthenticatorError::ApiKeyAuthenticatorNotAllowed(
-                identifier,
-            ));
-        };
+                let Some(api_key_hash) = user.secret() else {
+                    return self.handler_error(AuthenticatorError::ApiKeyAuthenticatorNotAllowed(
+                        identifier,
+                    ));
+                };
 
-        let is_verified = match self.api_key_verifier.is_verified(&api_key_hash, &dto.api_key) {
-            Ok(v) => v,
-            Err(e) => return self.handler_error(e),
+                // До проверки хэша: отозванный ключ не стоит проверять
+                if !user.is_active(chrono::Utc::now()) {
+                    return self.handler_error(AuthenticatorError::ApiKeyInactive(identifier));
+                }
+
+                let is_verified = match self.api_key_verifier.is_verified(&api_key_hash, &dto.api_key) {
+                    Ok(v) => v,
+                    Err(e) => return self.handler_error(e),
+                };
+
+                if !is_verified {
+                    return self.handler_error(AuthenticatorError::NotCorrectApiKey);
+                }
+
+                // last_used_at - справочная информация, вход из-за неё не ломаем
+                if let Err(e) = self.command_user_service.touch_auth_method(*user.id()).await {
+                    self.map_service_error(e);
+                }
+
+                if self.api_key_verifier.needs_rehash(api_key_hash) {
+                    self.rehash(&user, &dto.api_key).await;
+                }
+
+                self.api_key_verifier.remember(&dto.api_key, &user);
+                user
+            }
         };
 
-        if !is_verified {
-            return self.handler_error(AuthenticatorError::NotCorrectApiKey);
+        // Ключ из кэша мог истечь после проверки
+        if !user.is_active(chrono::Utc::now()) {
+            return self.handler_error(AuthenticatorError::ApiKeyInactive(identifier));
         }
 
         let claims = match self.claims_provider.access_claims(&user) {
@@ -106,12 +138,129 @@ where
     }
 
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<LoginApiKeyResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(LoginApiKeyResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
+    /// Переводит старый bcrypt хэш на текущий алгоритм; ошибка входу не мешает
+    async fn rehash(&self, user: &ExtendedAuthMethod, api_key: &str) {
+        let api_key_hash = match self.api_key_verifier.create_hash(api_key) {
+            Ok(v) => v,
+            Err(e) => {
+                self.map_service_error(e);
+                return;
+            }
+        };
+        if let Err(e) = self
+            .command_user_service
+            .update_auth_method_secret(*user.id(), api_key_hash)
+            .await
+        {
+            self.map_service_error(e);
         }
     }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<LoginApiKeyResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user::MockUser;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn login(hasura_client: &mut MockHasuraClientBuilder) -> Result<LoginApiKeyResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = LoginWithApiKeyUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+
+        action.execute(LoginApiKeyRequestDto { api_key: MockUser::api_key() }).await
+    }
+
+    #[tokio::test]
+    async fn active_api_key() {
+        let result = login(
+            MockHasuraClientBuilder::new()
+                .with_apikey_auth_method()
+                .with_auth_method_touch()
+        ).await;
+
+        assert!(matches!(result, Ok(LoginApiKeyResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn bcrypt_api_key_is_migrated_and_cached() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client
+            .with_apikey_auth_method()
+            .with_auth_method_touch()
+            .with_auth_method_secret_update();
+        let recorder = hasura_client.recorder();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = LoginWithApiKeyUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+
+        let result = action.execute(LoginApiKeyRequestDto { api_key: MockUser::api_key() }).await;
+        assert!(matches!(result, Ok(LoginApiKeyResponseDto::Success { .. })));
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert!(body["variables"]["secret"].as_str().unwrap().starts_with("hmac-sha256$"));
+
+        // Повторный вход - из кэша, без запросов в Hasura
+        recorder.set_data(String::new()).await;
+        let result = action.execute(LoginApiKeyRequestDto { api_key: MockUser::api_key() }).await;
+        assert!(matches!(result, Ok(LoginApiKeyResponseDto::Success { .. })));
+        assert_eq!(recorder.read_data().await, Some(String::new()));
+    }
+
+    #[tokio::test]
+    async fn scoped_api_key() {
+        let result = login(
+            MockHasuraClientBuilder::new()
+                .with_scoped_apikey_auth_method()
+                .with_auth_method_touch()
+        ).await;
+
+        let Ok(LoginApiKeyResponseDto::Success { auth_data }) = result else {
+            panic!("scoped api key is not accepted");
+        };
+        let claims = JWTProvider::new(Credentials::mock())
+            .token_service()
+            .validate_access(&auth_data.access_token)
+            .unwrap();
+        assert_eq!(claims.hasura_claims.x_hasura_allowed_roles, vec!["reports".to_string()]);
+        assert_eq!(claims.hasura_claims.x_hasura_default_role, "reports");
+        assert_eq!(claims.scopes, Some(vec!["reports:read".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn revoked_api_key() {
+        let result = login(MockHasuraClientBuilder::new().with_revoked_apikey_auth_method()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn expired_api_key() {
+        let result = login(MockHasuraClientBuilder::new().with_expired_apikey_auth_method()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
 }
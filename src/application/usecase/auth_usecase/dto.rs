@@ -5,10 +5,9 @@ pub struct LoginEmailPasRequestDto {
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
 pub enum JwtResponseDto {
     Success { auth_data: TokenPairDto },
-    Error { err_msg: String },
 }
 
 
@@ -23,10 +22,9 @@ pub struct RefreshTokenRequestDto {
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
 pub enum LoginApiKeyResponseDto {
     Success { auth_data: TokenPairDto },
-    Error { err_msg: String },
 }
 
 
@@ -1,8 +1,9 @@
 use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::account_usecase::cancel_deletion::CancelAccountDeletionUseCase;
 use crate::application::usecase::auth_usecase::dto::{LoginEmailPasRequestDto, JwtResponseDto};
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
 use crate::domain::jwt::service::{JwtClaimsService, TokenService};
-use crate::domain::user::service::QueryUserService;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
 use crate::domain::verifies::service::PasswordVerifierService;
 
 use crate::domain::jwt::factories::JWTProviderFactory;
@@ -16,17 +17,19 @@ use super::constants::AUTH_TYPE;
 
 
 
-impl<Q, V, CP, TP> ServiceErrorExt for LoginWithEmailPasswdUseCase<Q, V, CP, TP> {}
+impl<C, Q, V, CP, TP> ServiceErrorExt for LoginWithEmailPasswdUseCase<C, Q, V, CP, TP> {}
 
-pub struct LoginWithEmailPasswdUseCase<Q, V, CP, TP> {
+pub struct LoginWithEmailPasswdUseCase<C, Q, V, CP, TP> {
     user_provider: Q,
     password_verifier: V,
     claims_provider: CP,
     token_provider: TP,
+    cancel_account_deletion_use_case: CancelAccountDeletionUseCase<C>,
 }
 
-impl<Q, V, CP, TP> LoginWithEmailPasswdUseCase<Q, V, CP, TP>
+impl<C, Q, V, CP, TP> LoginWithEmailPasswdUseCase<C, Q, V, CP, TP>
 where
+    C: CommandUserService,
     Q: QueryUserService,
     V: PasswordVerifierService,
     CP: JwtClaimsService,
@@ -41,24 +44,26 @@ where
     where
         T: JWTProviderFactory<Claims = CP, Tokens = TP>,
         P: VerifiesProviderFactory<PasswordVerifier = V>,
-        U: UserProviderFactory<QueryUser = Q>,
+        U: UserProviderFactory<QueryUser = Q, CommandUser = C>,
     {
         let claims_provider = jwtprovider_factory.claims_service();
         let token_provider = jwtprovider_factory.token_service();
         let password_verifier = verifies_provider_factory.password_verifier();
         let user_provider = user_provider_factory.query_user();
+        let cancel_account_deletion_use_case = CancelAccountDeletionUseCase::new(user_provider_factory);
         Self {
             user_provider,
             password_verifier,
             claims_provider,
             token_provider,
+            cancel_account_deletion_use_case,
         }
     }
 
     pub async fn execute(
         &self,
         dto: LoginEmailPasRequestDto,
-    ) -> Result<JwtResponseDto, String> {
+    ) -> Result<JwtResponseDto, AppError> {
         println!("1");
         let user = match self.user_provider.get_user_by_identifier(&dto.email, AUTH_TYPE).await {
             Ok(Some(user)) => user,
@@ -82,6 +87,11 @@ where
             Err(e) => return self.handler_error(e),
         };
 
+        let user = match self.cancel_account_deletion_use_case.execute(user).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
         let claims = match self.claims_provider.access_claims(&user) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
@@ -113,13 +123,8 @@ where
         })
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(JwtResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
     }
 }
 
@@ -166,13 +171,7 @@ mod tests {
 
         println!("result {:?}", result);
         
-        assert!(result.is_ok());
-
-        let is_correct = match result.unwrap() {
-            JwtResponseDto::Success{..} => true,
-            _ => false
-        };
-        assert!(is_correct)
+        assert!(matches!(result, Ok(JwtResponseDto::Success { .. })));
     }
 
 
@@ -204,17 +203,11 @@ mod tests {
 
         println!("result {:?}", result);
         
-        assert!(result.is_ok());
-
-        let is_correct_err = match result.unwrap() {
-            JwtResponseDto::Error{err_msg} => {
-                let error = AuthenticatorError::UserNotFound(email).client_message();
-                err_msg == error
-            },
-            _ => false
+        let Err(e) = result else {
+            panic!("unknown user is logged in");
         };
-
-        assert!(is_correct_err);
+        assert_eq!(e.status, 401);
+        assert_eq!(e.message, AuthenticatorError::UserNotFound(email).client_message());
     }
 
 
@@ -242,4 +235,36 @@ mod tests {
         
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    async fn login(hasura_client: &mut MockHasuraClientBuilder) -> Result<JwtResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factor = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = LoginWithEmailPasswdUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factor,
+            &jwtprovider_factory
+        );
+        action.execute(login_email_pas_request_dto()).await
+    }
+
+    #[tokio::test]
+    async fn inactive_user_is_not_logged_in() {
+        let result = login(MockHasuraClientBuilder::new().with_locked_user()).await;
+        assert!(matches!(result, Err(e) if e.status == 423 && e.code == "account_locked"));
+
+        let result = login(MockHasuraClientBuilder::new().with_pending_user()).await;
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "account_not_verified"));
+
+        let result = login(MockHasuraClientBuilder::new().with_disabled_user()).await;
+        assert!(matches!(result, Err(e) if e.code == "account_disabled"));
+    }
+
+    #[tokio::test]
+    async fn expired_lock_is_ignored() {
+        let result = login(MockHasuraClientBuilder::new().with_lock_expired_user()).await;
+        assert!(matches!(result, Ok(JwtResponseDto::Success { .. })));
+    }
+}
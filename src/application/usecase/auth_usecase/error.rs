@@ -15,6 +15,8 @@ pub enum AuthenticatorError {
     NotCorrectPassword,
     #[error("Refresh token is not verified")]
     NotCorrectRefreshToken,
+    #[error("Api Key {0} is expired or revoked")]
+    ApiKeyInactive(String),
 }
 
 impl AuthenticatorError {
@@ -54,6 +56,21 @@ impl AppErrorInfo for AuthenticatorError {
             AuthenticatorError::NotCorrectRefreshToken => {
                 format!("Refresh token is not verified")
             }
+            AuthenticatorError::ApiKeyInactive(identifier) => {
+                format!("Try create JWT with expired or revoked api key {}", identifier)
+            }
+        }
+    }
+
+    fn status_code(&self) -> u16 {
+        401
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AuthenticatorError::NotCorrectRefreshToken => "invalid_token",
+            AuthenticatorError::ApiKeyInactive(_) => "api_key_inactive",
+            _ => "invalid_credentials",
         }
     }
 }
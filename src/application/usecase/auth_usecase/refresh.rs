@@ -2,7 +2,8 @@ use uuid::Uuid;
 
 use crate::application::error_ext::ServiceErrorExt;
 use crate::application::usecase::auth_usecase::dto::{JwtResponseDto, RefreshTokenRequestDto};
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::API_KEY_AMR;
 use crate::domain::jwt::service::{JwtClaimsService, TokenService};
 use crate::domain::user::service::QueryUserService;
 use crate::domain::verifies::service::PasswordVerifierService;
@@ -56,7 +57,7 @@ where
         }
     }
 
-    pub async fn execute(&self, dto: RefreshTokenRequestDto) -> Result<JwtResponseDto, String> {
+    pub async fn execute(&self, dto: RefreshTokenRequestDto) -> Result<JwtResponseDto, AppError> {
         let refresh_claims =  match self.token_provider.validate_refresh(&dto.refresh_token){
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
@@ -72,20 +73,35 @@ where
             Err(e) => return self.handler_error(e),
         };
 
-        let Some(user) = user_data.first() else {
+        // Сессия пользователя не связана с его API ключами
+        let Some(user) = user_data.iter().find(|v| v.auth_type() != API_KEY_AMR) else {
             return self.handler_error(AuthenticatorError::UserNotFound(user_id.to_string()));
         };
 
-        let claims = match self.claims_provider.access_claims(&user) {
+        // После принудительного выхода старые сессии не продлеваются
+        if let Some(logged_out_at) = user.user().logged_out_at() {
+            let started_at = refresh_claims.auth_time.unwrap_or_default() as i64;
+            if started_at <= logged_out_at.timestamp() {
+                return self.handler_error(AuthenticatorError::NotCorrectRefreshToken);
+            }
+        }
+
+        let mut claims = match self.claims_provider.access_claims(&user) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
         };
 
-        let refresh_claims = match self.claims_provider.refresh_claims(&user) {
+        let mut new_refresh_claims = match self.claims_provider.refresh_claims(&user) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
         };
 
+        // Обновление токена - не повторный вход: auth_time остаётся прежним.
+        // У refresh токенов, выпущенных до появления auth_time, его нет - сессия считается давней
+        claims.auth_time = refresh_claims.auth_time;
+        new_refresh_claims.auth_time = refresh_claims.auth_time;
+        let refresh_claims = new_refresh_claims;
+
         let access_token = match self.token_provider.generate_access(claims) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
@@ -107,12 +123,146 @@ where
         
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(JwtResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::model::RefreshClaims;
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn refresh_keeps_auth_time() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new().with_email_auth_method().build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let auth_time = chrono::Utc::now().timestamp() as usize - 3600;
+        let exp = chrono::Utc::now().timestamp() as usize + 3600;
+        let refresh_token = jwtprovider_factory
+            .token_service()
+            .generate_refresh(
+                RefreshClaims::new("801bd045-a367-4683-9234-297586264e39".to_string(), exp)
+                    .with_auth_time(auth_time),
+            )
+            .unwrap();
+
+        let action = RefreshTokenUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let result = action.execute(RefreshTokenRequestDto { refresh_token }).await;
+
+        let Ok(JwtResponseDto::Success { auth_data }) = result else {
+            panic!("token is not refreshed");
+        };
+        let tokens = jwtprovider_factory.token_service();
+        let claims = tokens.validate_access(&auth_data.access_token).unwrap();
+        assert_eq!(claims.auth_time, Some(auth_time));
+        assert!(claims.iat.unwrap() > auth_time);
+        let refresh_claims = tokens.validate_refresh(&auth_data.refresh_token.unwrap()).unwrap();
+        assert_eq!(refresh_claims.auth_time, Some(auth_time));
+    }
+
+    async fn refresh(hasura_client: &mut MockHasuraClientBuilder, auth_time: usize) -> Result<JwtResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let exp = chrono::Utc::now().timestamp() as usize + 3600;
+        let refresh_token = jwtprovider_factory
+            .token_service()
+            .generate_refresh(
+                RefreshClaims::new("801bd045-a367-4683-9234-297586264e39".to_string(), exp)
+                    .with_auth_time(auth_time),
+            )
+            .unwrap();
+
+        let action = RefreshTokenUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        action.execute(RefreshTokenRequestDto { refresh_token }).await
+    }
+
+    #[tokio::test]
+    async fn session_before_logout_is_not_refreshed() {
+        let before_logout = chrono::DateTime::parse_from_rfc3339("2025-07-11T09:00:00+00:00").unwrap();
+        let after_logout = chrono::DateTime::parse_from_rfc3339("2025-07-11T11:00:00+00:00").unwrap();
+
+        let result = refresh(MockHasuraClientBuilder::new().with_logged_out_user(), before_logout.timestamp() as usize).await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+
+        let result = refresh(MockHasuraClientBuilder::new().with_logged_out_user(), after_logout.timestamp() as usize).await;
+        assert!(matches!(result, Ok(JwtResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn disabled_user_is_not_refreshed() {
+        let auth_time = chrono::Utc::now().timestamp() as usize - 60;
+
+        let result = refresh(MockHasuraClientBuilder::new().with_disabled_user(), auth_time).await;
+
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "account_disabled"));
+    }
+
+    #[tokio::test]
+    async fn session_is_not_refreshed_as_api_key() {
+        let auth_time = chrono::Utc::now().timestamp() as usize - 60;
+
+        let result = refresh(MockHasuraClientBuilder::new().with_user_api_keys(), auth_time).await;
+
+        let Ok(JwtResponseDto::Success { auth_data }) = result else {
+            panic!("token is not refreshed");
+        };
+        let claims = JWTProvider::new(Credentials::mock())
+            .token_service()
+            .validate_access(&auth_data.access_token)
+            .unwrap();
+        assert_eq!(claims.amr, Some(vec!["email".to_string()]));
+        assert_eq!(claims.auth_time, Some(auth_time));
+    }
+
+    #[tokio::test]
+    async fn refresh_without_auth_time_is_not_recent() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new().with_email_auth_method().build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let exp = chrono::Utc::now().timestamp() as usize + 3600;
+        let refresh_token = jwtprovider_factory
+            .token_service()
+            .generate_refresh(RefreshClaims::new("801bd045-a367-4683-9234-297586264e39".to_string(), exp))
+            .unwrap();
+
+        let action = RefreshTokenUseCase::new(
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let result = action.execute(RefreshTokenRequestDto { refresh_token }).await;
+
+        let Ok(JwtResponseDto::Success { auth_data }) = result else {
+            panic!("token is not refreshed");
+        };
+        let tokens = jwtprovider_factory.token_service();
+        assert_eq!(tokens.validate_access(&auth_data.access_token).unwrap().auth_time, None);
+        let refresh_claims = tokens.validate_refresh(&auth_data.refresh_token.unwrap()).unwrap();
+        assert_eq!(refresh_claims.auth_time, None);
     }
 }
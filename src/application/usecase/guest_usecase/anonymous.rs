@@ -0,0 +1,164 @@
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::auth_usecase::dto::{JwtResponseDto, TokenPairDto};
+use crate::application::usecase::invite_usecase::policy::SignUpPolicyUseCase;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::factories::JWTProviderFactory;
+use crate::domain::jwt::service::{JwtClaimsService, TokenService};
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::base::{AuthMethod, UserRole};
+use crate::domain::user::models::new_user::NewUser;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::constants::AUTH_TYPE;
+use super::error::GuestError;
+
+/// Гость с ролью `guest_role` (`POST /auth/anonymous`): получает id в Hasura до регистрации.
+/// Регистрация по email или вход через Telegram с токеном гостя сохраняют этот id.
+/// Гость - тоже новый пользователь, поэтому подчиняется `signup_mode`
+pub struct CreateGuestUseCase<CUS, QUS, CP, TP> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    sign_up_policy_use_case: SignUpPolicyUseCase<QUS>,
+    claims_provider: CP,
+    token_provider: TP,
+}
+
+impl<CUS, QUS, CP, TP> ServiceErrorExt for CreateGuestUseCase<CUS, QUS, CP, TP> {}
+
+impl<CUS, QUS, CP, TP> CreateGuestUseCase<CUS, QUS, CP, TP>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    CP: JwtClaimsService,
+    TP: TokenService,
+{
+    pub fn new<T, U>(credentials: Credentials, user_provider_factory: &U, jwtprovider_factory: &T) -> Self
+    where
+        T: JWTProviderFactory<Claims = CP, Tokens = TP>,
+        U: UserProviderFactory<CommandUser = CUS, QueryUser = QUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let sign_up_policy_use_case = SignUpPolicyUseCase::new(credentials.clone(), user_provider_factory);
+        let claims_provider = jwtprovider_factory.claims_service();
+        let token_provider = jwtprovider_factory.token_service();
+        Self { credentials, command_user_service, sign_up_policy_use_case, claims_provider, token_provider }
+    }
+
+    pub async fn execute(&self) -> Result<JwtResponseDto, AppError> {
+        let Some(guest_role) = self.credentials.guest_role().clone() else {
+            return self.handler_error(GuestError::AnonymousDisabled);
+        };
+        // Ни email, ни приглашения у гостя нет: в режимах кроме `open` гостей не создаём
+        if let Err(e) = self.sign_up_policy_use_case.check(None, None) {
+            return self.handler_error(e);
+        }
+
+        let auth_method = AuthMethod::new(NewUser::PENDING_ID, AUTH_TYPE.to_string(), Uuid::new_v4().to_string(), None);
+        let user_role = UserRole::new(true, guest_role, NewUser::PENDING_ID);
+        let new_user = NewUser::new(vec![auth_method], Vec::new(), vec![user_role]);
+
+        let guest = match self.command_user_service.create_user_with(new_user).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let claims = match self.claims_provider.access_claims(&guest) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let refresh_claims = match self.claims_provider.refresh_claims(&guest) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let access_token = match self.token_provider.generate_access(claims) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        // Секрета у гостя нет: refresh токен - единственный способ продлить сессию
+        let refresh_token = match self.token_provider.generate_refresh(refresh_claims) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let token_pair = TokenPairDto {
+            access_token,
+            refresh_token: Some(refresh_token),
+        };
+
+        Ok(JwtResponseDto::Success { auth_data: token_pair })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn guest_is_created_with_guest_role() {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_user_creation();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = CreateGuestUseCase::new(credentials, &user_provider_factory, &jwtprovider_factory);
+        let result = action.execute().await;
+
+        let Ok(JwtResponseDto::Success { auth_data }) = result else {
+            panic!("guest is not created");
+        };
+        assert!(auth_data.refresh_token.is_some());
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        let object = &body["variables"]["object"];
+        assert_eq!(object["auth_methods"]["data"][0]["auth_type"], "anonymous");
+        assert_eq!(object["auth_methods"]["data"][0]["secret"], serde_json::Value::Null);
+        assert_eq!(object["user_roles"]["data"][0]["role"], "guest");
+    }
+
+    #[tokio::test]
+    async fn guests_are_disabled() {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["guest_role"] = serde_json::Value::Null;
+        let credentials: Credentials = serde_json::from_value(credentials).unwrap();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = CreateGuestUseCase::new(credentials, &user_provider_factory, &jwtprovider_factory);
+        let result = action.execute().await;
+
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "anonymous_disabled"));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn guests_follow_signup_mode() {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["signup_mode"] = serde_json::json!("invite_only");
+        let credentials: Credentials = serde_json::from_value(credentials).unwrap();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = CreateGuestUseCase::new(credentials, &user_provider_factory, &jwtprovider_factory);
+        let result = action.execute().await;
+
+        assert!(matches!(result, Err(e) if e.code == "invite_required"));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+}
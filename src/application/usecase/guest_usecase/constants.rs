@@ -0,0 +1,2 @@
+/// Способ входа гостя: identifier случайный, секрета нет, вход только по refresh токену
+pub const AUTH_TYPE: &str = "anonymous";
@@ -0,0 +1,54 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone)]
+pub enum GuestError {
+    #[error("Anonymous users are disabled")]
+    AnonymousDisabled,
+    #[error("Guest token is not valid")]
+    InvalidToken,
+    #[error("User {0} is not a guest")]
+    NotGuest(String),
+    #[error("Failed to upgrade guest {0}: {1}")]
+    FailedUpgrade(String, String),
+}
+
+impl AppErrorInfo for GuestError {
+    fn client_message(&self) -> String {
+        match self {
+            GuestError::AnonymousDisabled => "Anonymous users are disabled".to_string(),
+            GuestError::InvalidToken => "Unauthorized".to_string(),
+            GuestError::NotGuest(_) => "User is already registered".to_string(),
+            GuestError::FailedUpgrade(..) => self.internal_error(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            GuestError::FailedUpgrade(..) => ErrorLevel::Error,
+            _ => ErrorLevel::Info,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("GuestError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            GuestError::AnonymousDisabled => 403,
+            GuestError::InvalidToken => 401,
+            GuestError::NotGuest(_) => 409,
+            GuestError::FailedUpgrade(..) => 500,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            GuestError::AnonymousDisabled => "anonymous_disabled",
+            GuestError::InvalidToken => "invalid_token",
+            GuestError::NotGuest(_) => "not_guest",
+            GuestError::FailedUpgrade(..) => "internal_error",
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod anonymous;
+pub mod constants;
+pub mod error;
+pub mod upgrade;
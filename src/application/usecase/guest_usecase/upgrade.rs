@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::domain::jwt::service::TokenService;
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::models::guest_upgrade::GuestUpgrade;
+use crate::domain::user::models::new_user::NewUser;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::constants::AUTH_TYPE;
+use super::error::GuestError;
+
+/// Id гостя из его access токена
+pub fn guest_id<TP: TokenService>(token_provider: &TP, token: &str) -> Result<Uuid, GuestError> {
+    let claims = token_provider.validate_access(token).map_err(|_| GuestError::InvalidToken)?;
+    Uuid::from_str(&claims.sub).map_err(|_| GuestError::InvalidToken)
+}
+
+/// Регистрация гостя: способы входа, атрибуты и роль добавляются к его id,
+/// после чего снимаются роль `guest_role` и способ входа `anonymous`.
+/// Id, роли Hasura и данные гостя сохраняются
+pub struct UpgradeGuestUseCase<CUS, QUS> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    query_user_service: QUS,
+}
+
+impl<CUS, QUS> UpgradeGuestUseCase<CUS, QUS>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, command_user_service, query_user_service }
+    }
+
+    /// Способ входа `anonymous` гостя. Зарегистрированный пользователь гостем не считается
+    pub async fn find_guest(&self, user_id: Uuid) -> Result<ExtendedAuthMethod, GuestError> {
+        let auth_methods = self
+            .query_user_service
+            .get_user_by_id(user_id)
+            .await
+            .map_err(|e| GuestError::FailedUpgrade(user_id.to_string(), e.to_string()))?;
+        if auth_methods.is_empty() {
+            return Err(GuestError::InvalidToken);
+        }
+        auth_methods
+            .into_iter()
+            .find(|v| v.auth_type() == AUTH_TYPE)
+            .ok_or(GuestError::NotGuest(user_id.to_string()))
+    }
+
+    /// `new_user` должен быть собран с id гостя. Возвращает первый добавленный способ входа.
+    /// Все изменения идут одной транзакцией: при ошибке гость остаётся гостем
+    pub async fn attach(&self, guest: &ExtendedAuthMethod, new_user: NewUser) -> Result<ExtendedAuthMethod, GuestError> {
+        let user_id = *guest.user_id();
+        let failed = |e: String| GuestError::FailedUpgrade(user_id.to_string(), e);
+
+        let upgrade = GuestUpgrade::plan(guest, new_user, self.credentials.guest_role().as_ref())
+            .ok_or_else(|| failed("no default role".to_string()))?;
+        let added = self.command_user_service.upgrade_guest(upgrade).await.map_err(|e| failed(e.to_string()))?;
+
+        let user = match self.query_user_service.get_user(user_id).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return Err(failed("user is not found after upgrade".to_string())),
+            Err(e) => return Err(failed(e.to_string())),
+        };
+        let Some(auth_method) = added.into_iter().next() else {
+            return Err(failed("no auth method is added".to_string()));
+        };
+        Ok(ExtendedAuthMethod::new(auth_method, user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::user::models::base::{AuthMethod, UserRole};
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn upgrade(hasura_client: &MockHasuraClientBuilder) -> UpgradeGuestUseCase<impl CommandUserService, impl QueryUserService> {
+        let credentials = Credentials::mock();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        UpgradeGuestUseCase::new(credentials, &user_provider_factory)
+    }
+
+    #[tokio::test]
+    async fn registered_user_is_not_guest() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_email_auth_method();
+
+        let result = upgrade(&hasura_client).find_guest(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(GuestError::NotGuest(_))));
+    }
+
+    #[tokio::test]
+    async fn guest_is_upgraded() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_guest();
+        let action = upgrade(&hasura_client);
+
+        let guest = action.find_guest(Uuid::new_v4()).await.unwrap();
+        let user_id = *guest.user_id();
+        let auth_method = AuthMethod::new(user_id, "email".to_string(), "guest@test.com".to_string(), None);
+        let user_role = UserRole::new(true, "default".to_string(), user_id);
+        let result = action.attach(&guest, NewUser::new(vec![auth_method], Vec::new(), vec![user_role])).await;
+
+        assert!(result.is_ok());
+        // Роли для токена берутся у пользователя после регистрации
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "GetUserById");
+    }
+}
@@ -1,11 +1,10 @@
 use crate::domain::user::models::base::User;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "notvalidtoken", ошибки - problem+json
 pub enum CheckTokenResponseDto {
     Success { user: User },
     NotValidToken,
-    Error { err_msg: String },
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
@@ -35,5 +35,13 @@ impl AppErrorInfo for CheckTokenError{
             CheckTokenError::AuthMethodNotValid(id) => format!("Auth method not valid {}", id)
         }
     }
+
+    fn status_code(&self) -> u16 {
+        401
+    }
+
+    fn error_code(&self) -> &'static str {
+        "invalid_api_key"
+    }
     
 }
\ No newline at end of file
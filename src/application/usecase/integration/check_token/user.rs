@@ -3,38 +3,43 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 use crate::application::error_ext::ServiceErrorExt;
-use crate::domain::errors::service::ErrorLevel;
+use crate::domain::errors::service::AppError;
 use crate::domain::{errors::service::AppErrorInfo, jwt::service::TokenService};
 use crate::domain::jwt::factories::JWTProviderFactory;
 
 use crate::domain::settings::model::Credentials;
 
 use crate::domain::user::models::base::User;
-use crate::domain::user::service::QueryUserService;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
 use crate::domain::user::factories::UserProviderFactory;
 
 use crate::domain::verifies::service::ApiKeyVerifierService;
 use crate::domain::verifies::factories::VerifiesProviderFactory;
 
+use crate::application::usecase::auth_usecase::error::AuthenticatorError;
+
 use super::dto::{CheckTokenRequestDto, CheckTokenResponseDto};
 use super::error::CheckTokenError;
 
 const AUTH_TYPE: &str = "apikey";
 
 
-pub struct CheckTokenUseCase<QUS, TS, AKV>{
+pub struct CheckTokenUseCase<CUS, QUS, TS, AKV>{
     credentials: Credentials,
+    command_user_service: CUS,
     query_user_service: QUS,
     token_service: TS,
     api_key_verifier: AKV,
 }
 
 
-impl<QUS, TS, AKV> ServiceErrorExt for CheckTokenUseCase<QUS, TS, AKV> {}
+impl<CUS, QUS, TS, AKV> ServiceErrorExt for CheckTokenUseCase<CUS, QUS, TS, AKV> {}
 
 
-impl <QUS, TS, AKV>CheckTokenUseCase<QUS, TS, AKV>
+impl <CUS, QUS, TS, AKV>CheckTokenUseCase<CUS, QUS, TS, AKV>
 where
+    CUS: CommandUserService,
     QUS: QueryUserService,
     TS: TokenService,
     AKV: ApiKeyVerifierService
@@ -49,48 +54,98 @@ where
     where
         T: JWTProviderFactory<Tokens = TS>,
         P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
-        U: UserProviderFactory<QueryUser = QUS>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
     {
+        let command_user_service = user_provider_factory.command_user();
         let query_user_service = user_provider_factory.query_user();
         let token_service = jwtprovider_factory.token_service();
         let api_key_verifier = verifies_provider_factory.api_key_verifier();
-        Self { credentials, query_user_service, token_service, api_key_verifier }
+        Self { credentials, command_user_service, query_user_service, token_service, api_key_verifier }
     }
-    pub async fn execute(&self, dto: CheckTokenRequestDto, api_key: String) -> Result<CheckTokenResponseDto, String> {
+    pub async fn execute(&self, dto: CheckTokenRequestDto, api_key: String) -> Result<CheckTokenResponseDto, AppError> {
         println!("1");
         let identifier = match self.api_key_verifier.extract_identifier(&api_key) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
         };
         println!("identifier {}", identifier);
-        let user = match self
-            .query_user_service
-            .get_user_by_identifier(&identifier, AUTH_TYPE)
-            .await
-        {
-            Ok(Some(v)) => v,
-            Ok(None) => return self.handler_error(CheckTokenError::UserNotFound(api_key)),
-            Err(e) => return self.handler_error(e),
-        };
-        println!("user {:?}", user);
+        // Сервис-клиент присылает свой ключ с каждым запросом - проверяем его не чаще раза за TTL
+        let user = match self.api_key_verifier.cached(&api_key) {
+            Some(v) => v,
+            None => {
+                let user = match self
+                    .query_user_service
+                    .get_user_by_identifier(&identifier, AUTH_TYPE)
+                    .await
+                {
+                    Ok(Some(v)) => v,
+                    Ok(None) => return self.handler_error(CheckTokenError::UserNotFound(api_key)),
+                    Err(e) => return self.handler_error(e),
+                };
 
-        let Some(api_key_hash) = user.secret() else {
-            return self.handler_error(CheckTokenError::AuthMethodNotValid(
-                identifier,
-            ));
-        };
+                let Some(api_key_hash) = user.secret() else {
+                    return self.handler_error(CheckTokenError::AuthMethodNotValid(
+                        identifier,
+                    ));
+                };
 
-        let is_verified = match self.api_key_verifier.is_verified(&api_key_hash, &api_key) {
-            Ok(v) => v,
-            Err(e) => return self.handler_error(e),
+                if !user.is_active(chrono::Utc::now()) {
+                    return self.handler_error(AuthenticatorError::ApiKeyInactive(identifier));
+                }
+
+                let is_verified = match self.api_key_verifier.is_verified(&api_key_hash, &api_key) {
+                    Ok(v) => v,
+                    Err(e) => return self.handler_error(e),
+                };
+
+                if !is_verified {
+                    return self.handler_error(CheckTokenError::AuthMethodNotValid(
+                        identifier,
+                    ));
+                }
+
+                if let Err(e) = self.command_user_service.touch_auth_method(*user.id()).await {
+                    self.map_service_error(e);
+                }
+
+                if self.api_key_verifier.needs_rehash(api_key_hash) {
+                    match self.api_key_verifier.create_hash(&api_key) {
+                        Ok(v) => {
+                            if let Err(e) = self.command_user_service.update_auth_method_secret(*user.id(), v).await {
+                                self.map_service_error(e);
+                            }
+                        }
+                        Err(e) => {
+                            self.map_service_error(e);
+                        }
+                    }
+                }
+
+                self.api_key_verifier.remember(&api_key, &user);
+                user
+            }
         };
 
-        if !is_verified {
-            return self.handler_error(CheckTokenError::AuthMethodNotValid(
-                identifier,
-            ));
+        // Отзыв ключа, смена ролей, отключение, выход и удаление владельца сбрасывают кэш (forget_user),
+        // а сроки - expires_at ключа и locked_until владельца - проверяем и для кэша
+        let now = chrono::Utc::now();
+        if !user.is_active(now) || !user.user().is_active(now) {
+            return self.handler_error(AuthenticatorError::ApiKeyInactive(identifier));
+        }
+
+        self.check(dto).await
+    }
+
+    /// Ключ уже проверен middleware по подписи запроса
+    pub async fn execute_signed(&self, dto: CheckTokenRequestDto, auth_method: ExtendedAuthMethod) -> Result<CheckTokenResponseDto, AppError> {
+        if let Err(e) = self.command_user_service.touch_auth_method(*auth_method.id()).await {
+            self.map_service_error(e);
         }
 
+        self.check(dto).await
+    }
+
+    async fn check(&self, dto: CheckTokenRequestDto) -> Result<CheckTokenResponseDto, AppError> {
         let claims = match self.token_service.validate_access(&dto.token) {
             Ok(v) => v,
             Err(e) => {
@@ -121,18 +176,159 @@ where
             None => return Ok(CheckTokenResponseDto::NotValidToken)
         };
 
+        // Токен отключённого или заблокированного пользователя больше не принимаем
+        if !auth_method.user().is_active(chrono::Utc::now()) {
+            return Ok(CheckTokenResponseDto::NotValidToken)
+        }
+
+        // Как и при refresh: токены, выданные до принудительного выхода, недействительны
+        if let Some(logged_out_at) = auth_method.user().logged_out_at() {
+            let issued_at = claims.iat.unwrap_or_default() as i64;
+            if issued_at <= logged_out_at.timestamp() {
+                return Ok(CheckTokenResponseDto::NotValidToken)
+            }
+        }
+
         let user = auth_method.user().as_base();
 
         Ok(CheckTokenResponseDto::Success { user: user })
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<CheckTokenResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(CheckTokenResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<CheckTokenResponseDto, AppError> {
+        Err(self.map_service_error(e))
     }
     
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::application::usecase::account_usecase::revoke_api_key::RevokeApiKeyUseCase;
+    use crate::application::usecase::account_usecase::unlink::UnlinkIdentityUseCase;
+    use crate::domain::jwt::model::Claims;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user::MockUser;
+    use crate::mock::user_provider::MockUserProvider;
+
+    const KEY_OWNER: &str = "801bd045-a367-4683-9234-297586264e39";
+
+    fn owner_claims(jwtprovider_factory: &JWTProvider) -> Claims {
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        claims.sub = KEY_OWNER.to_string();
+        claims.hasura_claims.x_hasura_user_id = KEY_OWNER.to_string();
+        claims
+    }
+
+    fn owner_token(jwtprovider_factory: &JWTProvider) -> String {
+        jwtprovider_factory.token_service().generate_access(owner_claims(jwtprovider_factory)).unwrap()
+    }
+
+    async fn check_token(
+        verifies_provider_factory: &VerifiesProvider,
+        hasura_client: &MockHasuraClientBuilder,
+    ) -> Result<CheckTokenResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = CheckTokenUseCase::new(
+            credentials,
+            &user_provider_factory,
+            verifies_provider_factory,
+            &jwtprovider_factory,
+        );
+        let dto = CheckTokenRequestDto { token: owner_token(&jwtprovider_factory) };
+        action.execute(dto, MockUser::api_key()).await
+    }
+
+    #[tokio::test]
+    async fn token_issued_before_logout_is_not_valid() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_logged_out_user().with_auth_method_touch();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let action = CheckTokenUseCase::new(
+            credentials,
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory,
+        );
+        let before_logout = chrono::DateTime::parse_from_rfc3339("2025-07-11T09:00:00+00:00").unwrap();
+        let token = |iat: usize| {
+            let claims = jwtprovider_factory
+                .claims_service()
+                .access_claims(&ExtendedAuthMethod::mock())
+                .unwrap()
+                .with_auth_time(iat, iat);
+            jwtprovider_factory.token_service().generate_access(claims).unwrap()
+        };
+
+        let dto = CheckTokenRequestDto { token: token(before_logout.timestamp() as usize) };
+        let result = action.execute_signed(dto, ExtendedAuthMethod::mock()).await;
+        assert!(matches!(result, Ok(CheckTokenResponseDto::NotValidToken)));
+
+        let dto = CheckTokenRequestDto { token: token(chrono::Utc::now().timestamp() as usize) };
+        let result = action.execute_signed(dto, ExtendedAuthMethod::mock()).await;
+        assert!(matches!(result, Ok(CheckTokenResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn revoked_api_key_is_not_taken_from_cache() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut active = MockHasuraClientBuilder::new();
+        active.with_apikey_auth_method().with_auth_method_touch();
+        let mut revoked = MockHasuraClientBuilder::new();
+        revoked.with_email_auth_method().with_revoked_apikey_auth_method().with_auth_method_revoke();
+
+        let result = check_token(&verifies_provider_factory, &active).await;
+        assert!(matches!(result, Ok(CheckTokenResponseDto::Success { .. })));
+
+        // До отзыва через сервис ключ принимается из кэша
+        let result = check_token(&verifies_provider_factory, &revoked).await;
+        assert!(matches!(result, Ok(CheckTokenResponseDto::Success { .. })));
+
+        let user_provider_factory = MockUserProvider::new(credentials, revoked.build());
+        let revoke = RevokeApiKeyUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let result = revoke
+            .execute(owner_claims(&jwtprovider_factory), "FNGFb2Px6cox1wvR98KmU6Fl8IXkBU1x".to_string())
+            .await;
+        assert!(result.is_ok());
+
+        let result = check_token(&verifies_provider_factory, &revoked).await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn unlinked_api_key_is_not_taken_from_cache() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut active = MockHasuraClientBuilder::new();
+        active.with_apikey_auth_method().with_auth_method_touch();
+        let mut removed = MockHasuraClientBuilder::new();
+        removed.with_user_api_keys().with_revoked_apikey_auth_method().with_api_key_removal();
+
+        let result = check_token(&verifies_provider_factory, &active).await;
+        assert!(matches!(result, Ok(CheckTokenResponseDto::Success { .. })));
+
+        let user_provider_factory = MockUserProvider::new(credentials, removed.build());
+        let unlink = UnlinkIdentityUseCase::new(&user_provider_factory, &verifies_provider_factory);
+        let api_key_id = Uuid::from_str("a11bd045-a367-4683-9234-293580264e39").unwrap();
+        let result = unlink.execute(owner_claims(&jwtprovider_factory), api_key_id).await;
+        assert!(result.is_ok());
+
+        let result = check_token(&verifies_provider_factory, &removed).await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+}
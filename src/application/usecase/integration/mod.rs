@@ -1,2 +1,4 @@
+#[cfg(feature = "telegram")]
 pub mod telegram;
-pub mod check_token;
\ No newline at end of file
+pub mod check_token;
+pub mod signed_request;
\ No newline at end of file
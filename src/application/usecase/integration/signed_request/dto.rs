@@ -0,0 +1,16 @@
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+
+/// Результат проверки подписи. Наружу не отдаётся - его читает middleware
+#[derive(Debug, Clone)]
+pub enum SignedRequestResponseDto {
+    Success { auth_method: Box<ExtendedAuthMethod> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedRequestDto {
+    /// Значение заголовка Authorization
+    pub authorization: String,
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
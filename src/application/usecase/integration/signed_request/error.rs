@@ -0,0 +1,34 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SignedRequestError {
+    #[error("Api key {0} not found")]
+    ApiKeyNotFound(String),
+    #[error("Api key {0} was created without a signing secret")]
+    SigningNotEnabled(String),
+    #[error("Signature of api key {0} is not verified")]
+    NotVerified(String),
+}
+
+impl AppErrorInfo for SignedRequestError {
+    fn client_message(&self) -> String {
+        "Request signature is not valid".to_string()
+    }
+
+    fn level(&self) -> ErrorLevel {
+        ErrorLevel::Info
+    }
+
+    fn log_message(&self) -> String {
+        format!("SignedRequestError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        401
+    }
+
+    fn error_code(&self) -> &'static str {
+        "invalid_signature"
+    }
+}
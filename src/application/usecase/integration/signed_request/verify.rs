@@ -0,0 +1,159 @@
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::auth_usecase::error::AuthenticatorError;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+use crate::domain::verifies::model::SignedRequest;
+use crate::domain::verifies::service::ApiKeyVerifierService;
+
+use super::dto::{SignedRequestDto, SignedRequestResponseDto};
+use super::error::SignedRequestError;
+
+const AUTH_TYPE: &str = "apikey";
+
+/// Проверка запроса, подписанного API ключом (`Authorization: AWR-HMAC-SHA256 ...`).
+/// Сам ключ клиент не передаёт, ищем его по идентификатору из подписи
+pub struct VerifySignedRequestUseCase<QUS, AKV> {
+    query_user_service: QUS,
+    api_key_verifier: AKV,
+}
+
+impl<QUS, AKV> ServiceErrorExt for VerifySignedRequestUseCase<QUS, AKV> {}
+
+impl<QUS, AKV> VerifySignedRequestUseCase<QUS, AKV>
+where
+    QUS: QueryUserService,
+    AKV: ApiKeyVerifierService,
+{
+    pub fn new<P, U>(user_provider_factory: &U, verifies_provider_factory: &P) -> Self
+    where
+        P: VerifiesProviderFactory<ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        Self { query_user_service, api_key_verifier }
+    }
+
+    pub async fn execute(&self, dto: SignedRequestDto) -> Result<SignedRequestResponseDto, AppError> {
+        let signature = match self.api_key_verifier.parse_signature(&dto.authorization) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let auth_method = match self
+            .query_user_service
+            .get_user_by_identifier(&signature.identifier, AUTH_TYPE)
+            .await
+        {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(SignedRequestError::ApiKeyNotFound(signature.identifier)),
+            Err(e) => return self.handler_error(e),
+        };
+
+        // Ключи отключённого или заблокированного владельца не действуют
+        let now = chrono::Utc::now();
+        if !auth_method.is_active(now) || !auth_method.user().is_active(now) {
+            return self.handler_error(AuthenticatorError::ApiKeyInactive(signature.identifier));
+        }
+
+        let Some(signing_secret) = auth_method.signing_secret() else {
+            return self.handler_error(SignedRequestError::SigningNotEnabled(signature.identifier));
+        };
+
+        let request = SignedRequest { method: dto.method, path: dto.path, body: dto.body };
+        match self.api_key_verifier.is_signature_verified(signing_secret, &signature, &request) {
+            Ok(true) => Ok(SignedRequestResponseDto::Success { auth_method: Box::new(auth_method) }),
+            Ok(false) => self.handler_error(SignedRequestError::NotVerified(signature.identifier)),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<SignedRequestResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::infrastructure::verifies::request_signature::{self, SCHEME};
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user::MockUser;
+    use crate::mock::user_provider::MockUserProvider;
+
+    const IDENTIFIER: &str = "FNGFb2Px6cox1wvR98KmU6Fl8IXkBU1x";
+
+    fn signed(path: &str, body: &[u8]) -> SignedRequestDto {
+        let timestamp = chrono::Utc::now().timestamp();
+        let request = SignedRequest { method: "POST".to_string(), path: path.to_string(), body: body.to_vec() };
+        let signing_key = request_signature::signing_key(&MockUser::api_key());
+        let signature = request_signature::sign(&signing_key, &request_signature::string_to_sign(timestamp, &request));
+
+        SignedRequestDto {
+            authorization: format!("{} Credential={}, Timestamp={}, Signature={}", SCHEME, IDENTIFIER, timestamp, signature),
+            method: request.method,
+            path: request.path,
+            body: request.body,
+        }
+    }
+
+    async fn verify(
+        hasura_client: &mut MockHasuraClientBuilder,
+        dto: SignedRequestDto,
+    ) -> Result<SignedRequestResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        VerifySignedRequestUseCase::new(&user_provider_factory, &verifies_provider_factory)
+            .execute(dto)
+            .await
+    }
+
+    #[tokio::test]
+    async fn signed_request_is_verified_once() {
+        let dto = signed("/auth/integration/checkjwt", b"{\"token\":\"abc\"}");
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_signed_apikey_auth_method();
+
+        let result = verify(&mut hasura_client, dto.clone()).await;
+        assert!(matches!(result, Ok(SignedRequestResponseDto::Success { auth_method }) if auth_method.identifier() == IDENTIFIER));
+
+        let result = verify(&mut hasura_client, dto).await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn changed_body_is_rejected() {
+        let mut dto = signed("/auth/integration/checkjwt?changed", b"{\"token\":\"abc\"}");
+        dto.body = b"{\"token\":\"xyz\"}".to_vec();
+
+        let result = verify(MockHasuraClientBuilder::new().with_signed_apikey_auth_method(), dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn key_without_signing_secret_is_rejected() {
+        let dto = signed("/auth/integration/checkjwt?legacy", b"");
+
+        let result = verify(MockHasuraClientBuilder::new().with_apikey_auth_method(), dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn revoked_key_is_rejected() {
+        let dto = signed("/auth/integration/checkjwt?revoked", b"");
+
+        let result = verify(MockHasuraClientBuilder::new().with_revoked_apikey_auth_method(), dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+}
@@ -1,108 +1,315 @@
-use crate::application::usecase::integration::telegram::dto::TelegramDataDTO; 
+use uuid::Uuid;
+
 use crate::domain::{
     jwt::factories::JWTProviderFactory,
     settings::model::Credentials,
+    telegram::model::TelegramUser,
     user::{
         factories::UserProviderFactory,
         models::{
-            base::{AuthMethod, UserAttribute, UserRole, User},
+            base::{AuthMethod, UserAttribute, UserRole},
             extended::{
                 ExtendedUser,
                 ExtendedAuthMethod
-            }
+            },
+            new_user::NewUser,
         },
-    service::CommandUserService}};
+    service::{CommandUserService, QueryUserService}}};
+
+use crate::application::usecase::attribute_usecase::validate::ValidateAttributesUseCase;
+use crate::application::usecase::guest_usecase::upgrade::UpgradeGuestUseCase;
+use crate::application::usecase::invite_usecase::policy::SignUpPolicyUseCase;
 
 use super::errors::{AddCredError, TelegramIntError};
 use super::constants::{AUTH_TYPE, TELEGRAM_USERNAME, TELEGRAM_LAST_NAME, TELEGRAM_FIRST_NAME};
 
-pub struct AddTelegramCredUseCase<CUS> {
+pub struct AddTelegramCredUseCase<CUS, QUS> {
     credentials: Credentials,
     command_user_service: CUS,
+    validate_attributes_use_case: ValidateAttributesUseCase<QUS>,
+    sign_up_policy_use_case: SignUpPolicyUseCase<QUS>,
+    upgrade_guest_use_case: UpgradeGuestUseCase<CUS, QUS>,
 }
-impl <CUS>AddTelegramCredUseCase <CUS>
+impl <CUS, QUS>AddTelegramCredUseCase <CUS, QUS>
 where
-    CUS: CommandUserService
+    CUS: CommandUserService,
+    QUS: QueryUserService,
 {
     pub fn new<U>(credentials: Credentials, user_provider_factory: &U,) -> Self 
     where
-        U: UserProviderFactory<CommandUser = CUS>,
+        U: UserProviderFactory<CommandUser = CUS, QueryUser = QUS>,
     {
         let command_user_service = user_provider_factory.command_user();
-        Self {credentials, command_user_service}
+        let validate_attributes_use_case = ValidateAttributesUseCase::new(user_provider_factory);
+        let sign_up_policy_use_case = SignUpPolicyUseCase::new(credentials.clone(), user_provider_factory);
+        let upgrade_guest_use_case = UpgradeGuestUseCase::new(credentials.clone(), user_provider_factory);
+        Self {credentials, command_user_service, validate_attributes_use_case, sign_up_policy_use_case, upgrade_guest_use_case}
     }
 
-    pub async fn execute(&self, user: User, dto: TelegramDataDTO) -> Result<ExtendedAuthMethod, TelegramIntError> {
-        let user_id = user.id();
+    /// Новый пользователь Telegram: создаётся вместе со способами входа,
+    /// атрибутами и ролью одной транзакцией, если это разрешает `signup_mode`.
+    /// С `guest` учётные данные добавляются гостю вместо создания пользователя
+    pub async fn execute_new_user(
+        &self,
+        profile: TelegramUser,
+        bot: &str,
+        invite: Option<&str>,
+        guest: Option<Uuid>,
+    ) -> Result<ExtendedAuthMethod, TelegramIntError> {
+        self.sign_up_policy_use_case
+            .check(None, invite)
+            .map_err(|e| TelegramIntError::from(AddCredError::SignUpNotAllowed(e)))?;
 
-        let auth_method_by_id = AuthMethod::new(
-            user_id.clone(),
-            AUTH_TYPE.to_string(),
-            dto.id.to_string(),
-            None
-        );
+        let guest = match guest {
+            Some(user_id) => Some(
+                self.upgrade_guest_use_case
+                    .find_guest(user_id)
+                    .await
+                    .map_err(|e| TelegramIntError::from(AddCredError::Guest(e)))?,
+            ),
+            None => None,
+        };
+        let guest_id = guest.as_ref().map(|v| *v.user_id());
+        let user_id = guest_id.unwrap_or(NewUser::PENDING_ID);
 
-        let auth_method_by_username = AuthMethod::new(
-            user_id.clone(),
-            AUTH_TYPE.to_string(),
-            dto.username.clone(),
-            None
-        );
+        let mut new_user = self.credentials_for(user_id, profile, bot);
+        self.validate(guest_id, &new_user).await?;
 
-        let Ok(auth_method) = self.command_user_service.add_auth_method(auth_method_by_id.clone()).await else {
-            return Err(TelegramIntError::from(AddCredError::FailedAddingAuthMethod(auth_method_by_id.identifier().clone())));
-        };
+        // Приглашение списывается той же мутацией, что создаёт пользователя
+        let invite = self
+            .sign_up_policy_use_case
+            .invite(invite)
+            .await
+            .map_err(|e| TelegramIntError::from(AddCredError::SignUpNotAllowed(e)))?;
+        if let Some(role) = invite.as_ref().and_then(|v| v.role().clone()) {
+            new_user.roles = vec![UserRole::new(true, role, user_id)];
+        }
+        new_user.invite = invite.map(|v| v.code().clone());
 
-        if let Err(e) = self.command_user_service.add_auth_method(auth_method_by_username).await {
-            return Err(TelegramIntError::from(AddCredError::FailedAddingAuthMethod(e.to_string())));
+        if let Some(guest) = guest.as_ref() {
+            return self
+                .upgrade_guest_use_case
+                .attach(guest, new_user)
+                .await
+                .map_err(|e| TelegramIntError::from(AddCredError::Guest(e)));
         }
 
-        let mut user_attributes = vec![
-            UserAttribute::new(
-                user_id.clone(),
-                TELEGRAM_USERNAME.to_string(),
-                dto.username.clone(),
-            )
+        self.command_user_service
+            .create_user_with(new_user)
+            .await
+            .map_err(|e| TelegramIntError::from(AddCredError::FailedCreatingUser(e.to_string())))
+    }
+
+    /// Привязка Telegram к существующему пользователю: способы входа, атрибуты
+    /// и недостающая роль добавляются одной транзакцией
+    pub async fn execute(&self, mut user: ExtendedUser, profile: TelegramUser, bot: &str) -> Result<ExtendedAuthMethod, TelegramIntError> {
+        let user_id = *user.id();
+        let mut credentials = self.credentials_for(user_id, profile, bot);
+        self.validate(Some(user_id), &credentials).await?;
+
+        // Роль по умолчанию у пользователя уже есть, новая добавляется без неё
+        credentials.roles = credentials
+            .roles
+            .into_iter()
+            .filter(|role| !user.user_roles().iter().any(|v| v.role() == role.role()))
+            .map(|role| UserRole::new(false, role.role().clone(), user_id))
+            .collect();
+        let NewUser { attributes, roles, .. } = credentials.clone();
+
+        let added = self
+            .command_user_service
+            .link_credentials(user_id, credentials)
+            .await
+            .map_err(|e| TelegramIntError::from(AddCredError::FailedAddingAuthMethod(e.to_string())))?;
+
+        // Первым идёт способ входа по Telegram id, его и возвращаем
+        let Some(auth_method) = added.into_iter().next() else {
+            return Err(TelegramIntError::from(AddCredError::FailedAddingAuthMethod(user_id.to_string())));
+        };
+        roles.into_iter().for_each(|v| user.add_role(v));
+        attributes.into_iter().for_each(|v| user.set_attribute(v));
+        Ok(ExtendedAuthMethod::new(auth_method, user))
+    }
+
+    /// Способы входа (по id и по username), атрибуты профиля и роль нового пользователя
+    fn credentials_for(&self, user_id: Uuid, profile: TelegramUser, bot: &str) -> NewUser {
+        let mut auth_methods = vec![
+            AuthMethod::new(user_id, AUTH_TYPE.to_string(), profile.id.to_string(), None)
+                .with_provider(Some(bot.to_string())),
         ];
 
-        if let Some(first_name) = dto.first_name {
-            user_attributes.push(
-                UserAttribute::new(
-                    user_id.clone(),
-                    TELEGRAM_FIRST_NAME.to_string(),
-                    first_name
-                )
+        // Username в Telegram необязателен, через бота он может не прийти
+        if let Some(username) = profile.username.as_ref() {
+            auth_methods.push(
+                AuthMethod::new(user_id, AUTH_TYPE.to_string(), username.clone(), None)
+                    .with_provider(Some(bot.to_string())),
             );
         }
 
-        if let Some(last_name) = dto.last_name {
-            user_attributes.push(
-                UserAttribute::new(
-                    user_id.clone(),
-                    TELEGRAM_LAST_NAME.to_string(),
-                    last_name
-                )
-            );
+        let attributes = [
+            (TELEGRAM_USERNAME, profile.username),
+            (TELEGRAM_FIRST_NAME, profile.first_name),
+            (TELEGRAM_LAST_NAME, profile.last_name),
+        ]
+        .into_iter()
+        .filter_map(|(attribute, value)| Some((attribute, value?.trim().to_string())))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(attribute, value)| UserAttribute::new(user_id, attribute.to_string(), value))
+        .collect::<Vec<_>>();
+
+        let user_role = UserRole::new(true, self.credentials.new_user_role().with_telegram().clone(), user_id);
+
+        NewUser::new(auth_methods, attributes, vec![user_role])
+    }
+
+    /// Обязательные атрибуты Telegram не заполнит, проверяются только его значения
+    async fn validate(&self, user_id: Option<Uuid>, new_user: &NewUser) -> Result<(), TelegramIntError> {
+        let checked = new_user
+            .attributes
+            .iter()
+            .map(|v| (v.attribute().as_str(), v.value().as_str()))
+            .collect::<Vec<_>>();
+        self.validate_attributes_use_case
+            .execute(user_id, &checked)
+            .await
+            .map_err(|e| TelegramIntError::from(AddCredError::InvalidAttributes(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::errors::service::{AppErrorInfo, FieldError};
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn taken_telegram_username_is_rejected() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema().with_taken_attribute_value().with_user_creation();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let user = ExtendedAuthMethod::mock().user().clone();
+        let profile = TelegramUser {
+            id: 42,
+            first_name: Some("Mock".to_string()),
+            last_name: None,
+            username: Some("mock".to_string()),
         };
 
-        if let Err(e) = self.command_user_service.add_user_attribute(user_attributes).await {
-            return Err(TelegramIntError::from(AddCredError::FailedAddingUserAttribute(e.to_string())));
+        let action = AddTelegramCredUseCase::new(credentials, &user_provider_factory);
+        let result = action.execute(user, profile, "TEST").await;
+
+        let Err(e) = result else {
+            panic!("taken telegram username is accepted");
         };
+        assert_eq!(e.status_code(), 409);
+        assert_eq!(e.field_errors(), vec![FieldError::new(TELEGRAM_USERNAME, "not_unique")]);
+        let request = hasura_client.recorder().read_data().await.unwrap();
+        assert!(request.contains("CheckAttributeValue"));
+    }
 
-        let user_role = UserRole::new(
-            true,
-            self.credentials.new_user_role().with_email().clone(),
-            user_id.clone(),
+    #[tokio::test]
+    async fn new_user_is_created_by_one_mutation() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema().with_user_creation();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let profile = TelegramUser {
+            id: 42,
+            first_name: Some("Mock".to_string()),
+            last_name: None,
+            username: Some("mock".to_string()),
+        };
+
+        let action = AddTelegramCredUseCase::new(credentials, &user_provider_factory);
+        let result = action.execute_new_user(profile, "TEST", None, None).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "InsertUserWith");
+        let auth_methods = &body["variables"]["object"]["auth_methods"]["data"];
+        assert_eq!(auth_methods[0]["identifier"], "42");
+        assert_eq!(auth_methods[1]["identifier"], "mock");
+        assert_eq!(auth_methods[1]["provider"], "TEST");
+        assert_eq!(body["variables"]["object"]["user_roles"]["data"][0]["role"], "@TEST");
+    }
+
+    #[tokio::test]
+    async fn existing_user_is_linked_by_one_mutation() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema().with_user_creation();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let user = ExtendedAuthMethod::mock().user().clone();
+        let profile = TelegramUser {
+            id: 42,
+            first_name: Some("Mock".to_string()),
+            last_name: None,
+            username: None,
+        };
+
+        let action = AddTelegramCredUseCase::new(credentials, &user_provider_factory);
+        let result = action.execute(user.clone(), profile, "TEST").await.unwrap();
+
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "LinkCredentials");
+        assert_eq!(body["variables"]["attribute_names"], serde_json::json!([TELEGRAM_FIRST_NAME]));
+        assert_eq!(
+            body["variables"]["roles"],
+            serde_json::json!([{ "user_id": user.id(), "role": "@TEST", "is_default": false }])
         );
+        // Роли и состояние пользователя сохраняются
+        assert_eq!(result.user().user_roles().len(), user.user_roles().len() + 1);
+        assert_eq!(result.user().status(), user.status());
+    }
 
-        if let Err(e) = self.command_user_service.add_role(user_role.clone()).await{
-            return Err(TelegramIntError::from(AddCredError::FailedAddingUserRole(e.to_string())));
-        }
-        
-        let mut extended = ExtendedUser::new(user.id().clone(), user.created_at().clone(), user.updated_at().clone());
+    #[tokio::test]
+    async fn invite_role_is_assigned_to_new_user() {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["signup_mode"] = serde_json::json!("allow_list");
+        let credentials: Credentials = serde_json::from_value(credentials).unwrap();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_attribute_schema().with_user_creation().with_valid_invite();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let profile = TelegramUser { id: 42, first_name: None, last_name: None, username: None };
+
+        let action = AddTelegramCredUseCase::new(credentials, &user_provider_factory);
+        let without_invite = action.execute_new_user(profile.clone(), "TEST", None, None).await;
+        let result = action.execute_new_user(profile, "TEST", Some("beta-tester"), None).await;
 
-        extended.add_role(user_role);
+        assert!(matches!(without_invite, Err(e) if e.error_code() == "invite_required"));
+        assert!(result.is_ok());
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        let roles = &body["variables"]["object"]["user_roles"]["data"];
+        assert_eq!(roles.as_array().unwrap().len(), 1);
+        assert_eq!(roles[0]["role"], "test");
+        assert_eq!(body["variables"]["invite"], "beta-tester");
+    }
+
+    #[tokio::test]
+    async fn guest_keeps_user_id() {
+        let credentials = Credentials::mock();
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_guest().with_attribute_schema();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        let profile = TelegramUser { id: 42, first_name: None, last_name: None, username: None };
+
+        let action = AddTelegramCredUseCase::new(credentials, &user_provider_factory);
+        let guest_id = Uuid::new_v4();
+        let result = action.execute_new_user(profile, "TEST", None, Some(guest_id)).await;
 
-        Ok(ExtendedAuthMethod::new(auth_method, extended))
+        let Ok(extended_auth_method) = result else {
+            panic!("guest is not upgraded");
+        };
+        // Id берётся из users.user гостя, а не создаётся заново
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "GetUserById");
+        assert_ne!(extended_auth_method.user_id(), &NewUser::PENDING_ID);
     }
-}
\ No newline at end of file
+}
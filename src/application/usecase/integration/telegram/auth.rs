@@ -2,10 +2,12 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::account_usecase::cancel_deletion::CancelAccountDeletionUseCase;
 use crate::application::usecase::auth_usecase::dto::{JwtResponseDto, TokenPairDto};
+use crate::application::usecase::guest_usecase::upgrade::guest_id;
 use crate::application::usecase::integration::telegram::errors::LinkAccountError;
 
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
 
 use crate::domain::jwt::service::{JwtClaimsService, TokenService};
 use crate::domain::jwt::factories::JWTProviderFactory;
@@ -24,18 +26,19 @@ use crate::domain::verifies::factories::VerifiesProviderFactory;
 
 use super::dto::TelegramDataDTO;
 use super::add_cred::AddTelegramCredUseCase;
+use super::find_user::FindTelegramUserUseCase;
 
-use super::constants::{AUTH_TYPE, TELEGRAM_USERNAME, TELEGRAM_LAST_NAME, TELEGRAM_FIRST_NAME};
 
 
 pub struct AuthTelegramUseCase<CUS, QUS, V, CP, TP> {
     credentials: Credentials,
-    command_user_service: CUS,
     query_user_service: QUS,
     telegram_verifier: V,
     claims_provider: CP,
     token_provider: TP,
-    add_telegram_cred_use_case: AddTelegramCredUseCase<CUS>,
+    add_telegram_cred_use_case: AddTelegramCredUseCase<CUS, QUS>,
+    find_telegram_user_use_case: FindTelegramUserUseCase<QUS>,
+    cancel_account_deletion_use_case: CancelAccountDeletionUseCase<CUS>,
 }
 
 impl<CUS, QUS, V, CP, TP> ServiceErrorExt for AuthTelegramUseCase<CUS, QUS, V, CP, TP> {}
@@ -62,40 +65,40 @@ where
         let claims_provider = jwtprovider_factory.claims_service();
         let token_provider = jwtprovider_factory.token_service();
         let telegram_verifier = verifies_provider_factory.telegram_verifier();
-        let command_user_service = user_provider_factory.command_user();
         let query_user_service = user_provider_factory.query_user();
         let add_telegram_cred_use_case = AddTelegramCredUseCase::new(credentials.clone(), user_provider_factory);
+        let find_telegram_user_use_case = FindTelegramUserUseCase::new(credentials.clone(), user_provider_factory);
+        let cancel_account_deletion_use_case = CancelAccountDeletionUseCase::new(user_provider_factory);
         Self {
             credentials,
-            command_user_service,
             query_user_service,
             telegram_verifier,
             claims_provider,
             token_provider,
-            add_telegram_cred_use_case
+            add_telegram_cred_use_case,
+            find_telegram_user_use_case,
+            cancel_account_deletion_use_case,
         }
     }
 
-    pub async fn execute(&self, dto: TelegramDataDTO) -> Result<JwtResponseDto, String> {
-        let extended_auth_method = match self.query_user_service.get_user_by_identifier(&dto.id.to_string(), AUTH_TYPE).await {
-            Ok(Some(user)) => user,
-            Ok(None) => {
-                let user = match self.command_user_service.add_user().await{
-                    Ok(v) => v,
-                    Err(e) => return self.handler_error(e)
-                };
-                let extended_auth_method = match self.add_telegram_cred_use_case.execute(user, dto.clone()).await {
-                    Ok(v) => v,
-                    Err(e) => return self.handler_error(e)
-                };
-                extended_auth_method
-            },
-            Err(e) => return self.handler_error(e)
-        };
+    /// `invite` нужен только для создания пользователя при закрытой регистрации.
+    /// `guest_token` - access токен гостя: новый пользователь Telegram становится этим гостем
+    pub async fn execute(
+        &self,
+        dto: TelegramDataDTO,
+        bot: Option<String>,
+        invite: Option<String>,
+        guest_token: Option<String>,
+    ) -> Result<JwtResponseDto, AppError> {
+        // Подпись проверяем до любых обращений к пользователям,
+        // иначе неподписанный payload создаст пользователя
+        let telegram_data: TelegramData = dto.clone().into();
 
-        let telegram_data: TelegramData = dto.into();
+        let Some(bot) = self.credentials.telegram_bot(bot.as_deref()).map(|v| v.name().clone()) else {
+            return self.handler_error(LinkAccountError::UnknownBot(bot.unwrap_or_default()));
+        };
 
-        let is_verified = match self.telegram_verifier.is_verified(telegram_data.clone()) {
+        let is_verified = match self.telegram_verifier.is_verified(Some(&bot), telegram_data.clone()) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
         };
@@ -104,7 +107,26 @@ where
             return self.handler_error(LinkAccountError::NotVerified);
         }
 
-        
+        let guest = match guest_token.map(|v| guest_id(&self.token_provider, &v)).transpose() {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let extended_auth_method = match self.find_telegram_user_use_case.execute(dto.id, &bot).await {
+            Ok(Some(user)) => match self.cancel_account_deletion_use_case.execute(user).await {
+                Ok(v) => v,
+                Err(e) => return self.handler_error(e),
+            },
+            Ok(None) => {
+                let extended_auth_method = match self.add_telegram_cred_use_case.execute_new_user(dto.into(), &bot, invite.as_deref(), guest).await {
+                    Ok(v) => v,
+                    Err(e) => return self.handler_error(e)
+                };
+                extended_auth_method
+            },
+            Err(e) => return self.handler_error(e)
+        };
+
         let claims = match self.claims_provider.access_claims(&extended_auth_method) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
@@ -115,6 +137,11 @@ where
             Err(e) => return self.handler_error(e),
         };
 
+        // Данные запоминаются только после успешного входа, иначе неудачный вход нельзя повторить
+        if let Err(e) = self.telegram_verifier.remember(&telegram_data) {
+            return self.handler_error(e);
+        }
+
         let token_pair = TokenPairDto {
             access_token,
             refresh_token: None, // Telegram integration may not require refresh tokens
@@ -123,13 +150,8 @@ where
         Ok(JwtResponseDto::Success { auth_data: token_pair })
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(JwtResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
     }
 
     async fn get_user_by_jwt(&self, token: String) -> Result<ExtendedUser, ()> {
@@ -151,3 +173,118 @@ where
     
 }
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+    use crate::mock::telegram::MockTelegramData;
+
+    #[tokio::test]
+    async fn unsigned_payload_does_not_create_user() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        // Ответы на InsertUser не заданы: если пользователь создаётся, use case вернёт Err
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_auth_method_not_found()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = AuthTelegramUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+
+        let mut dto = MockTelegramData::signed(credentials.telegram_bot(None).unwrap().token());
+        dto.hash = "00".repeat(32);
+
+        let result = action.execute(dto, None, None, None).await;
+
+        let Err(e) = result else {
+            panic!("not verified data is accepted");
+        };
+        assert_eq!(e.status, 401);
+        assert_eq!(e.message, "Telegram data is not verified");
+    }
+
+    #[tokio::test]
+    async fn signed_payload_of_existing_user() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = AuthTelegramUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let bot_token = credentials.telegram_bot(None).unwrap().token();
+
+        let result = action.execute(MockTelegramData::signed(bot_token), None, None, None).await;
+
+        assert!(matches!(result, Ok(JwtResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn invalid_guest_token_is_rejected() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = AuthTelegramUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let bot_token = credentials.telegram_bot(None).unwrap().token();
+
+        let result = action.execute(MockTelegramData::signed(bot_token), None, None, Some("token".to_string())).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code == "invalid_token"));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn failed_login_can_be_retried() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = AuthTelegramUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let dto = MockTelegramData::signed(credentials.telegram_bot(None).unwrap().token());
+
+        let failed = action.execute(dto.clone(), None, None, Some("token".to_string())).await;
+        let retried = action.execute(dto.clone(), None, None, None).await;
+        let replayed = action.execute(dto, None, None, None).await;
+
+        assert!(matches!(failed, Err(e) if e.code == "invalid_token"));
+        assert!(matches!(retried, Ok(JwtResponseDto::Success { .. })));
+        assert!(matches!(replayed, Err(e) if e.code == "telegram_not_verified"));
+    }
+}
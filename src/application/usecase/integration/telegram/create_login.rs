@@ -0,0 +1,57 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::telegram::factories::TelegramProviderFactory;
+use crate::domain::telegram::service::LoginSessionService;
+
+use super::dto::TelegramLoginSessionResponseDto;
+use super::errors::DeepLinkError;
+
+/// Начало входа через бота: сессия и ссылка `t.me/<bot>?start=<nonce>`
+pub struct CreateTelegramLoginUseCase<S> {
+    credentials: Credentials,
+    login_session: S,
+}
+
+impl<S> ServiceErrorExt for CreateTelegramLoginUseCase<S> {}
+
+impl<S> CreateTelegramLoginUseCase<S>
+where
+    S: LoginSessionService,
+{
+    pub fn new<F>(credentials: Credentials, telegram_provider_factory: &F) -> Self
+    where
+        F: TelegramProviderFactory<LoginSession = S>,
+    {
+        let login_session = telegram_provider_factory.login_session();
+        Self { credentials, login_session }
+    }
+
+    pub async fn execute(&self, bot: Option<String>) -> Result<TelegramLoginSessionResponseDto, AppError> {
+        let Some(telegram_bot) = self.credentials.telegram_bot(bot.as_deref()) else {
+            return self.handler_error(DeepLinkError::UnknownBot(bot.unwrap_or_default()));
+        };
+
+        let Some(username) = telegram_bot.username() else {
+            return self.handler_error(DeepLinkError::NoBotUsername(telegram_bot.name().clone()));
+        };
+
+        let session = match self.login_session.create(telegram_bot.name()) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        Ok(TelegramLoginSessionResponseDto::Success {
+            deep_link: format!("https://t.me/{}?start={}", username, session.nonce),
+            nonce: session.nonce,
+            secret: session.secret,
+            code: session.code,
+            expires_at: session.expires_at,
+        })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<TelegramLoginSessionResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
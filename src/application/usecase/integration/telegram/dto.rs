@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use crate::application::usecase::auth_usecase::dto::TokenPairDto;
+use crate::domain::telegram::model::TelegramUser;
 use crate::domain::verifies::model::TelegramData;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -9,11 +13,22 @@ pub struct TelegramDataDTO {
     pub photo_url: Option<String>,
     pub auth_date: i64,
     pub hash: String,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
 }
 
 
 impl Into<TelegramData> for TelegramDataDTO {
     fn into(self) -> TelegramData {
+        let extra = self
+            .extra
+            .into_iter()
+            .map(|(k, v)| match v {
+                serde_json::Value::String(s) => (k, s),
+                other => (k, other.to_string()),
+            })
+            .collect();
+
         TelegramData {
             id: self.id,
             first_name: self.first_name,
@@ -22,8 +37,104 @@ impl Into<TelegramData> for TelegramDataDTO {
             photo_url: self.photo_url,
             auth_date: self.auth_date,
             hash: self.hash,
+            extra,
+        }
+    }
+}
+
+impl From<TelegramDataDTO> for TelegramUser {
+    fn from(dto: TelegramDataDTO) -> Self {
+        TelegramUser {
+            id: dto.id,
+            first_name: dto.first_name,
+            last_name: dto.last_name,
+            username: Some(dto.username),
         }
     }
 }
 
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum TelegramLoginSessionResponseDto {
+    /// `secret` нужен для обмена и не должен попадать в deep link.
+    /// `code` приложение показывает пользователю: бот просит сверить его перед подтверждением
+    Success { nonce: String, secret: String, code: String, deep_link: String, expires_at: i64 },
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct TelegramLoginExchangeRequestDto {
+    pub nonce: String,
+    /// `secret` из ответа на создание сессии
+    pub secret: String,
+    /// Код приглашения, если пользователь ещё не зарегистрирован
+    #[serde(default)]
+    pub invite: Option<String>,
+    /// Access токен гостя из `POST /auth/anonymous`
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "pending", "success", ошибки - problem+json
+pub enum TelegramLoginExchangeResponseDto {
+    /// Пользователь ещё не подтвердил вход в боте
+    Pending,
+    Success { auth_data: TokenPairDto },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+pub enum TelegramWebhookResponseDto {
+    Success,
+    Error { err_msg: String },
+}
+
+/// Update из Bot API, разбираем только то, что нужно для `/start <nonce>`
+/// и нажатия кнопки подтверждения
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TelegramUpdateDTO {
+    pub update_id: i64,
+    pub message: Option<TelegramMessageDTO>,
+    #[serde(default)]
+    pub callback_query: Option<TelegramCallbackQueryDTO>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TelegramCallbackQueryDTO {
+    pub from: TelegramUserDTO,
+    /// Сообщение с кнопкой, может отсутствовать для старых сообщений
+    pub message: Option<TelegramMessageDTO>,
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TelegramMessageDTO {
+    pub chat: TelegramChatDTO,
+    pub from: Option<TelegramUserDTO>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TelegramChatDTO {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TelegramUserDTO {
+    pub id: i64,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub username: Option<String>,
+}
+
+impl From<TelegramUserDTO> for TelegramUser {
+    fn from(dto: TelegramUserDTO) -> Self {
+        TelegramUser {
+            id: dto.id,
+            first_name: dto.first_name,
+            last_name: dto.last_name,
+            username: dto.username,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt::format;
 
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::application::usecase::attribute_usecase::error::AttributeError;
+use crate::application::usecase::guest_usecase::error::GuestError;
+use crate::application::usecase::invite_usecase::error::InviteError;
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel, FieldError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -33,6 +36,34 @@ impl AppErrorInfo for TelegramIntError{
             TelegramIntError::AddCred(e) => e.log_message(),
         }
     }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            TelegramIntError::LinkAccount(e) => e.status_code(),
+            TelegramIntError::AddCred(e) => e.status_code(),
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            TelegramIntError::LinkAccount(e) => e.error_code(),
+            TelegramIntError::AddCred(e) => e.error_code(),
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            TelegramIntError::LinkAccount(e) => e.message_args(),
+            TelegramIntError::AddCred(e) => e.message_args(),
+        }
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            TelegramIntError::LinkAccount(e) => e.field_errors(),
+            TelegramIntError::AddCred(e) => e.field_errors(),
+        }
+    }
     
 }
 
@@ -47,6 +78,16 @@ pub enum LinkAccountError {
     UserNotFound(String),
     #[error("User doesn't have telegram credentials")]
     NoTelegramCreds,
+    #[error("Telegram bot is not configured: {0}")]
+    UnknownBot(String),
+    #[error("Telegram account belongs to another user")]
+    OwnedByAnotherUser,
+    #[error("Telegram account is not linked to any user")]
+    NothingToMerge,
+    #[error("Access token is not verified")]
+    NotCorrectAccessToken,
+    #[error("Session is older than {max_age_minutes} minutes (auth_time: {auth_time:?})")]
+    ReauthenticationRequired { auth_time: Option<usize>, max_age_minutes: i64 },
 }
 
 
@@ -56,6 +97,11 @@ impl AppErrorInfo for LinkAccountError {
             LinkAccountError::NotVerified => "Telegram data is not verified".to_string(),
             LinkAccountError::UserNotFound(v) => format!("User not found by: {}", v),
             LinkAccountError::NoTelegramCreds => "User doesn't have telegram credentials".to_string(),
+            LinkAccountError::UnknownBot(v) => format!("Telegram bot is not configured: {}", v),
+            LinkAccountError::OwnedByAnotherUser => "Telegram account belongs to another user, merge accounts to link it".to_string(),
+            LinkAccountError::NothingToMerge => "Telegram account is not linked to any user, link it instead".to_string(),
+            LinkAccountError::NotCorrectAccessToken => "Unauthorized".to_string(),
+            LinkAccountError::ReauthenticationRequired { .. } => "Recent authentication required".to_string(),
         }
     }
 
@@ -66,6 +112,37 @@ impl AppErrorInfo for LinkAccountError {
     fn log_message(&self) -> String {
         self.client_message()
     }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            LinkAccountError::NotVerified
+            | LinkAccountError::NotCorrectAccessToken
+            | LinkAccountError::ReauthenticationRequired { .. } => 401,
+            LinkAccountError::UserNotFound(_) | LinkAccountError::UnknownBot(_) => 404,
+            LinkAccountError::NoTelegramCreds => 400,
+            LinkAccountError::OwnedByAnotherUser | LinkAccountError::NothingToMerge => 409,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            LinkAccountError::NotVerified => "telegram_not_verified",
+            LinkAccountError::UserNotFound(_) => "user_not_found",
+            LinkAccountError::NoTelegramCreds => "telegram_not_linked",
+            LinkAccountError::UnknownBot(_) => "unknown_bot",
+            LinkAccountError::OwnedByAnotherUser => "telegram_owned_by_another_user",
+            LinkAccountError::NothingToMerge => "nothing_to_merge",
+            LinkAccountError::NotCorrectAccessToken => "invalid_token",
+            LinkAccountError::ReauthenticationRequired { .. } => "reauthentication_required",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            LinkAccountError::UnknownBot(v) => vec![("bot", v.clone())],
+            _ => Vec::new(),
+        }
+    }
 }
 
 
@@ -76,7 +153,15 @@ pub enum AddCredError {
     #[error("Failed to add telegram User Attribute")]
     FailedAddingUserAttribute(String),
     #[error("Failed to add UserRole")]
-    FailedAddingUserRole(String)
+    FailedAddingUserRole(String),
+    #[error("Failed to create telegram user")]
+    FailedCreatingUser(String),
+    #[error("Telegram attributes are not valid: {0}")]
+    InvalidAttributes(AttributeError),
+    #[error("Telegram sign up is not allowed: {0}")]
+    SignUpNotAllowed(InviteError),
+    #[error("Guest is not upgraded: {0}")]
+    Guest(GuestError),
 }
 
 impl AppErrorInfo for AddCredError {
@@ -84,17 +169,121 @@ impl AppErrorInfo for AddCredError {
         match self {
             AddCredError::FailedAddingAuthMethod(msg) => format!("Failed to add telegram AUTH method: {}", msg),
             AddCredError::FailedAddingUserAttribute(msg) => format!("Failed to add telegram User Attribute: {}", msg),
-            AddCredError::FailedAddingUserRole(msg) => format!("Failed to add UserRole: {}", msg)
+            AddCredError::FailedAddingUserRole(msg) => format!("Failed to add UserRole: {}", msg),
+            AddCredError::FailedCreatingUser(msg) => format!("Failed to create telegram user: {}", msg),
+            AddCredError::InvalidAttributes(e) => e.client_message(),
+            AddCredError::SignUpNotAllowed(e) => e.client_message(),
+            AddCredError::Guest(e) => e.client_message(),
         }
     }
 
     fn level(&self) -> ErrorLevel {
-        ErrorLevel::Warning
+        match self {
+            AddCredError::InvalidAttributes(e) => e.level(),
+            AddCredError::SignUpNotAllowed(e) => e.level(),
+            AddCredError::Guest(e) => e.level(),
+            _ => ErrorLevel::Warning,
+        }
     }
 
     fn log_message(&self) -> String {
-        self.client_message()
+        match self {
+            AddCredError::InvalidAttributes(e) => e.log_message(),
+            AddCredError::SignUpNotAllowed(e) => e.log_message(),
+            AddCredError::Guest(e) => e.log_message(),
+            _ => self.client_message(),
+        }
     }
-    
+
+    fn status_code(&self) -> u16 {
+        match self {
+            AddCredError::InvalidAttributes(e) => e.status_code(),
+            AddCredError::SignUpNotAllowed(e) => e.status_code(),
+            AddCredError::Guest(e) => e.status_code(),
+            _ => 500,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AddCredError::InvalidAttributes(e) => e.error_code(),
+            AddCredError::SignUpNotAllowed(e) => e.error_code(),
+            AddCredError::Guest(e) => e.error_code(),
+            _ => "telegram_link_failed",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AddCredError::InvalidAttributes(e) => e.message_args(),
+            AddCredError::SignUpNotAllowed(e) => e.message_args(),
+            AddCredError::Guest(e) => e.message_args(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            AddCredError::InvalidAttributes(e) => e.field_errors(),
+            _ => Vec::new(),
+        }
+    }
+
 }
 
+
+
+
+#[derive(Debug, Error)]
+pub enum DeepLinkError {
+    #[error("Telegram bot is not configured: {0}")]
+    UnknownBot(String),
+    #[error("Telegram bot {0} has no username, deep link is unavailable")]
+    NoBotUsername(String),
+    #[error("Webhook secret mismatch for bot {0}")]
+    WebhookUnauthorized(String),
+}
+
+impl AppErrorInfo for DeepLinkError {
+    fn client_message(&self) -> String {
+        match self {
+            DeepLinkError::UnknownBot(v) => format!("Telegram bot is not configured: {}", v),
+            DeepLinkError::NoBotUsername(_) => "Telegram login is unavailable".to_string(),
+            DeepLinkError::WebhookUnauthorized(_) => "Unauthorized".to_string(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            DeepLinkError::NoBotUsername(_) => ErrorLevel::Warning,
+            _ => ErrorLevel::Info,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("DeepLinkError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            DeepLinkError::UnknownBot(_) => 404,
+            DeepLinkError::NoBotUsername(_) => 503,
+            DeepLinkError::WebhookUnauthorized(_) => 401,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            DeepLinkError::UnknownBot(_) => "unknown_bot",
+            DeepLinkError::NoBotUsername(_) => "telegram_login_unavailable",
+            DeepLinkError::WebhookUnauthorized(_) => "unauthorized",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            DeepLinkError::UnknownBot(v) => vec![("bot", v.clone())],
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::account_usecase::cancel_deletion::CancelAccountDeletionUseCase;
+use crate::application::usecase::auth_usecase::dto::TokenPairDto;
+use crate::application::usecase::guest_usecase::upgrade::guest_id;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+
+use crate::domain::jwt::service::{JwtClaimsService, TokenService};
+use crate::domain::jwt::factories::JWTProviderFactory;
+
+use crate::domain::settings::model::Credentials;
+
+use crate::domain::telegram::factories::TelegramProviderFactory;
+use crate::domain::telegram::service::LoginSessionService;
+
+use crate::domain::user::service::{QueryUserService, CommandUserService};
+use crate::domain::user::factories::UserProviderFactory;
+
+use super::add_cred::AddTelegramCredUseCase;
+use super::dto::{TelegramLoginExchangeRequestDto, TelegramLoginExchangeResponseDto};
+use super::find_user::FindTelegramUserUseCase;
+
+/// Обмен nonce и secret на токены после того, как пользователь подтвердил вход в боте.
+/// Nonce публичен (он в deep link), поэтому без secret сессию не забрать.
+/// Сессия одноразовая: после выдачи токенов она удаляется
+pub struct ExchangeTelegramLoginUseCase<CUS, QUS, S, CP, TP> {
+    login_session: S,
+    claims_provider: CP,
+    token_provider: TP,
+    add_telegram_cred_use_case: AddTelegramCredUseCase<CUS, QUS>,
+    find_telegram_user_use_case: FindTelegramUserUseCase<QUS>,
+    cancel_account_deletion_use_case: CancelAccountDeletionUseCase<CUS>,
+}
+
+impl<CUS, QUS, S, CP, TP> ServiceErrorExt for ExchangeTelegramLoginUseCase<CUS, QUS, S, CP, TP> {}
+
+impl<CUS, QUS, S, CP, TP> ExchangeTelegramLoginUseCase<CUS, QUS, S, CP, TP>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    S: LoginSessionService,
+    CP: JwtClaimsService,
+    TP: TokenService,
+{
+    pub fn new<T, F, U>(
+        credentials: Credentials,
+        user_provider_factory: &U,
+        telegram_provider_factory: &F,
+        jwtprovider_factory: &T,
+    ) -> Self
+    where
+        T: JWTProviderFactory<Claims = CP, Tokens = TP>,
+        F: TelegramProviderFactory<LoginSession = S>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        Self {
+            login_session: telegram_provider_factory.login_session(),
+            claims_provider: jwtprovider_factory.claims_service(),
+            token_provider: jwtprovider_factory.token_service(),
+            add_telegram_cred_use_case: AddTelegramCredUseCase::new(credentials.clone(), user_provider_factory),
+            find_telegram_user_use_case: FindTelegramUserUseCase::new(credentials, user_provider_factory),
+            cancel_account_deletion_use_case: CancelAccountDeletionUseCase::new(user_provider_factory),
+        }
+    }
+
+    pub async fn execute(&self, dto: TelegramLoginExchangeRequestDto) -> Result<TelegramLoginExchangeResponseDto, AppError> {
+        let session = match self.login_session.take_bound(&dto.nonce, &dto.secret) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(TelegramLoginExchangeResponseDto::Pending),
+            Err(e) => return self.handler_error(e),
+        };
+
+        // take_bound возвращает только сессии с пользователем
+        let Some(telegram_user) = session.user else {
+            return Ok(TelegramLoginExchangeResponseDto::Pending);
+        };
+
+        let guest = match dto.token.as_deref().map(|v| guest_id(&self.token_provider, v)).transpose() {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let extended_auth_method = match self.find_telegram_user_use_case.execute(telegram_user.id, &session.bot).await {
+            Ok(Some(user)) => match self.cancel_account_deletion_use_case.execute(user).await {
+                Ok(v) => v,
+                Err(e) => return self.handler_error(e),
+            },
+            Ok(None) => {
+                match self.add_telegram_cred_use_case.execute_new_user(telegram_user, &session.bot, dto.invite.as_deref(), guest).await {
+                    Ok(v) => v,
+                    Err(e) => return self.handler_error(e),
+                }
+            }
+            Err(e) => return self.handler_error(e),
+        };
+
+        let claims = match self.claims_provider.access_claims(&extended_auth_method) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let access_token = match self.token_provider.generate_access(claims) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let token_pair = TokenPairDto {
+            access_token,
+            refresh_token: None,
+        };
+
+        Ok(TelegramLoginExchangeResponseDto::Success { auth_data: token_pair })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<TelegramLoginExchangeResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    use crate::application::usecase::integration::telegram::create_login::CreateTelegramLoginUseCase;
+    use crate::application::usecase::integration::telegram::dto::{
+        TelegramLoginSessionResponseDto, TelegramUpdateDTO, TelegramWebhookResponseDto,
+    };
+    use crate::application::usecase::integration::telegram::webhook::TelegramWebhookUseCase;
+
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::telegram::factory::TelegramProvider;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn bot_api() -> (MockServer, Credentials) {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/botTEST/sendMessage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true, "result": {}})))
+            .mount(&mock_server)
+            .await;
+
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["telegram_api_url"] = serde_json::json!(mock_server.uri());
+        (mock_server, serde_json::from_value(credentials).unwrap())
+    }
+
+    fn start_update(nonce: &str) -> TelegramUpdateDTO {
+        serde_json::from_value(serde_json::json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 1,
+                "date": 0,
+                "chat": {"id": 42, "type": "private"},
+                "from": {"id": 42, "is_bot": false, "first_name": "Mock"},
+                "text": format!("/start {}", nonce)
+            }
+        }))
+        .unwrap()
+    }
+
+    fn confirm_update(nonce: &str) -> TelegramUpdateDTO {
+        serde_json::from_value(serde_json::json!({
+            "update_id": 2,
+            "callback_query": {
+                "id": "1",
+                "from": {"id": 42, "is_bot": false, "first_name": "Mock"},
+                "message": {"message_id": 2, "date": 0, "chat": {"id": 42, "type": "private"}},
+                "data": format!("login:{}", nonce)
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn deep_link_login() {
+        let (mock_server, credentials) = bot_api().await;
+        let telegram_provider = TelegramProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let create = CreateTelegramLoginUseCase::new(credentials.clone(), &telegram_provider);
+        let webhook = TelegramWebhookUseCase::new(credentials.clone(), &telegram_provider);
+        let exchange = ExchangeTelegramLoginUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &telegram_provider,
+            &jwtprovider_factory,
+        );
+
+        let Ok(TelegramLoginSessionResponseDto::Success { nonce, secret, code, deep_link, .. }) = create.execute(None).await else {
+            panic!("session is not created");
+        };
+        assert_eq!(deep_link, format!("https://t.me/test_bot?start={}", nonce));
+        assert!(!deep_link.contains(&secret));
+        assert!(!deep_link.contains(&code));
+
+        let request = TelegramLoginExchangeRequestDto { nonce: nonce.clone(), secret, invite: None, token: None };
+        let result = exchange.execute(request.clone()).await;
+        assert!(matches!(result, Ok(TelegramLoginExchangeResponseDto::Pending)));
+
+        let result = webhook
+            .execute("TEST".to_string(), Some("TEST_SECRET".to_string()), start_update(&nonce))
+            .await;
+        assert!(matches!(result, Ok(TelegramWebhookResponseDto::Success)));
+        // /start только показывает код и кнопку: ссылку мог прислать злоумышленник
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert!(body["text"].as_str().unwrap().contains(&code));
+        assert_eq!(body["reply_markup"]["inline_keyboard"][0][0]["callback_data"], format!("login:{}", nonce));
+        let result = exchange.execute(request.clone()).await;
+        assert!(matches!(result, Ok(TelegramLoginExchangeResponseDto::Pending)));
+
+        let result = webhook
+            .execute("TEST".to_string(), Some("TEST_SECRET".to_string()), confirm_update(&nonce))
+            .await;
+        assert!(matches!(result, Ok(TelegramWebhookResponseDto::Success)));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        let by_nonce = TelegramLoginExchangeRequestDto { secret: String::new(), ..request.clone() };
+        let result = exchange.execute(by_nonce).await;
+        assert!(matches!(result, Err(e) if e.status == 404));
+
+        let result = exchange.execute(request.clone()).await;
+        assert!(matches!(result, Ok(TelegramLoginExchangeResponseDto::Success { .. })));
+
+        let result = exchange.execute(request).await;
+        assert!(matches!(result, Err(e) if e.status == 404));
+    }
+
+    #[tokio::test]
+    async fn webhook_without_secret_is_rejected() {
+        let (mock_server, credentials) = bot_api().await;
+        let telegram_provider = TelegramProvider::new(credentials.clone());
+
+        let create = CreateTelegramLoginUseCase::new(credentials.clone(), &telegram_provider);
+        let webhook = TelegramWebhookUseCase::new(credentials.clone(), &telegram_provider);
+
+        let Ok(TelegramLoginSessionResponseDto::Success { nonce, .. }) = create.execute(None).await else {
+            panic!("session is not created");
+        };
+
+        let result = webhook
+            .execute("TEST".to_string(), Some("WRONG".to_string()), start_update(&nonce))
+            .await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+
+        let result = webhook.execute("TEST".to_string(), None, start_update(&nonce)).await;
+        assert!(matches!(result, Err(e) if e.status == 401));
+
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+}
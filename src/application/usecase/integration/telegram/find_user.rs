@@ -0,0 +1,91 @@
+use crate::domain::settings::model::{Credentials, TelegramLinkScope};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::service::QueryUserService;
+
+use super::constants::AUTH_TYPE;
+
+/// Ищет пользователя по Telegram id с учётом `telegram_link_scope`
+pub struct FindTelegramUserUseCase<QUS> {
+    credentials: Credentials,
+    query_user_service: QUS,
+}
+
+impl<QUS> FindTelegramUserUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, query_user_service }
+    }
+
+    pub async fn execute(&self, telegram_id: i64, bot: &str) -> Result<Option<ExtendedAuthMethod>, QUS::Error> {
+        let identifier = telegram_id.to_string();
+
+        match self.credentials.telegram_link_scope() {
+            TelegramLinkScope::Global => {
+                self.query_user_service
+                    .get_user_by_identifier(&identifier, AUTH_TYPE)
+                    .await
+            }
+            TelegramLinkScope::PerBot => {
+                // Старые записи без provider принадлежат боту по умолчанию
+                let with_unassigned = self.credentials.is_default_telegram_bot(bot);
+                self.query_user_service
+                    .get_user_by_provider_identifier(&identifier, AUTH_TYPE, bot, with_unassigned)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn per_bot_credentials() -> Credentials {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["telegram_link_scope"] = serde_json::json!("per_bot");
+        credentials["telegram_bots"] = serde_json::json!([
+            { "name": "TEST", "token": "TEST" },
+            { "name": "staging", "token": "STAGING" }
+        ]);
+        serde_json::from_value(credentials).unwrap()
+    }
+
+    async fn find(bot: &str) -> serde_json::Value {
+        let credentials = per_bot_credentials();
+        let mut builder = MockHasuraClientBuilder::new();
+        builder.with_telegram_of_another_user();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), builder.build());
+        let action = FindTelegramUserUseCase::new(credentials, &user_provider_factory);
+
+        let result = action.execute(42, bot).await;
+
+        assert!(result.unwrap().is_some());
+        serde_json::from_str(&builder.recorder().read_data().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn default_bot_finds_records_without_provider() {
+        let body = find("TEST").await;
+
+        assert_eq!(body["variables"]["provider"], "TEST");
+        assert_eq!(body["variables"]["with_unassigned"], true);
+    }
+
+    #[tokio::test]
+    async fn other_bot_finds_only_own_records() {
+        let body = find("staging").await;
+
+        assert_eq!(body["variables"]["provider"], "staging");
+        assert_eq!(body["variables"]["with_unassigned"], false);
+    }
+}
@@ -5,7 +5,7 @@ use crate::application::error_ext::ServiceErrorExt;
 use crate::application::usecase::auth_usecase::dto::{JwtResponseDto, TokenPairDto};
 use crate::application::usecase::integration::telegram::errors::LinkAccountError;
 
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
 
 use crate::domain::jwt::service::{JwtClaimsService, TokenService};
 use crate::domain::jwt::factories::JWTProviderFactory;
@@ -24,8 +24,8 @@ use crate::domain::verifies::factories::VerifiesProviderFactory;
 
 use super::dto::TelegramDataDTO;
 use super::add_cred::AddTelegramCredUseCase;
+use super::find_user::FindTelegramUserUseCase;
 
-use super::constants::{AUTH_TYPE, TELEGRAM_USERNAME, TELEGRAM_LAST_NAME, TELEGRAM_FIRST_NAME};
 
 
 pub struct LinkTelegramAccountUseCase<CUS, QUS, V, CP, TP> {
@@ -35,7 +35,8 @@ pub struct LinkTelegramAccountUseCase<CUS, QUS, V, CP, TP> {
     telegram_verifier: V,
     claims_provider: CP,
     token_provider: TP,
-    add_telegram_cred_use_case: AddTelegramCredUseCase<CUS>,
+    add_telegram_cred_use_case: AddTelegramCredUseCase<CUS, QUS>,
+    find_telegram_user_use_case: FindTelegramUserUseCase<QUS>,
 }
 
 impl<CUS, QUS, V, CP, TP> ServiceErrorExt for LinkTelegramAccountUseCase<CUS, QUS, V, CP, TP> {}
@@ -65,6 +66,7 @@ where
         let command_user_service = user_provider_factory.command_user();
         let query_user_service = user_provider_factory.query_user();
         let add_telegram_cred_use_case = AddTelegramCredUseCase::new(credentials.clone(), user_provider_factory);
+        let find_telegram_user_use_case = FindTelegramUserUseCase::new(credentials.clone(), user_provider_factory);
         Self {
             credentials,
             command_user_service,
@@ -72,23 +74,40 @@ where
             telegram_verifier,
             claims_provider,
             token_provider,
-            add_telegram_cred_use_case
+            add_telegram_cred_use_case,
+            find_telegram_user_use_case
         }
     }
 
-    pub async fn execute(&self, dto: TelegramDataDTO, token: String) -> Result<JwtResponseDto, String> {
-        let user_by_token = self.get_user_by_jwt(token).await
-            .map_err(|_| "Ошибка получения пользователя")?;
+    pub async fn execute(&self, dto: TelegramDataDTO, token: String, bot: Option<String>) -> Result<JwtResponseDto, AppError> {
+        let Ok(user_by_token) = self.get_user_by_jwt(token).await else {
+            return self.handler_error(LinkAccountError::NotCorrectAccessToken);
+        };
         
-        let extended_auth_method = match self.query_user_service.get_user_by_identifier(&dto.id.to_string(), AUTH_TYPE).await {
+        let telegram_data: TelegramData = dto.clone().into();
+
+        let Some(bot) = self.credentials.telegram_bot(bot.as_deref()).map(|v| v.name().clone()) else {
+            return self.handler_error(LinkAccountError::UnknownBot(bot.unwrap_or_default()));
+        };
+
+        let is_verified = match self.telegram_verifier.is_verified(Some(&bot), telegram_data.clone()) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e)
+        };
+
+        if !is_verified {
+            return self.handler_error(LinkAccountError::NotVerified);
+        }
+
+        let extended_auth_method = match self.find_telegram_user_use_case.execute(dto.id, &bot).await {
             Ok(Some(user)) => {
-                if !user.user().eq(&user_by_token) {
-                    return self.handler_error(LinkAccountError::NotVerified);
+                if user.user().id() != user_by_token.id() {
+                    return self.handler_error(LinkAccountError::OwnedByAnotherUser);
                 }
                 user
             },
             Ok(None) => {
-                let extended_auth_method = match self.add_telegram_cred_use_case.execute(user_by_token.clone().into(), dto.clone()).await {
+                let extended_auth_method = match self.add_telegram_cred_use_case.execute(user_by_token.clone(), dto.into(), &bot).await {
                     Ok(v) => v,
                     Err(e) => return self.handler_error(e)
                 };
@@ -98,18 +117,6 @@ where
             Err(e) => return self.handler_error(e)
         };
 
-        let telegram_data: TelegramData = dto.into();
-
-        let is_verified = match self.telegram_verifier.is_verified(telegram_data.clone()) {
-            Ok(v) => v,
-            Err(e) => return self.handler_error(e)
-        };
-
-        if !is_verified {
-            return self.handler_error(LinkAccountError::NotVerified);
-        }
-
-        
         let claims = match self.claims_provider.access_claims(&extended_auth_method) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e),
@@ -120,6 +127,10 @@ where
             Err(e) => return self.handler_error(e),
         };
 
+        if let Err(e) = self.telegram_verifier.remember(&telegram_data) {
+            return self.handler_error(e);
+        }
+
         let token_pair = TokenPairDto {
             access_token,
             refresh_token: None, // Telegram integration may not require refresh tokens
@@ -128,13 +139,8 @@ where
         Ok(JwtResponseDto::Success { auth_data: token_pair })
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(JwtResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
     }
 
     async fn get_user_by_jwt(&self, token: String) -> Result<ExtendedUser, ()> {
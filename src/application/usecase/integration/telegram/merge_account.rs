@@ -0,0 +1,282 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::account_usecase::delete_account::DEFAULT_REAUTH_MAX_AGE_MINUTES;
+use crate::application::usecase::auth_usecase::dto::{JwtResponseDto, TokenPairDto};
+use crate::application::usecase::integration::telegram::errors::LinkAccountError;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+
+use crate::domain::jwt::model::Claims;
+use crate::domain::jwt::service::{JwtClaimsService, TokenService};
+use crate::domain::jwt::factories::JWTProviderFactory;
+
+use crate::domain::settings::model::Credentials;
+
+use crate::domain::user::models::extended::ExtendedUser;
+use crate::domain::user::models::merge::UserMerge;
+use crate::domain::user::service::{QueryUserService, CommandUserService};
+use crate::domain::user::factories::UserProviderFactory;
+
+use crate::domain::verifies::model::TelegramData;
+use crate::domain::verifies::service::{ApiKeyVerifierService, TelegramVerifierService};
+use crate::domain::verifies::factories::VerifiesProviderFactory;
+
+use super::dto::TelegramDataDTO;
+use super::find_user::FindTelegramUserUseCase;
+
+/// Слияние аккаунтов: Telegram уже привязан к другому пользователю.
+/// Владение основным аккаунтом подтверждает недавний вход (не API ключ), второстепенным - подпись Telegram
+pub struct MergeTelegramAccountUseCase<CUS, QUS, V, AKV, CP, TP> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    query_user_service: QUS,
+    telegram_verifier: V,
+    api_key_verifier: AKV,
+    claims_provider: CP,
+    token_provider: TP,
+    find_telegram_user_use_case: FindTelegramUserUseCase<QUS>,
+}
+
+impl<CUS, QUS, V, AKV, CP, TP> ServiceErrorExt for MergeTelegramAccountUseCase<CUS, QUS, V, AKV, CP, TP> {}
+
+impl<CUS, QUS, V, AKV, CP, TP> MergeTelegramAccountUseCase<CUS, QUS, V, AKV, CP, TP>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+    V: TelegramVerifierService,
+    AKV: ApiKeyVerifierService,
+    CP: JwtClaimsService,
+    TP: TokenService,
+{
+    pub fn new<T, P, U>(
+        credentials: Credentials,
+        user_provider_factory: &U,
+        verifies_provider_factory: &P,
+        jwtprovider_factory: &T,
+    ) -> Self
+    where
+        T: JWTProviderFactory<Claims = CP, Tokens = TP>,
+        P: VerifiesProviderFactory<TelegramVerifierService = V, ApiKeyVerifier = AKV>,
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let claims_provider = jwtprovider_factory.claims_service();
+        let token_provider = jwtprovider_factory.token_service();
+        let telegram_verifier = verifies_provider_factory.telegram_verifier();
+        let api_key_verifier = verifies_provider_factory.api_key_verifier();
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        let find_telegram_user_use_case = FindTelegramUserUseCase::new(credentials.clone(), user_provider_factory);
+        Self {
+            credentials,
+            command_user_service,
+            query_user_service,
+            telegram_verifier,
+            api_key_verifier,
+            claims_provider,
+            token_provider,
+            find_telegram_user_use_case,
+        }
+    }
+
+    pub async fn execute(&self, dto: TelegramDataDTO, token: String, bot: Option<String>) -> Result<JwtResponseDto, AppError> {
+        let Ok(claims) = self.token_provider.validate_access(&token) else {
+            return self.handler_error(LinkAccountError::NotCorrectAccessToken);
+        };
+        // Слияние забирает способы входа, роли и атрибуты другого аккаунта
+        if claims.is_api_key() {
+            return self.handler_error(LinkAccountError::NotCorrectAccessToken);
+        }
+        if let Err(e) = self.check_recent_auth(&claims) {
+            return self.handler_error(e);
+        }
+        let Ok(primary) = self.get_user(&claims.sub).await else {
+            return self.handler_error(LinkAccountError::NotCorrectAccessToken);
+        };
+
+        let Some(bot) = self.credentials.telegram_bot(bot.as_deref()).map(|v| v.name().clone()) else {
+            return self.handler_error(LinkAccountError::UnknownBot(bot.unwrap_or_default()));
+        };
+
+        let telegram_data: TelegramData = dto.clone().into();
+        match self.telegram_verifier.is_verified(Some(&bot), telegram_data.clone()) {
+            Ok(true) => {}
+            Ok(false) => return self.handler_error(LinkAccountError::NotVerified),
+            Err(e) => return self.handler_error(e),
+        };
+
+        let secondary = match self.find_telegram_user_use_case.execute(dto.id, &bot).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(LinkAccountError::NothingToMerge),
+            Err(e) => return self.handler_error(e),
+        };
+
+        if secondary.user().id() != primary.id() {
+            let merge = UserMerge::plan(&primary, secondary.user());
+            if let Err(e) = self.command_user_service.merge_users(merge).await {
+                return self.handler_error(e);
+            }
+            // Закэшированные ключи второстепенного пользователя иначе работали бы до конца TTL
+            self.api_key_verifier.forget_user(*secondary.user_id());
+        }
+
+        // После слияния Telegram принадлежит основному пользователю
+        let extended_auth_method = match self.find_telegram_user_use_case.execute(dto.id, &bot).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return self.handler_error(LinkAccountError::NoTelegramCreds),
+            Err(e) => return self.handler_error(e),
+        };
+
+        let claims = match self.claims_provider.access_claims(&extended_auth_method) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let access_token = match self.token_provider.generate_access(claims) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        if let Err(e) = self.telegram_verifier.remember(&telegram_data) {
+            return self.handler_error(e);
+        }
+
+        let token_pair = TokenPairDto {
+            access_token,
+            refresh_token: None,
+        };
+
+        Ok(JwtResponseDto::Success { auth_data: token_pair })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<JwtResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+
+    /// Недавний вход по `reauth_max_age_minutes` (по умолчанию `DEFAULT_REAUTH_MAX_AGE_MINUTES`)
+    fn check_recent_auth(&self, claims: &Claims) -> Result<(), LinkAccountError> {
+        let max_age_minutes = self
+            .credentials
+            .reauth_max_age_minutes()
+            .unwrap_or(DEFAULT_REAUTH_MAX_AGE_MINUTES);
+        let now = chrono::Utc::now().timestamp();
+        let is_recent = claims
+            .auth_time
+            .is_some_and(|v| now - (v as i64) <= max_age_minutes * 60);
+        if !is_recent {
+            return Err(LinkAccountError::ReauthenticationRequired { auth_time: claims.auth_time, max_age_minutes });
+        }
+        Ok(())
+    }
+
+    async fn get_user(&self, sub: &str) -> Result<ExtendedUser, ()> {
+        let user_id = Uuid::from_str(sub)
+            .map_err(|_| ())?;
+
+        let user_data = self.query_user_service.get_user_by_id(user_id)
+            .await.map_err(|_| ())?;
+
+        let Some(user) = user_data.first() else {
+            return Err(());
+        };
+
+        Ok(user.user().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+    use crate::mock::telegram::MockTelegramData;
+
+    fn access_token(jwtprovider_factory: &JWTProvider) -> String {
+        access_token_with(jwtprovider_factory, |_| {})
+    }
+
+    fn access_token_with(jwtprovider_factory: &JWTProvider, tweak: impl FnOnce(&mut Claims)) -> String {
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        tweak(&mut claims);
+        jwtprovider_factory.token_service().generate_access(claims).unwrap()
+    }
+
+    async fn merge(token: impl FnOnce(&JWTProvider) -> String) -> Result<JwtResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        // Ответ на MergeUsers не задан: если слияние начнётся, use case вернёт Err
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .with_telegram_of_another_user()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = MergeTelegramAccountUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let dto = MockTelegramData::signed(credentials.telegram_bot(None).unwrap().token());
+
+        action.execute(dto, token(&jwtprovider_factory), None).await
+    }
+
+    #[tokio::test]
+    async fn telegram_of_another_user_is_merged() {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .with_telegram_of_another_user()
+            .with_users_merge()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = MergeTelegramAccountUseCase::new(
+            credentials.clone(),
+            &user_provider_factory,
+            &verifies_provider_factory,
+            &jwtprovider_factory
+        );
+        let dto = MockTelegramData::signed(credentials.telegram_bot(None).unwrap().token());
+
+        let result = action.execute(dto, access_token(&jwtprovider_factory), None).await;
+
+        assert!(matches!(result, Ok(JwtResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn merge_requires_valid_token() {
+        let result = merge(|_| "not-a-token".to_string()).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn stale_session_is_rejected() {
+        let an_hour_ago = chrono::Utc::now().timestamp() as usize - 3600;
+
+        let result = merge(|v| access_token_with(v, |claims| claims.auth_time = Some(an_hour_ago))).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code == "reauthentication_required"));
+    }
+
+    #[tokio::test]
+    async fn api_key_token_is_rejected() {
+        let result = merge(|v| access_token_with(v, |claims| claims.scopes = Some(vec!["read".to_string()]))).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code == "invalid_token"));
+    }
+}
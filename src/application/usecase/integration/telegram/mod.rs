@@ -1,6 +1,11 @@
 pub mod link_account;
 pub mod add_cred;
+pub mod find_user;
 pub mod dto;
 pub mod errors;
 pub mod auth;
+pub mod merge_account;
+pub mod create_login;
+pub mod webhook;
+pub mod exchange_login;
 mod constants;
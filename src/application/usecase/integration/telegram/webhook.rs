@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use subtle::ConstantTimeEq;
+
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::telegram::factories::TelegramProviderFactory;
+use crate::domain::telegram::service::{LoginSessionService, TelegramBotService};
+
+use super::dto::{TelegramCallbackQueryDTO, TelegramMessageDTO, TelegramUpdateDTO, TelegramWebhookResponseDto};
+use super::errors::DeepLinkError;
+
+const START_COMMAND: &str = "/start";
+/// callback_data кнопки подтверждения: `login:<nonce>`, в лимит Telegram 64 байта укладывается
+const CONFIRM_PREFIX: &str = "login:";
+
+const CONFIRM_BUTTON: &str = "Confirm sign in";
+
+const SIGNED_IN_MESSAGE: &str = "You are signed in. Return to the app to continue.";
+const LINK_EXPIRED_MESSAGE: &str = "This login link has expired. Request a new one in the app.";
+
+/// Вход через бота. `/start <nonce>` только показывает код сессии и кнопку подтверждения:
+/// ссылку мог прислать кто угодно, поэтому отправитель привязывается к сессии
+/// лишь после нажатия кнопки, сверив код с приложением
+pub struct TelegramWebhookUseCase<S, B> {
+    credentials: Credentials,
+    login_session: S,
+    bots: HashMap<String, B>,
+}
+
+impl<S, B> ServiceErrorExt for TelegramWebhookUseCase<S, B> {}
+
+impl<S, B> TelegramWebhookUseCase<S, B>
+where
+    S: LoginSessionService,
+    B: TelegramBotService,
+{
+    pub fn new<F>(credentials: Credentials, telegram_provider_factory: &F) -> Self
+    where
+        F: TelegramProviderFactory<LoginSession = S, Bot = B>,
+    {
+        let login_session = telegram_provider_factory.login_session();
+        let bots = credentials
+            .telegram_bots()
+            .iter()
+            .filter_map(|bot| {
+                telegram_provider_factory
+                    .bot(bot.name())
+                    .map(|client| (bot.name().clone(), client))
+            })
+            .collect();
+        Self { credentials, login_session, bots }
+    }
+
+    pub async fn execute(
+        &self,
+        bot: String,
+        secret: Option<String>,
+        update: TelegramUpdateDTO,
+    ) -> Result<TelegramWebhookResponseDto, AppError> {
+        let Some(telegram_bot) = self.credentials.telegram_bot(Some(&bot)) else {
+            return self.handler_error(DeepLinkError::UnknownBot(bot));
+        };
+
+        let authorized = match (telegram_bot.webhook_secret(), secret) {
+            (Some(expected), Some(secret)) => bool::from(expected.as_bytes().ct_eq(secret.as_bytes())),
+            _ => false,
+        };
+        if !authorized {
+            return self.handler_error(DeepLinkError::WebhookUnauthorized(bot));
+        }
+
+        // Остальные update боту не адресованы, Telegram должен получить 200
+        let result = if let Some(query) = update.callback_query {
+            self.confirm(&bot, query).await
+        } else if let Some(message) = update.message {
+            self.start(&bot, message).await
+        } else {
+            None
+        };
+
+        // Telegram повторяет update на любой статус кроме 2xx, ошибку входа отдаём в теле
+        match result {
+            None | Some(Ok(())) => Ok(TelegramWebhookResponseDto::Success),
+            Some(Err(e)) => Ok(TelegramWebhookResponseDto::Error { err_msg: self.map_service_error(e).message }),
+        }
+    }
+
+    /// `/start <nonce>`: сессия не привязывается, пользователь получает код и кнопку
+    async fn start(&self, bot: &str, message: TelegramMessageDTO) -> Option<Result<(), S::Error>> {
+        let nonce = Self::start_payload(message.text.as_deref()?)?;
+
+        let result = self.login_session.pending(bot, nonce);
+        match &result {
+            Ok(session) => {
+                let text = format!(
+                    "Someone is signing in with your Telegram account. Confirm only if the app shows the code {}. If it was not you, ignore this message.",
+                    session.code
+                );
+                let callback_data = format!("{}{}", CONFIRM_PREFIX, nonce);
+                self.reply_button(bot, message.chat.id, &text, &callback_data).await;
+            }
+            Err(_) => self.reply(bot, message.chat.id, LINK_EXPIRED_MESSAGE).await,
+        }
+        Some(result.map(|_| ()))
+    }
+
+    /// Нажатие кнопки подтверждения: нажавший привязывается к сессии
+    async fn confirm(&self, bot: &str, query: TelegramCallbackQueryDTO) -> Option<Result<(), S::Error>> {
+        let nonce = query.data.as_deref()?.strip_prefix(CONFIRM_PREFIX)?;
+        // В личном чате id чата совпадает с id пользователя
+        let chat_id = query.message.as_ref().map_or(query.from.id, |v| v.chat.id);
+
+        let result = self.login_session.bind(bot, nonce, query.from.into());
+
+        let reply = match result {
+            Ok(_) => SIGNED_IN_MESSAGE,
+            Err(_) => LINK_EXPIRED_MESSAGE,
+        };
+        self.reply(bot, chat_id, reply).await;
+        Some(result.map(|_| ()))
+    }
+
+    /// `/start <nonce>` -> nonce
+    fn start_payload(text: &str) -> Option<&str> {
+        let payload = text.strip_prefix(START_COMMAND)?.strip_prefix(' ')?.trim();
+        (!payload.is_empty()).then_some(payload)
+    }
+
+    /// Ответ пользователю не влияет на вход, ошибки только логируем
+    async fn reply(&self, bot: &str, chat_id: i64, text: &str) {
+        let Some(client) = self.bots.get(bot) else {
+            return;
+        };
+        if let Err(e) = client.send_message(chat_id, text).await {
+            self.map_service_error(e);
+        }
+    }
+
+    async fn reply_button(&self, bot: &str, chat_id: i64, text: &str, callback_data: &str) {
+        let Some(client) = self.bots.get(bot) else {
+            return;
+        };
+        if let Err(e) = client.send_button(chat_id, text, CONFIRM_BUTTON, callback_data).await {
+            self.map_service_error(e);
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<TelegramWebhookResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
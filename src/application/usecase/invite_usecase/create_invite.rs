@@ -0,0 +1,135 @@
+use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::role_usecase::error::RoleError;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::invite::Invite;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::dto::{CreateInviteRequestDto, InviteResponseDto};
+use super::error::InviteError;
+
+/// Выпускает приглашение (`POST /auth/admin/invites`)
+pub struct CreateInviteUseCase<CUS, QUS> {
+    command_user_service: CUS,
+    query_user_service: QUS,
+}
+
+impl<CUS, QUS> ServiceErrorExt for CreateInviteUseCase<CUS, QUS> {}
+
+impl<CUS, QUS> CreateInviteUseCase<CUS, QUS>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        Self { command_user_service, query_user_service }
+    }
+
+    pub async fn execute(&self, dto: CreateInviteRequestDto) -> Result<InviteResponseDto, AppError> {
+        if let Some(max_uses) = dto.max_uses.filter(|v| *v < 1) {
+            return self.handler_error(InviteError::InvalidMaxUses(max_uses));
+        }
+
+        if let Some(role) = dto.role.as_ref() {
+            let roles = match self.query_user_service.list_roles().await {
+                Ok(v) => v,
+                Err(e) => return self.handler_error(e),
+            };
+            if !roles.iter().any(|v| v.value() == role) {
+                return self.handler_error(RoleError::RoleNotFound(role.clone()));
+            }
+        }
+
+        let code = match dto.code.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+            Some(code) => {
+                match self.query_user_service.get_invite(&code).await {
+                    Ok(None) => code,
+                    Ok(Some(_)) => return self.handler_error(InviteError::InviteExists(code)),
+                    Err(e) => return self.handler_error(e),
+                }
+            }
+            None => hex::encode(rand::random::<[u8; 12]>()),
+        };
+
+        let comment = dto.comment.filter(|v| !v.trim().is_empty());
+        let invite = Invite::new(code, dto.role, dto.max_uses, dto.expires_at, comment);
+        match self.command_user_service.create_invite(invite).await {
+            Ok(invite) => Ok(InviteResponseDto::Success { invite }),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<InviteResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::settings::model::Credentials;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn create(hasura_client: &MockHasuraClientBuilder, dto: CreateInviteRequestDto) -> Result<InviteResponseDto, AppError> {
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+
+        let action = CreateInviteUseCase::new(&user_provider_factory);
+        action.execute(dto).await
+    }
+
+    #[tokio::test]
+    async fn invite_with_generated_code() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_roles().with_invites();
+        let dto = CreateInviteRequestDto { role: Some("test".to_string()), max_uses: Some(10), ..Default::default() };
+
+        let result = create(&hasura_client, dto).await;
+
+        assert!(matches!(result, Ok(InviteResponseDto::Success { .. })));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "InsertInvite");
+        assert_eq!(body["variables"]["code"].as_str().unwrap().len(), 24);
+        assert_eq!(body["variables"]["uses_left"], 10);
+    }
+
+    #[tokio::test]
+    async fn unknown_role() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_roles().with_invites();
+        let dto = CreateInviteRequestDto { role: Some("support".to_string()), ..Default::default() };
+
+        let result = create(&hasura_client, dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 404 && e.code == "role_not_found"));
+    }
+
+    #[tokio::test]
+    async fn existing_code() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_invites().with_unusable_invite("query_invite_exhausted.json");
+        let dto = CreateInviteRequestDto { code: Some("beta-tester".to_string()), ..Default::default() };
+
+        let result = create(&hasura_client, dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 409 && e.code == "invite_exists"));
+    }
+
+    #[tokio::test]
+    async fn not_positive_limit() {
+        let hasura_client = MockHasuraClientBuilder::new();
+        let dto = CreateInviteRequestDto { max_uses: Some(0), ..Default::default() };
+
+        let result = create(&hasura_client, dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 422));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::domain::user::models::invite::Invite;
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct CreateInviteRequestDto {
+    /// Без кода генерируется случайный
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Роль нового пользователя вместо `new_user_role`, должна быть в `users.role`
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Без значения - без ограничения
+    #[serde(default)]
+    pub max_uses: Option<i32>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum InvitesResponseDto {
+    Success { invites: Vec<Invite> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum InviteResponseDto {
+    Success { invite: Invite },
+}
@@ -0,0 +1,83 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone)]
+pub enum InviteError {
+    #[error("Sign up is disabled")]
+    SignUpDisabled,
+    #[error("Sign up requires an invite")]
+    InviteRequired,
+    #[error("Invite {0} not found")]
+    InviteNotFound(String),
+    #[error("Invite {0} is expired")]
+    InviteExpired(String),
+    #[error("Invite {0} has no uses left")]
+    InviteExhausted(String),
+    #[error("Email domain {0} is not allowed")]
+    DomainNotAllowed(String),
+    #[error("Invite {0} already exists")]
+    InviteExists(String),
+    #[error("Invite max_uses must be positive, got {0}")]
+    InvalidMaxUses(i32),
+    #[error("Invites are not available: {0}")]
+    Unavailable(String),
+}
+
+impl AppErrorInfo for InviteError {
+    fn client_message(&self) -> String {
+        match self {
+            InviteError::SignUpDisabled => "Sign up is disabled".to_string(),
+            InviteError::InviteRequired => "Sign up requires an invite".to_string(),
+            // Не раскрываем, существует ли код
+            InviteError::InviteNotFound(_) => "Invite is not valid".to_string(),
+            InviteError::InviteExpired(_) => "Invite is expired".to_string(),
+            InviteError::InviteExhausted(_) => "Invite has no uses left".to_string(),
+            InviteError::DomainNotAllowed(v) => format!("Sign up with {} email is not allowed", v),
+            InviteError::InviteExists(v) => format!("Invite {} already exists", v),
+            InviteError::InvalidMaxUses(_) => "Invite max_uses must be positive".to_string(),
+            InviteError::Unavailable(_) => self.internal_error(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            InviteError::Unavailable(_) => ErrorLevel::Error,
+            _ => ErrorLevel::Info,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("InviteError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            InviteError::InviteExists(_) => 409,
+            InviteError::InvalidMaxUses(_) => 422,
+            InviteError::Unavailable(_) => 500,
+            _ => 403,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            InviteError::SignUpDisabled => "signup_disabled",
+            InviteError::InviteRequired => "invite_required",
+            InviteError::InviteNotFound(_) => "invite_invalid",
+            InviteError::InviteExpired(_) => "invite_expired",
+            InviteError::InviteExhausted(_) => "invite_exhausted",
+            InviteError::DomainNotAllowed(_) => "email_domain_not_allowed",
+            InviteError::InviteExists(_) => "invite_exists",
+            InviteError::InvalidMaxUses(_) => "invalid_invite",
+            InviteError::Unavailable(_) => "internal_error",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            InviteError::DomainNotAllowed(v) => vec![("domain", v.clone())],
+            InviteError::InviteExists(v) => vec![("invite", v.clone())],
+            _ => Vec::new(),
+        }
+    }
+}
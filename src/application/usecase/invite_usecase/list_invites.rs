@@ -0,0 +1,38 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::InvitesResponseDto;
+
+/// Приглашения `users.invite` (`GET /auth/admin/invites`)
+pub struct ListInvitesUseCase<QUS> {
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ListInvitesUseCase<QUS> {}
+
+impl<QUS> ListInvitesUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { query_user_service }
+    }
+
+    pub async fn execute(&self) -> Result<InvitesResponseDto, AppError> {
+        match self.query_user_service.list_invites().await {
+            Ok(invites) => Ok(InvitesResponseDto::Success { invites }),
+            Err(e) => self.handler_error(e),
+        }
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<InvitesResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
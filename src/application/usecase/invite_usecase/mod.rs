@@ -0,0 +1,5 @@
+pub mod create_invite;
+pub mod dto;
+pub mod error;
+pub mod list_invites;
+pub mod policy;
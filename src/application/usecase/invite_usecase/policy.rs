@@ -0,0 +1,142 @@
+use crate::domain::settings::model::{Credentials, SignUpMode};
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::invite::Invite;
+use crate::domain::user::service::QueryUserService;
+
+use super::error::InviteError;
+
+/// Можно ли создать нового пользователя по `signup_mode` и с какой ролью.
+/// `check` вызывается до любых проверок данных, `invite` - прямо перед созданием
+pub struct SignUpPolicyUseCase<QUS> {
+    credentials: Credentials,
+    query_user_service: QUS,
+}
+
+impl<QUS> SignUpPolicyUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, query_user_service }
+    }
+
+    /// Режим без обращения к Hasura. `email` есть только у регистрации по email,
+    /// поэтому в `allow_list` Telegram регистрируется только по приглашению
+    pub fn check(&self, email: Option<&str>, invite: Option<&str>) -> Result<(), InviteError> {
+        let has_invite = invite.is_some_and(|v| !v.trim().is_empty());
+        match self.credentials.signup_mode() {
+            SignUpMode::Open => Ok(()),
+            SignUpMode::Disabled => Err(InviteError::SignUpDisabled),
+            SignUpMode::InviteOnly if has_invite => Ok(()),
+            SignUpMode::InviteOnly => Err(InviteError::InviteRequired),
+            SignUpMode::AllowList if has_invite => Ok(()),
+            SignUpMode::AllowList => {
+                let Some((_, domain)) = email.and_then(|v| v.rsplit_once('@')) else {
+                    return Err(InviteError::InviteRequired);
+                };
+                let allowed = self
+                    .credentials
+                    .signup_allowed_domains()
+                    .iter()
+                    .any(|v| v.eq_ignore_ascii_case(domain));
+                if allowed {
+                    Ok(())
+                } else {
+                    Err(InviteError::DomainNotAllowed(domain.to_lowercase()))
+                }
+            }
+        }
+    }
+
+    /// Действующее приглашение, его роль заменяет `new_user_role`.
+    /// Использование не списывается: код передаётся в [`NewUser::with_invite`](crate::domain::user::models::new_user::NewUser::with_invite)
+    /// и списывается той же мутацией, что создаёт пользователя
+    pub async fn invite(&self, invite: Option<&str>) -> Result<Option<Invite>, InviteError> {
+        let Some(code) = invite.map(str::trim).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+
+        let invite = self
+            .query_user_service
+            .get_invite(code)
+            .await
+            .map_err(|e| InviteError::Unavailable(e.to_string()))?;
+        match invite {
+            None => Err(InviteError::InviteNotFound(code.to_string())),
+            Some(v) if v.is_expired(chrono::Utc::now().fixed_offset()) => Err(InviteError::InviteExpired(code.to_string())),
+            Some(v) if v.is_exhausted() => Err(InviteError::InviteExhausted(code.to_string())),
+            Some(v) => Ok(Some(v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::errors::service::AppErrorInfo;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn credentials(mode: &str) -> Credentials {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["signup_mode"] = serde_json::json!(mode);
+        serde_json::from_value(credentials).unwrap()
+    }
+
+    fn policy(mode: &str, hasura_client: &MockHasuraClientBuilder) -> SignUpPolicyUseCase<impl QueryUserService> {
+        let credentials = credentials(mode);
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+        SignUpPolicyUseCase::new(credentials, &user_provider_factory)
+    }
+
+    #[test]
+    fn signup_modes() {
+        let hasura_client = MockHasuraClientBuilder::new();
+
+        assert!(policy("open", &hasura_client).check(None, None).is_ok());
+        assert!(matches!(policy("disabled", &hasura_client).check(None, Some("code")), Err(InviteError::SignUpDisabled)));
+        assert!(matches!(policy("invite_only", &hasura_client).check(Some("a@test.com"), None), Err(InviteError::InviteRequired)));
+        assert!(policy("invite_only", &hasura_client).check(None, Some("code")).is_ok());
+
+        let allow_list = policy("allow_list", &hasura_client);
+        assert!(allow_list.check(Some("a@TEST.com"), None).is_ok());
+        assert!(matches!(allow_list.check(Some("a@other.com"), None), Err(InviteError::DomainNotAllowed(v)) if v == "other.com"));
+        assert!(allow_list.check(Some("a@other.com"), Some("code")).is_ok());
+        assert!(matches!(allow_list.check(None, None), Err(InviteError::InviteRequired)));
+    }
+
+    #[tokio::test]
+    async fn invite_role_is_returned() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_valid_invite();
+
+        let invite = policy("invite_only", &hasura_client).invite(Some("beta-tester")).await.unwrap();
+
+        assert_eq!(invite.and_then(|v| v.role().clone()).as_deref(), Some("test"));
+        // Только чтение: использование списывает мутация создания пользователя
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "GetInvite");
+    }
+
+    #[tokio::test]
+    async fn unusable_invites() {
+        let cases = [
+            ("query_invite_expired.json", "invite_expired"),
+            ("query_invite_exhausted.json", "invite_exhausted"),
+            ("query_invite_empty.json", "invite_invalid"),
+        ];
+        for (file, code) in cases {
+            let mut hasura_client = MockHasuraClientBuilder::new();
+            hasura_client.with_unusable_invite(file);
+
+            let result = policy("invite_only", &hasura_client).invite(Some("beta-tester")).await;
+
+            assert!(matches!(result, Err(e) if e.error_code() == code), "{file}");
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod account_usecase;
+pub mod admin_usecase;
+pub mod attribute_usecase;
 pub mod auth_usecase;
+pub mod guest_usecase;
+pub mod invite_usecase;
+pub mod role_usecase;
 pub mod sign_up_usecase;
 pub mod integration;
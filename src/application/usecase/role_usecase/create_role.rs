@@ -0,0 +1,110 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::models::role::Role;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
+
+use super::dto::{CreateRoleRequestDto, RoleDto, RoleResponseDto};
+use super::error::RoleError;
+
+/// Добавляет роль в каталог `users.role` (`POST /auth/admin/roles`)
+pub struct CreateRoleUseCase<CUS, QUS> {
+    credentials: Credentials,
+    command_user_service: CUS,
+    query_user_service: QUS,
+}
+
+impl<CUS, QUS> ServiceErrorExt for CreateRoleUseCase<CUS, QUS> {}
+
+impl<CUS, QUS> CreateRoleUseCase<CUS, QUS>
+where
+    CUS: CommandUserService,
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS, CommandUser = CUS>,
+    {
+        let command_user_service = user_provider_factory.command_user();
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, command_user_service, query_user_service }
+    }
+
+    pub async fn execute(&self, dto: CreateRoleRequestDto) -> Result<RoleResponseDto, AppError> {
+        if !Role::is_valid_name(&dto.value) {
+            return self.handler_error(RoleError::InvalidRoleName(dto.value));
+        }
+
+        let roles = match self.query_user_service.list_roles().await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+        if roles.iter().any(|v| v.value() == &dto.value) {
+            return self.handler_error(RoleError::RoleExists(dto.value));
+        }
+
+        let comment = dto.comment.filter(|v| !v.trim().is_empty());
+        let role = match self.command_user_service.create_role(Role::new(dto.value, comment)).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let role = RoleDto::new(&role, self.credentials.role_hierarchy());
+        Ok(RoleResponseDto::Success { role })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<RoleResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn create(hasura_client: &MockHasuraClientBuilder, value: &str) -> Result<RoleResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = CreateRoleUseCase::new(credentials, &user_provider_factory);
+        let dto = CreateRoleRequestDto { value: value.to_string(), comment: Some("Support team".to_string()) };
+        action.execute(dto).await
+    }
+
+    #[tokio::test]
+    async fn role_is_created() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_roles();
+
+        let result = create(&hasura_client, "support").await;
+
+        assert!(matches!(result, Ok(RoleResponseDto::Success { .. })));
+        let body: serde_json::Value = serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["value"], "support");
+    }
+
+    #[tokio::test]
+    async fn existing_role() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_roles();
+
+        let result = create(&hasura_client, "admin").await;
+
+        assert!(matches!(result, Err(e) if e.status == 409 && e.code == "role_exists"));
+    }
+
+    #[tokio::test]
+    async fn invalid_name() {
+        let hasura_client = MockHasuraClientBuilder::new();
+
+        let result = create(&hasura_client, "Support Team").await;
+
+        assert!(matches!(result, Err(e) if e.status == 422));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use crate::domain::user::models::role::{expand_roles, Role};
+
+/// Роль каталога и роли, которые она подразумевает по `role_hierarchy`
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct RoleDto {
+    pub value: String,
+    pub comment: Option<String>,
+    /// Все подразумеваемые роли, с учётом вложенности
+    pub implies: Vec<String>,
+}
+
+impl RoleDto {
+    pub fn new(role: &Role, hierarchy: &BTreeMap<String, Vec<String>>) -> Self {
+        let implies = expand_roles(&[role.value().clone()], hierarchy)
+            .into_iter()
+            .skip(1)
+            .collect();
+        Self { value: role.value().clone(), comment: role.comment().clone(), implies }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CreateRoleRequestDto {
+    pub value: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum RolesResponseDto {
+    Success { roles: Vec<RoleDto> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum RoleResponseDto {
+    Success { role: RoleDto },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
+pub enum RoleDetailsResponseDto {
+    Success { role: RoleDto, users: i64 },
+}
@@ -0,0 +1,66 @@
+use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RoleError {
+    #[error("Role {0} not found")]
+    RoleNotFound(String),
+    #[error("Role {0} already exists")]
+    RoleExists(String),
+    #[error("Role name {0} is not valid")]
+    InvalidRoleName(String),
+    #[error("Configured roles are missing in users.role: {}", .0.join(", "))]
+    UnknownConfiguredRoles(Vec<String>),
+}
+
+impl AppErrorInfo for RoleError {
+    fn client_message(&self) -> String {
+        match self {
+            RoleError::RoleNotFound(v) => format!("Role {} not found", v),
+            RoleError::RoleExists(v) => format!("Role {} already exists", v),
+            RoleError::InvalidRoleName(v) => format!(
+                "Role name {} is not valid: use lowercase latin letters, digits and _",
+                v
+            ),
+            RoleError::UnknownConfiguredRoles(_) => self.internal_error(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            RoleError::UnknownConfiguredRoles(_) => ErrorLevel::Critical,
+            _ => ErrorLevel::Info,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("RoleError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            RoleError::RoleNotFound(_) => 404,
+            RoleError::RoleExists(_) => 409,
+            RoleError::InvalidRoleName(_) => 422,
+            RoleError::UnknownConfiguredRoles(_) => 500,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            RoleError::RoleNotFound(_) => "role_not_found",
+            RoleError::RoleExists(_) => "role_exists",
+            RoleError::InvalidRoleName(_) => "invalid_role_name",
+            RoleError::UnknownConfiguredRoles(_) => "internal_error",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            RoleError::RoleNotFound(v) | RoleError::RoleExists(v) | RoleError::InvalidRoleName(v) => {
+                vec![("role", v.clone())]
+            }
+            RoleError::UnknownConfiguredRoles(_) => Vec::new(),
+        }
+    }
+}
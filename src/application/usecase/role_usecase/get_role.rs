@@ -0,0 +1,84 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{RoleDetailsResponseDto, RoleDto};
+use super::error::RoleError;
+
+/// Описание роли и число её пользователей (`GET /auth/admin/roles/{role}`)
+pub struct GetRoleUseCase<QUS> {
+    credentials: Credentials,
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for GetRoleUseCase<QUS> {}
+
+impl<QUS> GetRoleUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, query_user_service }
+    }
+
+    pub async fn execute(&self, value: String) -> Result<RoleDetailsResponseDto, AppError> {
+        // Неизвестное значение enum Hasura не примет в запросе, поэтому сначала каталог
+        let roles = match self.query_user_service.list_roles().await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+        let Some(role) = roles.iter().find(|v| v.value() == &value) else {
+            return self.handler_error(RoleError::RoleNotFound(value));
+        };
+
+        let users = match self.query_user_service.count_role_users(&value).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let role = RoleDto::new(role, self.credentials.role_hierarchy());
+        Ok(RoleDetailsResponseDto::Success { role, users })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<RoleDetailsResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn describe(value: &str) -> Result<RoleDetailsResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_roles()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = GetRoleUseCase::new(credentials, &user_provider_factory);
+        action.execute(value.to_string()).await
+    }
+
+    #[tokio::test]
+    async fn role_with_users() {
+        let result = describe("default").await;
+        assert!(matches!(result, Ok(RoleDetailsResponseDto::Success { users: 7, .. })));
+    }
+
+    #[tokio::test]
+    async fn unknown_role() {
+        let result = describe("manager").await;
+        assert!(matches!(result, Err(e) if e.status == 404 && e.code == "role_not_found"));
+    }
+}
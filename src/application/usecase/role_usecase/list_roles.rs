@@ -0,0 +1,69 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::dto::{RoleDto, RolesResponseDto};
+
+/// Каталог ролей `users.role` (`GET /auth/admin/roles`)
+pub struct ListRolesUseCase<QUS> {
+    credentials: Credentials,
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ListRolesUseCase<QUS> {}
+
+impl<QUS> ListRolesUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, query_user_service }
+    }
+
+    pub async fn execute(&self) -> Result<RolesResponseDto, AppError> {
+        let roles = match self.query_user_service.list_roles().await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let hierarchy = self.credentials.role_hierarchy();
+        let roles = roles.iter().map(|v| RoleDto::new(v, hierarchy)).collect();
+        Ok(RolesResponseDto::Success { roles })
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<RolesResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn roles_with_implied() {
+        let credentials = Credentials::mock();
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_roles()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = ListRolesUseCase::new(credentials, &user_provider_factory);
+        let Ok(RolesResponseDto::Success { roles }) = action.execute().await else {
+            panic!("roles are not returned");
+        };
+
+        let admin = roles.iter().find(|v| v.value == "admin").unwrap();
+        assert_eq!(admin.implies, vec!["default".to_string()]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod create_role;
+pub mod dto;
+pub mod error;
+pub mod get_role;
+pub mod list_roles;
+pub mod validate_roles;
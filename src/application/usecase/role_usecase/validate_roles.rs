@@ -0,0 +1,86 @@
+use crate::application::error_ext::ServiceErrorExt;
+
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::settings::model::Credentials;
+use crate::domain::user::factories::UserProviderFactory;
+use crate::domain::user::service::QueryUserService;
+
+use super::error::RoleError;
+
+/// Проверка при запуске: роли из настроек (`new_user_role`, `admin_role`,
+/// `role_hierarchy`) должны быть в `users.role`, иначе регистрация упадёт позже
+pub struct ValidateRolesUseCase<QUS> {
+    credentials: Credentials,
+    query_user_service: QUS,
+}
+
+impl<QUS> ServiceErrorExt for ValidateRolesUseCase<QUS> {}
+
+impl<QUS> ValidateRolesUseCase<QUS>
+where
+    QUS: QueryUserService,
+{
+    pub fn new<U>(credentials: Credentials, user_provider_factory: &U) -> Self
+    where
+        U: UserProviderFactory<QueryUser = QUS>,
+    {
+        let query_user_service = user_provider_factory.query_user();
+        Self { credentials, query_user_service }
+    }
+
+    pub async fn execute(&self) -> Result<(), AppError> {
+        let roles = match self.query_user_service.list_roles().await {
+            Ok(v) => v,
+            Err(e) => return Err(self.map_service_error(e)),
+        };
+
+        let missing = self
+            .credentials
+            .configured_roles()
+            .into_iter()
+            .filter(|v| !roles.iter().any(|role| role.value() == v))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return self.handler_error(RoleError::UnknownConfiguredRoles(missing));
+        }
+        Ok(())
+    }
+
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<(), AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    #[tokio::test]
+    async fn configured_roles_exist() {
+        let credentials = Credentials::mock();
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_roles()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = ValidateRolesUseCase::new(credentials, &user_provider_factory);
+
+        assert!(action.execute().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_roles() {
+        let credentials = Credentials::mock();
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_roles_without_admin()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = ValidateRolesUseCase::new(credentials, &user_provider_factory);
+
+        assert!(matches!(action.execute().await, Err(e) if e.status == 500));
+    }
+}
@@ -1,7 +1,13 @@
+use std::str::FromStr;
+use uuid::Uuid;
+
 use crate::application::error_ext::ServiceErrorExt;
-use crate::application::usecase::sign_up_usecase::dto::{ApiKeyDto, CreateApiKeyRequestDto, CreateApiKeyResponseDto, SignUpRequestDto};
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::application::usecase::sign_up_usecase::dto::{ApiKeyDto, ApiKeyOptionsDto, CreateApiKeyRequestDto, CreateApiKeyResponseDto, SignUpRequestDto};
+use crate::domain::errors::service::{AppError, AppErrorInfo};
+use crate::domain::jwt::model::Claims;
+use crate::domain::settings::model::Credentials;
 use crate::domain::user::models::base::AuthMethod;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
 use crate::domain::user::service::{CommandUserService, QueryUserService};
 use crate::domain::verifies::service::{ApiKeyVerifierService, PasswordVerifierService};
 
@@ -17,6 +23,7 @@ const AUTH_TYPE: &str = "apikey";
 const WRONG_CREDENTIALS: &str = "Incorrect login or password";
 
 pub struct CreateApiKeyUseCase<CU, QU, V, A> {
+    credentials: Credentials,
     command_user_service: CU,
     query_user_service: QU,
     password_verifier: V,
@@ -34,7 +41,11 @@ where
     V: PasswordVerifierService,
     A: ApiKeyVerifierService,
 {
-    pub fn new<VP, UP>(user_provider_factory: &UP, verifies_provider_factory: &VP) -> Self
+    pub fn new<VP, UP>(
+        credentials: Credentials,
+        user_provider_factory: &UP,
+        verifies_provider_factory: &VP,
+    ) -> Self
     where
         VP: VerifiesProviderFactory<ApiKeyVerifier = A, PasswordVerifier = V>,
         UP: UserProviderFactory<QueryUser = QU, CommandUser = CU>,
@@ -44,6 +55,7 @@ where
         let api_key_verifier = verifies_provider_factory.api_key_verifier();
         let password_verifier = verifies_provider_factory.password_verifier();
         Self {
+            credentials,
             command_user_service,
             query_user_service,
             password_verifier,
@@ -54,7 +66,7 @@ where
     pub async fn execute(
         &self,
         sing_up_user: CreateApiKeyRequestDto
-    ) -> Result<CreateApiKeyResponseDto, String> {
+    ) -> Result<CreateApiKeyResponseDto, AppError> {
         let user = match self.query_user_service.get_user_by_identifier(&sing_up_user.email, SEARCH_AUTH_TYPE).await {
             Ok(Some(user)) => user,
             Ok(None) => return self.handler_error(UserAttributeError::UserNotFound(sing_up_user.email)),
@@ -74,6 +86,66 @@ where
             Err(e) => return self.handler_error(e),
         };
 
+        self.create(&user, sing_up_user.options).await
+    }
+
+    /// Выпуск ключа по access token - для пользователей без пароля (например, только Telegram).
+    /// Токен проверяется `BearerAuth`, сюда приходят его claims
+    pub async fn execute_with_claims(
+        &self,
+        claims: Claims,
+        options: ApiKeyOptionsDto
+    ) -> Result<CreateApiKeyResponseDto, AppError> {
+        // Токен API ключа не может выпускать новые ключи
+        if claims.is_api_key() {
+            return self.handler_error(UserAttributeError::NotCorrectAccessToken);
+        }
+        let Ok(user_id) = Uuid::from_str(&claims.sub) else {
+            return self.handler_error(UserAttributeError::NotCorrectAccessToken);
+        };
+
+        if let Some(max_age_minutes) = *self.credentials.reauth_max_age_minutes() {
+            let now = chrono::Utc::now().timestamp();
+            let is_recent = claims
+                .auth_time
+                .is_some_and(|v| now - (v as i64) <= max_age_minutes * 60);
+            if !is_recent {
+                return self.handler_error(UserAttributeError::ReauthenticationRequired {
+                    auth_time: claims.auth_time,
+                    max_age_minutes,
+                });
+            }
+        }
+
+        let auth_methods = match self.query_user_service.get_user_by_id(user_id).await {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e),
+        };
+
+        let Some(user) = auth_methods.first() else {
+            return self.handler_error(UserAttributeError::UserNotFound(user_id.to_string()));
+        };
+
+        self.create(user, options).await
+    }
+
+    async fn create(
+        &self,
+        user: &ExtendedAuthMethod,
+        options: ApiKeyOptionsDto
+    ) -> Result<CreateApiKeyResponseDto, AppError> {
+        if let Some(roles) = &options.roles {
+            if roles.is_empty() {
+                return self.handler_error(UserAttributeError::ApiKeyRolesEmpty);
+            }
+            let not_assigned = roles
+                .iter()
+                .find(|role| !user.user().user_roles().iter().any(|v| v.role() == *role));
+            if let Some(role) = not_assigned {
+                return self.handler_error(UserAttributeError::RoleNotAssigned(role.clone()));
+            }
+        }
+
         let api_key = self.api_key_verifier.generate();
 
         let identifier = match self.api_key_verifier.extract_identifier(&api_key) {
@@ -93,13 +165,21 @@ where
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
         };
+        let signing_secret = match self.api_key_verifier.signing_secret(&api_key) {
+            Ok(v) => v,
+            Err(e) => return self.handler_error(e)
+        };
 
         let auth_method = AuthMethod::new(
             user.user_id().clone(),
             AUTH_TYPE.to_string(),
             identifier,
             Some(api_key_hash),
-        );
+        )
+        .with_name(options.name)
+        .with_expires_at(options.expires_at)
+        .with_scope(options.roles, options.scopes)
+        .with_signing_secret(signing_secret);
 
         if let Err(e) = self.command_user_service.add_auth_method(auth_method).await {
             return self.handler_error(e);
@@ -111,12 +191,137 @@ where
     }
 
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<CreateApiKeyResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(CreateApiKeyResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<CreateApiKeyResponseDto, AppError> {
+        Err(self.map_service_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::jwt::factories::JWTProviderFactory;
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user::MockUser;
+    use crate::mock::user_provider::MockUserProvider;
+
+    async fn create(roles: Option<Vec<String>>) -> Result<CreateApiKeyResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_email_auth_method()
+            .with_nonexistent_auth_method()
+            .with_user_creation()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+
+        let action = CreateApiKeyUseCase::new(
+            credentials,
+            &user_provider_factory,
+            &verifies_provider_factory,
+        );
+
+        action.execute(CreateApiKeyRequestDto {
+            email: MockUser::email(),
+            password: MockUser::password(),
+            options: ApiKeyOptionsDto {
+                name: Some("reports".to_string()),
+                roles,
+                scopes: Some(vec!["reports:read".to_string()]),
+                ..Default::default()
+            },
+        }).await
+    }
+
+    async fn create_with_token(auth_time: usize) -> Result<CreateApiKeyResponseDto, AppError> {
+        create_with_claims(|claims| claims.auth_time = Some(auth_time)).await
+    }
+
+    async fn create_with_claims(tweak: impl FnOnce(&mut Claims)) -> Result<CreateApiKeyResponseDto, AppError> {
+        let credentials = Credentials::mock();
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let hasura_client = MockHasuraClientBuilder::new()
+            .with_linked_telegram()
+            .with_nonexistent_auth_method()
+            .with_user_creation()
+            .build();
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client);
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let mut claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        tweak(&mut claims);
+
+        let action = CreateApiKeyUseCase::new(
+            credentials,
+            &user_provider_factory,
+            &verifies_provider_factory,
+        );
+
+        action.execute_with_claims(claims, ApiKeyOptionsDto::default()).await
+    }
+
+    #[tokio::test]
+    async fn scoped_api_key_with_assigned_role() {
+        let result = create(Some(vec!["test".to_string()])).await;
+
+        assert!(matches!(result, Ok(CreateApiKeyResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn scoped_api_key_with_not_assigned_role() {
+        let result = create(Some(vec!["admin".to_string()])).await;
+
+        let Err(e) = result else {
+            panic!("not assigned role is accepted");
+        };
+        assert_eq!(e.status, 403);
+        assert!(e.message.contains("admin"));
+    }
+
+    #[tokio::test]
+    async fn scoped_api_key_without_roles() {
+        let result = create(Some(Vec::new())).await;
+
+        assert!(matches!(result, Err(e) if e.status == 422));
+    }
+
+    #[tokio::test]
+    async fn api_key_with_recent_session() {
+        let now = chrono::Utc::now().timestamp() as usize;
+
+        let result = create_with_token(now - 60).await;
+
+        assert!(matches!(result, Ok(CreateApiKeyResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn api_key_with_stale_session() {
+        let max_age = Credentials::mock().reauth_max_age_minutes().unwrap() as usize;
+        let now = chrono::Utc::now().timestamp() as usize;
+
+        let result = create_with_token(now - max_age * 60 - 1).await;
+
+        let Err(e) = result else {
+            panic!("stale session is accepted");
+        };
+        assert_eq!(e.status, 401);
+        assert_eq!(e.code, "reauthentication_required");
+        assert_eq!(e.message, "Recent authentication required");
+    }
+
+    #[tokio::test]
+    async fn api_key_token_cannot_create_api_key() {
+        let result = create_with_claims(|claims| {
+            claims.amr = Some(vec!["apikey".to_string()]);
+            claims.auth_time = None;
+        })
+        .await;
+
+        assert!(matches!(result, Err(e) if e.status == 401 && e.code != "reauthentication_required"));
     }
 }
@@ -1,20 +1,34 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::domain::user::models::base::UserAttribute;
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct SignUpRequestDto {
-    pub username: String,
+    /// Необязателен, если схема `users.attribute` не требует username
+    #[serde(default)]
+    pub username: Option<String>,
     pub email: String,
     pub password: String,
+    /// Дополнительные атрибуты (`company`, `phone`, ...), проверяются по схеме
+    /// `users.attribute`. `email` и `username` задаются полями выше
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+    /// Код приглашения, обязателен при `signup_mode = "invite_only"`
+    #[serde(default)]
+    pub invite: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
 pub enum SignUpResponseDto {
-    Success { user: UserDataDto },
-    Error { err_msg: String },
+    Success { user: UserDataDto, attributes: Vec<UserAttribute> },
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct UserDataDto {
-    pub username: String,
+    pub username: Option<String>,
     pub email: String,
 }
 
@@ -27,11 +41,27 @@ pub struct ApiKeyDto {
 pub struct CreateApiKeyRequestDto {
     pub email: String,
     pub password: String,
+    #[serde(flatten)]
+    pub options: ApiKeyOptionsDto,
+}
+
+/// Параметры нового API ключа
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ApiKeyOptionsDto {
+    /// Подпись ключа, видна в списке ключей
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    /// Подмножество ролей пользователя; без него ключ получает все роли
+    #[serde(default)]
+    pub roles: Option<Vec<String>>,
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", "error"
+#[serde(tag = "status", rename_all = "lowercase")] // JSON: "success", ошибки - problem+json
 pub enum CreateApiKeyResponseDto {
     Success { auth_data: ApiKeyDto },
-    Error { err_msg: String },
 }
\ No newline at end of file
@@ -1,10 +1,17 @@
 use crate::application::error_ext::ServiceErrorExt;
+use crate::application::usecase::guest_usecase::upgrade::{guest_id, UpgradeGuestUseCase};
+use crate::application::usecase::attribute_usecase::error::{AttributeError, FIELD_RESERVED};
+use crate::application::usecase::attribute_usecase::validate::ValidateAttributesUseCase;
+use crate::application::usecase::invite_usecase::policy::SignUpPolicyUseCase;
 use crate::application::usecase::sign_up_usecase::dto::{SignUpRequestDto, SignUpResponseDto, UserDataDto};
-use crate::domain::errors::service::{AppErrorInfo, ErrorLevel};
+use crate::domain::errors::service::{AppError, AppErrorInfo, FieldError};
+use crate::domain::jwt::factories::JWTProviderFactory;
+use crate::domain::jwt::service::TokenService;
 use crate::domain::settings::model::Credentials;
 use crate::domain::user::factories::UserProviderFactory;
 use crate::domain::user::models::base::{AuthMethod, UserAttribute, UserRole};
-use crate::domain::user::service::CommandUserService;
+use crate::domain::user::models::new_user::NewUser;
+use crate::domain::user::service::{CommandUserService, QueryUserService};
 use crate::domain::verifies::factories::VerifiesProviderFactory;
 use crate::domain::verifies::service::PasswordVerifierService;
 
@@ -15,40 +22,78 @@ const AUTH_TYPE: &str = "email";
 const NAME_ATTRIBUTE: &str = "username";
 const EMAIL_ATTRIBUTE: &str = "email";
 
-pub struct SignUpWithEmailUseCase<U, V> {
+pub struct SignUpWithEmailUseCase<U, Q, V, TP> {
     credentials: Credentials,
     command_user_service: U,
     password_verifier: V,
+    token_provider: TP,
+    validate_attributes_use_case: ValidateAttributesUseCase<Q>,
+    sign_up_policy_use_case: SignUpPolicyUseCase<Q>,
+    upgrade_guest_use_case: UpgradeGuestUseCase<U, Q>,
 }
 
 
-impl<U, V> ServiceErrorExt for SignUpWithEmailUseCase<U, V> {}
+impl<U, Q, V, TP> ServiceErrorExt for SignUpWithEmailUseCase<U, Q, V, TP> {}
 
 
-impl<U, V> SignUpWithEmailUseCase<U, V>
+impl<U, Q, V, TP> SignUpWithEmailUseCase<U, Q, V, TP>
 where
     U: CommandUserService,
+    Q: QueryUserService,
     V: PasswordVerifierService,
+    TP: TokenService,
 {
-    pub fn new<VP, UP>(
+    pub fn new<VP, UP, T>(
         credentials: Credentials,
         verifies_provider_factory: &VP,
         user_provider_factory: &UP,
+        jwtprovider_factory: &T,
     ) -> Self
     where
         VP: VerifiesProviderFactory<PasswordVerifier = V>,
-        UP: UserProviderFactory<CommandUser = U>,
+        UP: UserProviderFactory<CommandUser = U, QueryUser = Q>,
+        T: JWTProviderFactory<Tokens = TP>,
     {
         let password_verifier = verifies_provider_factory.password_verifier();
         let command_user_service = user_provider_factory.command_user();
+        let token_provider = jwtprovider_factory.token_service();
+        let validate_attributes_use_case = ValidateAttributesUseCase::new(user_provider_factory);
+        let sign_up_policy_use_case = SignUpPolicyUseCase::new(credentials.clone(), user_provider_factory);
+        let upgrade_guest_use_case = UpgradeGuestUseCase::new(credentials.clone(), user_provider_factory);
         Self {
             credentials,
             command_user_service,
             password_verifier,
+            token_provider,
+            validate_attributes_use_case,
+            sign_up_policy_use_case,
+            upgrade_guest_use_case,
         }
     }
 
-    pub async fn execute(&self, user: SignUpRequestDto) -> Result<SignUpResponseDto, String> {
+    /// `guest_token` - access токен гостя из `POST /auth/anonymous`:
+    /// учётные данные добавляются ему, id и данные гостя сохраняются
+    pub async fn execute(&self, user: SignUpRequestDto, guest_token: Option<String>) -> Result<SignUpResponseDto, AppError> {
+        // До проверки email, чтобы закрытая регистрация не выдавала занятые адреса
+        if let Err(e) = self.sign_up_policy_use_case.check(Some(&user.email), user.invite.as_deref()) {
+            return self.handler_error(e);
+        }
+
+        let guest = match guest_token {
+            Some(token) => {
+                let guest_id = match guest_id(&self.token_provider, &token) {
+                    Ok(v) => v,
+                    Err(e) => return self.handler_error(e),
+                };
+                match self.upgrade_guest_use_case.find_guest(guest_id).await {
+                    Ok(v) => Some(v),
+                    Err(e) => return self.handler_error(e),
+                }
+            }
+            None => None,
+        };
+        let user_id = guest.as_ref().map_or(NewUser::PENDING_ID, |v| *v.user_id());
+
         let password_hash = match self.password_verifier.create_hash(&user.password) {
             Ok(v) => v,
             Err(e) => return self.handler_error(e)
@@ -65,49 +110,63 @@ where
             return self.handler_error(UserAttributeError::EmailIsBusy);
         };
 
-        let new_user = match self.command_user_service.add_user().await {
+        let reserved = [NAME_ATTRIBUTE, EMAIL_ATTRIBUTE]
+            .into_iter()
+            .filter(|v| user.attributes.contains_key(*v))
+            .map(|v| FieldError::new(v, FIELD_RESERVED))
+            .collect::<Vec<_>>();
+        if !reserved.is_empty() {
+            return self.handler_error(AttributeError::Invalid(reserved));
+        }
+
+        let mut attributes = user.attributes.clone();
+        if let Some(username) = user.username.as_ref() {
+            attributes.insert(NAME_ATTRIBUTE.to_string(), username.clone());
+        }
+        attributes.insert(EMAIL_ATTRIBUTE.to_string(), user.email.clone());
+        // Проверяется и записывается одно и то же значение
+        for value in attributes.values_mut() {
+            *value = value.trim().to_string();
+        }
+
+        // До вставки, чтобы вернуть ошибки по полям, а не ошибку Hasura
+        let checked = attributes.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>();
+        let guest_id = guest.as_ref().map(|v| *v.user_id());
+        if let Err(e) = self.validate_attributes_use_case.execute_new_user(guest_id, &checked).await {
+            return self.handler_error(e);
+        }
+
+        // Приглашение списывается той же мутацией, что создаёт пользователя
+        let invite = match self.sign_up_policy_use_case.invite(user.invite.as_deref()).await {
             Ok(v) => v,
-            Err(e) => return self.handler_error(e)
+            Err(e) => return self.handler_error(e),
         };
 
-        let user_id = new_user.id();
         let auth_method = AuthMethod::new(
-            user_id.clone(),
+            user_id,
             AUTH_TYPE.to_string(),
             user.email.clone(),
             Some(password_hash.to_string()),
         );
-
-        if let Err(e) = self.command_user_service.add_auth_method(auth_method).await {
-            return self.handler_error(e);
-        }
-
-        let user_attribute = vec![
-            UserAttribute::new(
-                user_id.clone(),
-                NAME_ATTRIBUTE.to_string(),
-                user.username.clone(),
-            ),
-            UserAttribute::new(
-                user_id.clone(),
-                EMAIL_ATTRIBUTE.to_string(),
-                user.email.clone(),
-            ),
-        ];
-
-        if let Err(e) = self.command_user_service.add_user_attribute(user_attribute).await {
-            return self.handler_error(e);
-        }
-
+        let user_attributes = attributes
+            .into_iter()
+            .map(|(attribute, value)| UserAttribute::new(user_id, attribute, value))
+            .collect::<Vec<_>>();
         let user_role = UserRole::new(
             true,
-            self.credentials.new_user_role().with_email().clone(),
-            user_id.clone(),
+            invite
+                .as_ref()
+                .and_then(|v| v.role().clone())
+                .unwrap_or_else(|| self.credentials.new_user_role().with_email().clone()),
+            user_id,
         );
 
-        if let Err(e) = self.command_user_service.add_role(user_role).await{
-            return self.handler_error(e);
-        }
+        let new_user = NewUser::new(vec![auth_method], user_attributes, vec![user_role])
+            .with_invite(invite.map(|v| v.code().clone()));
+        let created = match guest.as_ref() {
+            Some(guest) => self.upgrade_guest_use_case.attach(guest, new_user).await.map_err(|e| self.map_service_error(e)),
+            None => self.command_user_service.create_user_with(new_user).await.map_err(|e| self.map_service_error(e)),
+        }?;
 
         let user_dto = UserDataDto {
             email: user.email.clone(),
@@ -115,18 +174,275 @@ where
         };
 
         Ok(SignUpResponseDto::Success {
-            user: user_dto
+            user: user_dto,
+            attributes: created.user().user_attributes().clone(),
         })
         
     }
 
-    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<SignUpResponseDto, String> {
-        match e.level() {
-            ErrorLevel::Info | ErrorLevel::Warning => Ok(SignUpResponseDto::Error {
-                err_msg: self.map_service_error(e),
-            }),
-            _ => Err(self.map_service_error(e)),
-        }
+    fn handler_error<E: AppErrorInfo>(&self, e: E) -> Result<SignUpResponseDto, AppError> {
+        Err(self.map_service_error(e))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use crate::domain::jwt::service::JwtClaimsService;
+    use crate::domain::user::models::extended::ExtendedAuthMethod;
+    use crate::infrastructure::jwt::factory::JWTProvider;
+    use crate::infrastructure::verifies::factory::VerifiesProvider;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::user_provider::MockUserProvider;
+
+    fn request(attributes: &[(&str, &str)]) -> SignUpRequestDto {
+        SignUpRequestDto {
+            username: Some("test".to_string()),
+            email: "test@test.com".to_string(),
+            password: "password".to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+            invite: None,
+        }
+    }
+
+    async fn sign_up(hasura_client: &MockHasuraClientBuilder, dto: SignUpRequestDto) -> Result<SignUpResponseDto, AppError> {
+        sign_up_with(Credentials::mock(), hasura_client, dto).await
+    }
+
+    async fn sign_up_with(
+        credentials: Credentials,
+        hasura_client: &MockHasuraClientBuilder,
+        dto: SignUpRequestDto,
+    ) -> Result<SignUpResponseDto, AppError> {
+        sign_up_as(credentials, hasura_client, dto, None).await
+    }
+
+    async fn sign_up_as(
+        credentials: Credentials,
+        hasura_client: &MockHasuraClientBuilder,
+        dto: SignUpRequestDto,
+        guest_token: Option<String>,
+    ) -> Result<SignUpResponseDto, AppError> {
+        let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
+        let jwtprovider_factory = JWTProvider::new(credentials.clone());
+        let user_provider_factory = MockUserProvider::new(credentials.clone(), hasura_client.build());
+
+        let action = SignUpWithEmailUseCase::new(
+            credentials,
+            &verifies_provider_factory,
+            &user_provider_factory,
+            &jwtprovider_factory,
+        );
+        action.execute(dto, guest_token).await
+    }
+
+    fn guest_token() -> String {
+        let jwtprovider_factory = JWTProvider::new(Credentials::mock());
+        let claims = jwtprovider_factory
+            .claims_service()
+            .access_claims(&ExtendedAuthMethod::mock())
+            .unwrap();
+        jwtprovider_factory.token_service().generate_access(claims).unwrap()
+    }
+
+    fn invite_only() -> Credentials {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        credentials["signup_mode"] = serde_json::json!("invite_only");
+        serde_json::from_value(credentials).unwrap()
+    }
+
+    fn creation() -> MockHasuraClientBuilder {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_nonexistent_auth_method().with_attribute_schema().with_user_creation();
+        hasura_client
+    }
+
+    #[tokio::test]
+    async fn sign_up_with_valid_attributes() {
+        let result = sign_up(&creation(), request(&[])).await;
+
+        assert!(matches!(result, Ok(SignUpResponseDto::Success { .. })));
+    }
+
+    #[tokio::test]
+    async fn sign_up_with_extra_attributes() {
+        let dto = request(&[("company", "ACME"), ("phone", "+79991234567")]);
+
+        let result = sign_up(&creation(), dto).await;
+
+        let Ok(SignUpResponseDto::Success { user, attributes }) = result else {
+            panic!("valid attributes are rejected");
+        };
+        assert_eq!(user.username.as_deref(), Some("test"));
+        assert_eq!(attributes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn user_is_created_by_one_mutation() {
+        let hasura_client = creation();
+
+        let result = sign_up(&hasura_client, request(&[("company", "ACME")])).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "InsertUserWith");
+        let object = &body["variables"]["object"];
+        assert_eq!(object["auth_methods"]["data"][0]["identifier"], "test@test.com");
+        assert_eq!(object["user_attributes"]["data"].as_array().unwrap().len(), 3);
+        assert_eq!(object["user_roles"]["data"][0]["is_default"], true);
+    }
+
+    #[tokio::test]
+    async fn attributes_are_stored_trimmed() {
+        let hasura_client = creation();
+
+        let result = sign_up(&hasura_client, request(&[("company", "  ACME ")])).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        let attributes = body["variables"]["object"]["user_attributes"]["data"].as_array().unwrap().clone();
+        let company = attributes.iter().find(|v| v["attribute"] == "company").unwrap();
+        assert_eq!(company["value"], "ACME");
+    }
+
+    #[tokio::test]
+    async fn guest_is_upgraded_instead_of_created() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_guest().with_attribute_schema().with_nonexistent_auth_method();
+
+        let result = sign_up_as(Credentials::mock(), &hasura_client, request(&[]), Some(guest_token())).await;
+
+        assert!(matches!(result, Ok(SignUpResponseDto::Success { .. })));
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "GetUserById");
+    }
+
+    #[tokio::test]
+    async fn invalid_guest_token_is_rejected() {
+        let result = sign_up_as(Credentials::mock(), &creation(), request(&[]), Some("token".to_string())).await;
+
+        assert!(matches!(result, Err(e) if e.status == 401));
+    }
+
+    #[tokio::test]
+    async fn failed_insert_is_reported() {
+        let mut hasura_client = creation();
+        hasura_client.with_error_insert_user();
+
+        let result = sign_up(&hasura_client, request(&[])).await;
+
+        assert!(matches!(result, Err(e) if e.status == 500));
+    }
+
+    #[tokio::test]
+    async fn invalid_attributes_are_reported_per_field() {
+        let mut dto = request(&[("phone", "12-34"), ("shoe_size", "42")]);
+        dto.username = Some("a b".to_string());
+
+        let result = sign_up(&creation(), dto).await;
+
+        let Err(e) = result else {
+            panic!("invalid attributes are accepted");
+        };
+        assert_eq!(e.status, 422);
+        assert_eq!(e.code, "invalid_attributes");
+        assert_eq!(
+            e.errors,
+            vec![
+                FieldError::new("shoe_size", "unknown"),
+                FieldError::new("phone", "invalid_type"),
+                FieldError::new("username", "invalid_format"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn email_in_attributes_is_reserved() {
+        let result = sign_up(&creation(), request(&[("email", "other@test.com")])).await;
+
+        assert!(matches!(result, Err(e) if e.status == 422 && e.errors == vec![FieldError::new("email", "reserved")]));
+    }
+
+    #[tokio::test]
+    async fn taken_username_does_not_create_user() {
+        let mut hasura_client = creation();
+        hasura_client.with_taken_attribute_value();
+
+        let result = sign_up(&hasura_client, request(&[])).await;
+
+        let Err(e) = result else {
+            panic!("taken username is accepted");
+        };
+        assert_eq!(e.status, 409);
+        assert_eq!(e.code, "attribute_taken");
+        assert!(!e.errors.is_empty());
+        let request = hasura_client.recorder().read_data().await.unwrap();
+        assert!(request.contains("CheckAttributeValue"));
+    }
+
+    #[tokio::test]
+    async fn invite_role_overrides_new_user_role() {
+        let mut hasura_client = creation();
+        hasura_client.with_valid_invite();
+        let mut dto = request(&[]);
+        dto.invite = Some("beta-tester".to_string());
+
+        let result = sign_up_with(invite_only(), &hasura_client, dto).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["object"]["user_roles"]["data"][0]["role"], "test");
+    }
+
+    #[tokio::test]
+    async fn invite_is_used_by_user_creation() {
+        let mut hasura_client = creation();
+        hasura_client.with_valid_invite();
+        let mut dto = request(&[]);
+        dto.invite = Some(" beta-tester ".to_string());
+
+        let result = sign_up_with(invite_only(), &hasura_client, dto).await;
+
+        assert!(result.is_ok());
+        // Отдельного списания до вставки нет: при ошибке вставки приглашение остаётся
+        let body: serde_json::Value =
+            serde_json::from_str(&hasura_client.recorder().read_data().await.unwrap()).unwrap();
+        assert_eq!(body["operationName"], "InsertUserWith");
+        assert_eq!(body["variables"]["invite"], "beta-tester");
+        assert_eq!(body["variables"]["with_invite"], true);
+    }
+
+    #[tokio::test]
+    async fn invite_only_without_invite() {
+        let hasura_client = creation();
+
+        let result = sign_up_with(invite_only(), &hasura_client, request(&[])).await;
+
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "invite_required"));
+        assert!(hasura_client.recorder().read_data().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn expired_invite_does_not_create_user() {
+        let mut hasura_client = creation();
+        hasura_client.with_unusable_invite("query_invite_expired.json");
+        let mut dto = request(&[]);
+        dto.invite = Some("beta-tester".to_string());
+
+        let result = sign_up_with(invite_only(), &hasura_client, dto).await;
+
+        assert!(matches!(result, Err(e) if e.status == 403 && e.code == "invite_expired"));
+        let request = hasura_client.recorder().read_data().await.unwrap();
+        assert!(!request.contains("InsertUserWith"));
+    }
+}
@@ -15,6 +15,14 @@ pub enum UserAttributeError {
     NotCorrectApiKey,
     #[error("Password Hash is not verified")]
     NotCorrectPassword,
+    #[error("Role {0} is not assigned to the user")]
+    RoleNotAssigned(String),
+    #[error("Api key roles are empty")]
+    ApiKeyRolesEmpty,
+    #[error("Access token is not verified")]
+    NotCorrectAccessToken,
+    #[error("Authenticated at {auth_time:?}, required within {max_age_minutes} minutes")]
+    ReauthenticationRequired { auth_time: Option<usize>, max_age_minutes: i64 },
 }
 
 impl UserAttributeError {
@@ -32,6 +40,18 @@ impl AppErrorInfo for UserAttributeError {
             UserAttributeError::EmailIsBusy => {
                 format!("This email already is busy")
             }
+            UserAttributeError::RoleNotAssigned(role) => {
+                format!("Role {} is not assigned to the user", role)
+            }
+            UserAttributeError::ApiKeyRolesEmpty => {
+                format!("Api key needs at least one role")
+            }
+            UserAttributeError::NotCorrectAccessToken => {
+                format!("Unauthorized")
+            }
+            UserAttributeError::ReauthenticationRequired { .. } => {
+                format!("Recent authentication required")
+            }
             _ => self.msg_not_correct_credentials(),
         }
     }
@@ -59,6 +79,45 @@ impl AppErrorInfo for UserAttributeError {
             UserAttributeError::EmailIsBusy => {
                 format!("Try create user with busy email")
             }
+            UserAttributeError::RoleNotAssigned(role) => {
+                format!("Try create api key with not assigned role {}", role)
+            }
+            UserAttributeError::ApiKeyRolesEmpty => {
+                format!("Try create api key with empty roles")
+            }
+            UserAttributeError::NotCorrectAccessToken => {
+                format!("Try create api key with not correct access token")
+            }
+            UserAttributeError::ReauthenticationRequired { .. } => {
+                format!("Try create api key with stale session: {}", self)
+            }
+        }
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            UserAttributeError::EmailIsBusy => 409,
+            UserAttributeError::RoleNotAssigned(_) => 403,
+            UserAttributeError::ApiKeyRolesEmpty => 422,
+            _ => 401,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            UserAttributeError::EmailIsBusy => "email_taken",
+            UserAttributeError::RoleNotAssigned(_) => "role_not_assigned",
+            UserAttributeError::ApiKeyRolesEmpty => "api_key_roles_empty",
+            UserAttributeError::NotCorrectAccessToken => "invalid_token",
+            UserAttributeError::ReauthenticationRequired { .. } => "reauthentication_required",
+            _ => "invalid_credentials",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            UserAttributeError::RoleNotAssigned(role) => vec![("role", role.clone())],
+            _ => Vec::new(),
         }
     }
 }
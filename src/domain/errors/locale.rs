@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+/// Атрибут пользователя с предпочитаемым языком, попадает в access токен
+pub const LOCALE_ATTRIBUTE: &str = "locale";
+
+lazy_static! {
+    static ref EN: HashMap<String, String> = catalog(include_str!("../../../locales/en.json"));
+    static ref RU: HashMap<String, String> = catalog(include_str!("../../../locales/ru.json"));
+}
+
+#[cfg(feature = "actix")]
+tokio::task_local! {
+    static CURRENT: Locale;
+}
+
+fn catalog(json: &str) -> HashMap<String, String> {
+    serde_json::from_str(json).expect("valid locale catalog")
+}
+
+/// Язык сообщений об ошибках. Каталоги лежат в `locales/<tag>.json`,
+/// ключ - `AppErrorInfo::error_code`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    /// `ru`, `ru-RU`, `EN_us` ...; неизвестный язык - None
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "ru" => Some(Locale::Ru),
+            _ => None,
+        }
+    }
+
+    /// Первый поддерживаемый язык из `Accept-Language` с учётом q
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut tags = accept_language
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let tag = params.next()?.trim();
+                let q = params
+                    .find_map(|v| v.trim().strip_prefix("q="))
+                    .map(|v| v.parse::<f32>().unwrap_or(0.0))
+                    .unwrap_or(1.0);
+                (q > 0.0).then_some((tag, q))
+            })
+            .collect::<Vec<_>>();
+        // sort_by стабильная - при равных q сохраняется порядок клиента
+        tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+        tags.into_iter().find_map(|(tag, _)| Self::from_tag(tag))
+    }
+
+    /// Сообщение из каталога с подстановкой `{name}`; без перевода - None
+    pub fn message(&self, code: &str, args: &[(&str, String)]) -> Option<String> {
+        let catalog = match self {
+            Locale::En => &*EN,
+            Locale::Ru => &*RU,
+        };
+        let message = catalog.get(code)?;
+        Some(args.iter().fold(message.clone(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        }))
+    }
+
+    /// Язык текущего запроса, вне запроса - английский
+    pub fn current() -> Self {
+        #[cfg(feature = "actix")]
+        return CURRENT.try_with(|v| *v).unwrap_or_default();
+        #[cfg(not(feature = "actix"))]
+        return Self::default();
+    }
+
+    /// Выполняет future с этим языком в качестве [`Locale::current`]
+    #[cfg(feature = "actix")]
+    pub async fn scope<F: std::future::Future>(self, f: F) -> F::Output {
+        CURRENT.scope(self, f).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_accept_language() {
+        assert_eq!(Locale::negotiate("ru-RU,ru;q=0.9,en-US;q=0.8"), Some(Locale::Ru));
+        assert_eq!(Locale::negotiate("de-DE, en;q=0.5, ru;q=0.7"), Some(Locale::Ru));
+        assert_eq!(Locale::negotiate("ru;q=0, en"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("de, fr"), None);
+    }
+
+    #[test]
+    fn catalogs_have_same_codes() {
+        let mut en = EN.keys().collect::<Vec<_>>();
+        let mut ru = RU.keys().collect::<Vec<_>>();
+        en.sort();
+        ru.sort();
+        assert_eq!(en, ru);
+    }
+
+    #[test]
+    fn message_with_args() {
+        let args = [("role", "admin".to_string())];
+        assert_eq!(
+            Locale::Ru.message("role_not_assigned", &args).unwrap(),
+            "Роль admin не назначена пользователю"
+        );
+        assert_eq!(Locale::En.message("unknown_code", &args), None);
+    }
+
+    #[tokio::test]
+    async fn scoped_locale() {
+        assert_eq!(Locale::current(), Locale::En);
+        assert_eq!(Locale::Ru.scope(async { Locale::current() }).await, Locale::Ru);
+    }
+}
@@ -1 +1,2 @@
+pub mod locale;
 pub mod service;
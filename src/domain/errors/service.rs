@@ -1,3 +1,5 @@
+use super::locale::Locale;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ErrorLevel {
     Info,
@@ -13,4 +15,80 @@ pub trait AppErrorInfo {
     fn level(&self) -> ErrorLevel;
     fn client_message(&self) -> String;
     fn log_message(&self) -> String;
+    /// HTTP статус ответа. По умолчанию ошибки клиента (Info/Warning) - 400, остальные - 500
+    fn status_code(&self) -> u16 {
+        match self.level() {
+            ErrorLevel::Info | ErrorLevel::Warning => 400,
+            _ => 500,
+        }
+    }
+    /// Стабильный код ошибки для клиентов, в отличие от client_message не меняется
+    fn error_code(&self) -> &'static str {
+        match self.level() {
+            ErrorLevel::Info | ErrorLevel::Warning => "bad_request",
+            _ => "internal_error",
+        }
+    }
+    /// Значения для подстановки в сообщение каталога, например `{role}`
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+    /// Ошибки отдельных полей запроса, например атрибутов пользователя
+    fn field_errors(&self) -> Vec<FieldError> {
+        Vec::new()
+    }
+}
+
+/// Ошибка одного поля: `{"field": "username", "code": "not_unique"}`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str) -> Self {
+        Self { field: field.to_string(), code: code.to_string() }
+    }
+}
+
+/// Ошибка use case в том виде, в котором её получает клиент
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct AppError {
+    pub status: u16,
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl AppError {
+    pub fn new(status: u16, code: &str, message: &str) -> Self {
+        Self { status, code: code.to_string(), message: message.to_string(), errors: Vec::new() }
+    }
+
+    /// Сообщение из каталога языка, без перевода - `client_message`
+    pub fn localized<E: AppErrorInfo + ?Sized>(e: &E, locale: Locale) -> Self {
+        let message = locale
+            .message(e.error_code(), &e.message_args())
+            .unwrap_or_else(|| e.client_message());
+        Self {
+            status: e.status_code(),
+            code: e.error_code().to_string(),
+            message,
+            errors: e.field_errors(),
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.status, self.code, self.message)
+    }
+}
+
+impl<E: AppErrorInfo> From<E> for AppError {
+    fn from(e: E) -> Self {
+        Self::localized(&e, Locale::current())
+    }
 }
@@ -23,6 +23,9 @@ impl HasuraClaims {
     }
 }
 
+/// Значение `amr` у токенов, выпущенных по API ключу
+pub const API_KEY_AMR: &str = "apikey";
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Claims {
     pub sub: String,
@@ -30,6 +33,21 @@ pub struct Claims {
     pub exp: usize,
     #[serde(rename = "https://hasura.io/jwt/claims")]
     pub hasura_claims: HasuraClaims,
+    /// Ограничения API ключа; у обычных токенов отсутствует
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<usize>,
+    /// Способ входа (OIDC amr): `email`, `telegram`, `apikey`, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amr: Option<Vec<String>>,
+    /// Когда пользователь последний раз вводил учётные данные (OIDC auth_time).
+    /// При обновлении токена не меняется
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_time: Option<usize>,
+    /// Язык пользователя из атрибута `locale`, по нему выбираются сообщения об ошибках
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 impl Claims {
@@ -39,18 +57,63 @@ impl Claims {
             admin,
             exp,
             hasura_claims,
+            scopes: None,
+            iat: None,
+            amr: None,
+            auth_time: None,
+            locale: None,
         }
     }
+
+    pub fn with_auth_time(mut self, iat: usize, auth_time: usize) -> Self {
+        self.iat = Some(iat);
+        self.auth_time = Some(auth_time);
+        self
+    }
+
+    /// Вход без ввода учётных данных (API ключ): auth_time не ставится
+    pub fn with_iat(mut self, iat: usize) -> Self {
+        self.iat = Some(iat);
+        self
+    }
+
+    pub fn with_amr(mut self, auth_type: &str) -> Self {
+        self.amr = Some(vec![auth_type.to_string()]);
+        self
+    }
+
+    /// Токен выпущен по API ключу. Ключи с ограничениями до появления `amr`
+    /// отличались только наличием `scopes`
+    pub fn is_api_key(&self) -> bool {
+        self.scopes.is_some() || self.amr.as_ref().is_some_and(|v| v.iter().any(|v| v == API_KEY_AMR))
+    }
+
+    pub fn with_scopes(mut self, scopes: Option<Vec<String>>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
+    }
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct RefreshClaims {
     pub sub: String,
     pub exp: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_time: Option<usize>,
 }
 
 impl RefreshClaims {
     pub fn new(sub: String, exp: usize) -> Self {
-        Self { sub, exp }
+        Self { sub, exp, auth_time: None }
+    }
+
+    pub fn with_auth_time(mut self, auth_time: usize) -> Self {
+        self.auth_time = Some(auth_time);
+        self
     }
 }
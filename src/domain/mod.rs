@@ -1,5 +1,6 @@
 pub mod errors;
 pub mod jwt;
 pub mod settings;
+pub mod telegram;
 pub mod user;
 pub mod verifies;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use getset::{Getters, Setters};
 
 #[derive(
@@ -24,11 +26,143 @@ pub struct Credentials {
     new_user_role: NewUserRole,
     #[get = "pub"]
     api_key_length: u16,
+    /// Префикс новых API ключей, по нему ключ находят сканеры секретов
+    #[get = "pub"]
+    #[serde(default = "default_api_key_prefix")]
+    api_key_prefix: String,
+    /// Ключ HMAC-SHA256 для хэшей API ключей (AUTH_API_KEY_HASH_SECRET).
+    /// Без него новые ключи хэшируются bcrypt
+    #[get = "pub"]
+    #[serde(default)]
+    api_key_hash_secret: Option<String>,
+    /// Сколько секунд проверенный API ключ не перепроверяется
+    #[get = "pub"]
+    #[serde(default = "default_api_key_cache_ttl")]
+    api_key_cache_ttl: i64,
+    /// Сколько секунд схема `users.attribute` не перечитывается, 0 - без кеша
+    #[get = "pub"]
+    #[serde(default = "default_attribute_schema_cache_ttl")]
+    attribute_schema_cache_ttl: i64,
+    /// AES-256-GCM ключ (hex, 32 байта) для ключей подписи запросов
+    /// (AUTH_REQUEST_SIGNING_KEY). Без него подпись запросов выключена
+    #[get = "pub"]
+    #[serde(default)]
+    request_signing_key: Option<String>,
+    /// Допустимое расхождение timestamp подписанного запроса (секунды)
+    #[get = "pub"]
+    #[serde(default = "default_request_signature_max_skew")]
+    request_signature_max_skew: i64,
+    #[get = "pub"]
+    #[serde(default)]
+    telegram_bots: Vec<TelegramBot>,
+    /// Токен единственного бота из старых настроек (`AUTH_BOT_TOKEN`),
+    /// см. `with_legacy_bot_token`
+    #[serde(default, skip_serializing)]
+    bot_token: Option<String>,
+    #[get = "pub"]
+    #[serde(default)]
+    telegram_link_scope: TelegramLinkScope,
+    #[get = "pub"]
+    telegram_auth_max_age: i64, //seconds
+    #[get = "pub"]
+    telegram_api_url: String,
+    #[get = "pub"]
+    telegram_login_ttl: i64, //seconds
+    /// Как давно пользователь должен был ввести учётные данные для чувствительных
+    /// операций (выпуск API ключа по сессии). Без значения проверка отключена,
+    /// кроме удаления аккаунта: для него по умолчанию 15 минут
+    #[get = "pub"]
+    #[serde(default)]
+    reauth_max_age_minutes: Option<i64>,
+    /// Атрибуты, которые пользователь меняет сам через `PATCH /auth/me`
+    #[get = "pub"]
+    #[serde(default)]
+    user_editable_attributes: Vec<String>,
+    /// Роль из `x-hasura-allowed-roles`, открывающая `/auth/admin`
+    #[get = "pub"]
+    #[serde(default = "default_admin_role")]
+    admin_role: String,
+    /// Роль -> подразумеваемые ею роли, раскрываются в `x-hasura-allowed-roles`
+    #[get = "pub"]
+    #[serde(default)]
+    role_hierarchy: BTreeMap<String, Vec<String>>,
+    /// Кто может создать пользователя: email регистрацией или первым входом через Telegram
+    #[get = "pub"]
+    #[serde(default)]
+    signup_mode: SignUpMode,
+    /// Домены email для `signup_mode = "allow_list"`, без `@`
+    #[get = "pub"]
+    #[serde(default)]
+    signup_allowed_domains: Vec<String>,
+    /// Роль гостя из `POST /auth/anonymous`. Без значения, как и при `signup_mode` кроме `open`, гости не создаются
+    #[get = "pub"]
+    #[serde(default)]
+    guest_role: Option<String>,
+    /// Через сколько дней удаляется аккаунт после `DELETE /auth/me`, 0 - сразу
     #[get = "pub"]
-    bot_token: String
+    #[serde(default = "default_account_deletion_grace_days")]
+    account_deletion_grace_days: i64,
+    /// Как часто (в минутах) удалять аккаунты с истёкшим сроком, 0 - только через
+    /// `POST /auth/admin/users/purge`, например по cron
+    #[get = "pub"]
+    #[serde(default = "default_account_purge_interval_minutes")]
+    account_purge_interval_minutes: u64,
 }
 
 impl Credentials {
+    /// Роли из настроек, которые должны быть в `users.role`
+    pub fn configured_roles(&self) -> Vec<String> {
+        let mut roles = vec![
+            self.new_user_role.with_email().clone(),
+            self.new_user_role.with_telegram().clone(),
+            self.admin_role.clone(),
+        ];
+        roles.extend(self.guest_role.iter().cloned());
+        for (role, implied) in self.role_hierarchy.iter() {
+            roles.push(role.clone());
+            roles.extend(implied.iter().cloned());
+        }
+        roles.sort();
+        roles.dedup();
+        roles
+    }
+
+    /// Переносит `bot_token` старых настроек в `telegram_bots`, если ботов не задано,
+    /// чтобы вход через Telegram не отключился после обновления
+    pub fn with_legacy_bot_token(mut self) -> Self {
+        let Some(token) = self.bot_token.take().filter(|v| !v.is_empty()) else {
+            return self;
+        };
+        if self.telegram_bots.is_empty() {
+            self.telegram_bots.push(TelegramBot {
+                name: LEGACY_BOT_NAME.to_string(),
+                token,
+                ..Default::default()
+            });
+        }
+        self
+    }
+
+    /// Бот по имени, без имени - первый из списка
+    pub fn telegram_bot(&self, name: Option<&str>) -> Option<&TelegramBot> {
+        match name {
+            Some(name) => self.telegram_bots.iter().find(|v| v.name == name),
+            None => self.telegram_bots.first(),
+        }
+    }
+
+    /// Бот по умолчанию (первый) - им привязаны Telegram аккаунты,
+    /// созданные до появления `provider`
+    pub fn is_default_telegram_bot(&self, name: &str) -> bool {
+        self.telegram_bot(None).is_some_and(|v| v.name == name)
+    }
+
+    /// Только секреты JWT - для сервисов, которым нужна лишь проверка токенов
+    /// (`TokenProvider::new(Credentials::with_jwt_secrets(..))`)
+    pub fn with_jwt_secrets(access_secret: String, refresh_secret: String) -> Self {
+        Self { access_secret, refresh_secret, ..Default::default() }
+    }
+
     pub fn mock() -> Self {
         let new_user_role = NewUserRole {
             with_email: "TEST".to_string(),
@@ -51,11 +185,69 @@ impl Credentials {
             hasura_credentials,
             new_user_role,
             api_key_length: 32,
-            bot_token: "TEST".to_string()
+            api_key_prefix: "awr_test_".to_string(),
+            api_key_hash_secret: Some("TEST_API_KEY_SECRET".to_string()),
+            api_key_cache_ttl: 30,
+            attribute_schema_cache_ttl: 300,
+            request_signing_key: Some(
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f".to_string()
+            ),
+            request_signature_max_skew: 300,
+            telegram_bots: vec![TelegramBot {
+                name: "TEST".to_string(),
+                token: "TEST".to_string(),
+                username: Some("test_bot".to_string()),
+                webhook_secret: Some("TEST_SECRET".to_string()),
+            }],
+            bot_token: None,
+            telegram_link_scope: TelegramLinkScope::Global,
+            telegram_auth_max_age: 86400,
+            telegram_api_url: "URL".to_string(),
+            telegram_login_ttl: 300,
+            reauth_max_age_minutes: Some(15),
+            user_editable_attributes: vec!["name".to_string(), "locale".to_string()],
+            admin_role: "admin".to_string(),
+            role_hierarchy: BTreeMap::from([("admin".to_string(), vec!["default".to_string()])]),
+            signup_mode: SignUpMode::Open,
+            signup_allowed_domains: vec!["test.com".to_string()],
+            guest_role: Some("guest".to_string()),
+            account_deletion_grace_days: 30,
+            account_purge_interval_minutes: 0,
         }
     }
 }
 
+/// Имя бота, перенесённого из `bot_token`
+const LEGACY_BOT_NAME: &str = "default";
+
+fn default_api_key_prefix() -> String {
+    "awr_".to_string()
+}
+
+fn default_admin_role() -> String {
+    "admin".to_string()
+}
+
+fn default_account_deletion_grace_days() -> i64 {
+    30
+}
+
+fn default_account_purge_interval_minutes() -> u64 {
+    60
+}
+
+fn default_api_key_cache_ttl() -> i64 {
+    30
+}
+
+fn default_attribute_schema_cache_ttl() -> i64 {
+    300
+}
+
+fn default_request_signature_max_skew() -> i64 {
+    300
+}
+
 #[derive(
     Getters, Setters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq, Default,
 )]
@@ -77,3 +269,76 @@ pub struct NewUserRole {
     #[get = "pub"]
     with_telegram: String,
 }
+
+#[derive(
+    Getters, Setters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq, Default,
+)]
+pub struct TelegramBot {
+    #[get = "pub"]
+    name: String,
+    #[get = "pub"]
+    token: String,
+    /// Имя бота в Telegram, нужно для deep link `t.me/<username>`
+    #[get = "pub"]
+    #[serde(default)]
+    username: Option<String>,
+    /// Ожидаемый заголовок `X-Telegram-Bot-Api-Secret-Token` вебхука.
+    /// Без него вебхук бота отклоняется
+    #[get = "pub"]
+    #[serde(default)]
+    webhook_secret: Option<String>,
+}
+
+/// Как привязывается Telegram id: один раз на всех ботов или отдельно для каждого
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TelegramLinkScope {
+    #[default]
+    Global,
+    PerBot,
+}
+
+/// Режим регистрации. Приглашение подходит в любом режиме, кроме `Disabled`
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignUpMode {
+    #[default]
+    Open,
+    /// Только по приглашению
+    InviteOnly,
+    /// Email из `signup_allowed_domains` или приглашение
+    AllowList,
+    /// Новые пользователи не создаются
+    Disabled,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials_with(patch: serde_json::Value) -> Credentials {
+        let mut credentials = serde_json::to_value(Credentials::mock()).unwrap();
+        for (key, value) in patch.as_object().unwrap() {
+            credentials[key] = value.clone();
+        }
+        serde_json::from_value(credentials).unwrap()
+    }
+
+    #[test]
+    fn legacy_bot_token_becomes_default_bot() {
+        let credentials = credentials_with(serde_json::json!({ "telegram_bots": [], "bot_token": "LEGACY" }))
+            .with_legacy_bot_token();
+
+        let bot = credentials.telegram_bot(None).unwrap();
+        assert_eq!(bot.name(), LEGACY_BOT_NAME);
+        assert_eq!(bot.token(), "LEGACY");
+    }
+
+    #[test]
+    fn telegram_bots_win_over_legacy_bot_token() {
+        let credentials = credentials_with(serde_json::json!({ "bot_token": "LEGACY" })).with_legacy_bot_token();
+
+        assert_eq!(credentials.telegram_bots().len(), 1);
+        assert_eq!(credentials.telegram_bot(None).unwrap().token(), "TEST");
+    }
+}
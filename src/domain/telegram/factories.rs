@@ -0,0 +1,10 @@
+use super::service::{LoginSessionService, TelegramBotService};
+
+pub trait TelegramProviderFactory {
+    type LoginSession: LoginSessionService + Send;
+    type Bot: TelegramBotService + Send;
+
+    fn login_session(&self) -> Self::LoginSession;
+    /// Клиент Bot API для бота из настроек, `None` если бот не настроен
+    fn bot(&self, name: &str) -> Option<Self::Bot>;
+}
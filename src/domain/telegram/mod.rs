@@ -0,0 +1,3 @@
+pub mod factories;
+pub mod model;
+pub mod service;
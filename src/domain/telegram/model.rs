@@ -0,0 +1,22 @@
+/// Пользователь Telegram, как его описывает Bot API
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TelegramUser {
+    pub id: i64,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub username: Option<String>,
+}
+
+/// Ожидающий вход через deep link `t.me/<bot>?start=<nonce>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginSession {
+    pub nonce: String,
+    /// Отдаётся только создателю сессии: nonce публичен в deep link
+    pub secret: String,
+    /// Короткий код, который показывают и приложение, и бот перед подтверждением входа
+    pub code: String,
+    pub bot: String,
+    pub expires_at: i64,
+    /// Заполняется, когда пользователь подтвердил вход кнопкой в боте
+    pub user: Option<TelegramUser>,
+}
@@ -0,0 +1,24 @@
+use std::future::Future;
+
+use crate::domain::errors::service::AppErrorInfo;
+use super::model::{LoginSession, TelegramUser};
+
+pub trait LoginSessionService {
+    type Error: AppErrorInfo;
+    /// Новая сессия входа для бота `bot`
+    fn create(&self, bot: &str) -> Result<LoginSession, Self::Error>;
+    /// Ожидающая сессия бота `bot`, ещё не привязанная к пользователю
+    fn pending(&self, bot: &str, nonce: &str) -> Result<LoginSession, Self::Error>;
+    /// Привязывает пользователя Telegram к ожидающей сессии
+    fn bind(&self, bot: &str, nonce: &str, user: TelegramUser) -> Result<LoginSession, Self::Error>;
+    /// Забирает сессию, если пользователь уже привязан и `secret` совпадает.
+    /// `Ok(None)` - вход ещё не подтверждён в боте
+    fn take_bound(&self, nonce: &str, secret: &str) -> Result<Option<LoginSession>, Self::Error>;
+}
+
+pub trait TelegramBotService {
+    type Error: AppErrorInfo;
+    fn send_message(&self, chat_id: i64, text: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Сообщение с одной inline-кнопкой, нажатие приходит в вебхук как `callback_query`
+    fn send_button(&self, chat_id: i64, text: &str, button: &str, callback_data: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
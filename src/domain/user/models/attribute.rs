@@ -0,0 +1,64 @@
+/// Тип значения атрибута из `users.attribute.value_type`.
+/// Значения хранятся строками, тип проверяется только при записи
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Email,
+    /// Международный формат: `+` и от 7 до 15 цифр
+    Phone,
+}
+
+impl AttributeType {
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            AttributeType::String => true,
+            AttributeType::Integer => value.parse::<i64>().is_ok(),
+            AttributeType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            AttributeType::Boolean => value == "true" || value == "false",
+            AttributeType::Email => is_email(value),
+            AttributeType::Phone => is_phone(value),
+        }
+    }
+}
+
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !value.chars().any(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|part| !part.is_empty())
+}
+
+fn is_phone(value: &str) -> bool {
+    let digits = value.strip_prefix('+').unwrap_or(value);
+    (7..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_values() {
+        assert!(AttributeType::String.accepts("anything"));
+        assert!(AttributeType::Integer.accepts("-42"));
+        assert!(!AttributeType::Integer.accepts("4.2"));
+        assert!(AttributeType::Number.accepts("4.2"));
+        assert!(!AttributeType::Number.accepts("NaN"));
+        assert!(AttributeType::Boolean.accepts("false"));
+        assert!(!AttributeType::Boolean.accepts("yes"));
+        assert!(AttributeType::Email.accepts("test@test.com"));
+        assert!(!AttributeType::Email.accepts("test@test"));
+        assert!(!AttributeType::Email.accepts("te st@test.com"));
+        assert!(AttributeType::Phone.accepts("+79991234567"));
+        assert!(!AttributeType::Phone.accepts("+7 999 123"));
+    }
+}
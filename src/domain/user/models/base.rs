@@ -2,6 +2,7 @@ use chrono::{DateTime, FixedOffset};
 use getset::{Getters, Setters};
 use uuid::Uuid;
 
+use super::attribute::AttributeType;
 use super::extended::ExtendedUser;
 
 #[derive(Getters, Setters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
@@ -40,6 +41,26 @@ pub struct AuthMethod {
     identifier: String,
     #[get = "pub"]
     secret: Option<String>,
+    /// Кто выдал идентификатор, например имя Telegram-бота
+    #[get = "pub"]
+    provider: Option<String>,
+    /// Подпись, которую пользователь дал ключу
+    #[get = "pub"]
+    name: Option<String>,
+    #[get = "pub"]
+    expires_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    last_used_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    revoked_at: Option<DateTime<FixedOffset>>,
+    /// Роли, которые может получить ключ; None - все роли пользователя
+    #[get = "pub"]
+    roles: Option<Vec<String>>,
+    #[get = "pub"]
+    scopes: Option<Vec<String>>,
+    /// Зашифрованный ключ подписи запросов (только для apikey)
+    #[get = "pub"]
+    signing_secret: Option<String>,
 }
 
 impl AuthMethod {
@@ -56,12 +77,47 @@ impl AuthMethod {
             auth_type,
             identifier,
             secret,
+            provider: None,
+            name: None,
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            roles: None,
+            scopes: None,
+            signing_secret: None,
         }
     }
+
+    pub fn with_provider(mut self, provider: Option<String>) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    pub fn with_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn with_expires_at(mut self, expires_at: Option<DateTime<FixedOffset>>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    pub fn with_scope(mut self, roles: Option<Vec<String>>, scopes: Option<Vec<String>>) -> Self {
+        self.roles = roles;
+        self.scopes = scopes;
+        self
+    }
+
+    pub fn with_signing_secret(mut self, signing_secret: Option<String>) -> Self {
+        self.signing_secret = signing_secret;
+        self
+    }
 }
 
 #[derive(Getters, Setters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct UserRole {
+    #[get = "pub"]
     id: Option<Uuid>,
     #[get = "pub"]
     created_at: Option<DateTime<FixedOffset>>,
@@ -122,4 +178,11 @@ pub struct Attribute {
     is_unique: bool,
     #[get = "pub"]
     value: String,
+    #[get = "pub"]
+    #[serde(default)]
+    value_type: AttributeType,
+    /// Регулярное выражение, которому должно соответствовать всё значение
+    #[get = "pub"]
+    #[serde(default)]
+    pattern: Option<String>,
 }
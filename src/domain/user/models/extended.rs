@@ -5,6 +5,7 @@ use uuid::Uuid;
 use crate::domain::user::models::base::AuthMethod;
 
 use super::base::{UserAttribute, UserRole, User};
+use super::status::{effective_status, UserStatus};
 
 
 #[derive(Getters, Setters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
@@ -18,6 +19,22 @@ pub struct ExtendedAuthMethod {
     #[get = "pub"]
     secret: Option<String>,
     #[get = "pub"]
+    provider: Option<String>,
+    #[get = "pub"]
+    name: Option<String>,
+    #[get = "pub"]
+    expires_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    last_used_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    revoked_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    roles: Option<Vec<String>>,
+    #[get = "pub"]
+    scopes: Option<Vec<String>>,
+    #[get = "pub"]
+    signing_secret: Option<String>,
+    #[get = "pub"]
     created_at: Option<DateTime<FixedOffset>>,
     #[get = "pub"]
     user_id: Uuid,
@@ -39,12 +56,37 @@ impl ExtendedAuthMethod {
             auth_type: auth_method.auth_type().clone(),
             identifier: auth_method.identifier().clone(),
             secret: auth_method.secret().clone(),
+            provider: auth_method.provider().clone(),
+            name: auth_method.name().clone(),
+            expires_at: *auth_method.expires_at(),
+            last_used_at: *auth_method.last_used_at(),
+            revoked_at: *auth_method.revoked_at(),
+            roles: auth_method.roles().clone(),
+            scopes: auth_method.scopes().clone(),
+            signing_secret: auth_method.signing_secret().clone(),
             created_at: auth_method.created_at().clone(),
             user_id: auth_method.user_id().clone(),
             user: extended_user
         }
 
     }
+
+    /// См. `ExtendedUser::cancel_deletion`
+    pub fn cancel_user_deletion(&mut self) {
+        self.user.cancel_deletion();
+    }
+
+    /// Не отозван и не истёк
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        if self.revoked_at.is_some() {
+            return false;
+        }
+        match self.expires_at {
+            Some(expires_at) => expires_at > now,
+            None => true,
+        }
+    }
+
     pub fn mock() -> Self {
         let mock_user = ExtendedUser::mock();
         Self {
@@ -52,6 +94,14 @@ impl ExtendedAuthMethod {
             auth_type: "email".to_string(),
             identifier: "test@test.test".to_string(),
             secret: Some("random".to_string()),
+            provider: None,
+            name: None,
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            roles: None,
+            scopes: None,
+            signing_secret: None,
             created_at: Some(Utc::now().into()),
             user_id: mock_user.id.clone(),
             user: mock_user
@@ -73,18 +123,74 @@ pub struct ExtendedUser {
     user_roles: Vec<UserRole>,
     #[get = "pub"]
     user_attributes: Vec<UserAttribute>,
+    #[get = "pub"]
+    #[serde(default)]
+    status: UserStatus,
+    /// Причина отключения или блокировки, видна администратору
+    #[get = "pub"]
+    #[serde(default)]
+    status_reason: Option<String>,
+    #[get = "pub"]
+    #[serde(default)]
+    locked_until: Option<DateTime<FixedOffset>>,
+    /// Когда пользователь отключён администратором
+    #[get = "pub"]
+    #[serde(default)]
+    disabled_at: Option<DateTime<FixedOffset>>,
+    /// Принудительный выход: сессии, начатые раньше, не продлеваются
+    #[get = "pub"]
+    #[serde(default)]
+    logged_out_at: Option<DateTime<FixedOffset>>,
+    /// Когда удалить аккаунт по запросу пользователя (`DELETE /auth/me`)
+    #[get = "pub"]
+    #[serde(default)]
+    delete_after: Option<DateTime<FixedOffset>>,
 }
 
 
 impl ExtendedUser {
     pub fn new(id: Uuid, created_at: DateTime<FixedOffset>, updated_at: Option<DateTime<FixedOffset>>) -> Self {
-        Self { id, created_at, updated_at, user_roles: Vec::new(), user_attributes: Vec::new() }
+        Self {
+            id,
+            created_at,
+            updated_at,
+            user_roles: Vec::new(),
+            user_attributes: Vec::new(),
+            status: UserStatus::Active,
+            status_reason: None,
+            locked_until: None,
+            disabled_at: None,
+            logged_out_at: None,
+            delete_after: None,
+        }
     }
 
     pub fn as_base(&self) -> User {
         User::new(self.id.clone(), self.created_at.clone(), self.updated_at.clone())
     }
 
+    /// Состояние с учётом истёкшей блокировки
+    pub fn status_at(&self, now: DateTime<Utc>) -> UserStatus {
+        effective_status(self.status, self.locked_until, now)
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.status_at(now) == UserStatus::Active
+    }
+
+    /// Владелец запросил удаление (`DELETE /auth/me`), и срок ещё не вышел
+    pub fn is_deletion_pending(&self, now: DateTime<Utc>) -> bool {
+        self.status == UserStatus::Disabled && self.delete_after.is_some_and(|v| v > now)
+    }
+
+    /// Состояние после `UserStatusChange::active()`: удаление отменено
+    pub fn cancel_deletion(&mut self) {
+        self.status = UserStatus::Active;
+        self.status_reason = None;
+        self.disabled_at = None;
+        self.delete_after = None;
+    }
+
     pub fn add_role(&mut self, role: UserRole) {
         self.user_roles.push(role);
     }
@@ -93,6 +199,12 @@ impl ExtendedUser {
         self.user_attributes.push(attribute);
     }
 
+    /// Заменяет значение одноимённого атрибута или добавляет новый
+    pub fn set_attribute(&mut self, attribute: UserAttribute) {
+        self.user_attributes.retain(|v| v.attribute() != attribute.attribute());
+        self.user_attributes.push(attribute);
+    }
+
     fn mock() -> Self {
         let user_id = Uuid::new_v4();
         let user_roles = vec![
@@ -109,7 +221,13 @@ impl ExtendedUser {
             created_at: Utc::now().into(),
             updated_at: Some(Utc::now().into()),
             user_roles,
-            user_attributes
+            user_attributes,
+            status: UserStatus::Active,
+            status_reason: None,
+            locked_until: None,
+            disabled_at: None,
+            logged_out_at: None,
+            delete_after: None,
         }
     }
 }
\ No newline at end of file
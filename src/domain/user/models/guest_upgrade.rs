@@ -0,0 +1,65 @@
+use getset::Getters;
+use uuid::Uuid;
+
+use super::base::{AuthMethod, UserAttribute, UserRole};
+use super::extended::ExtendedAuthMethod;
+use super::new_user::NewUser;
+
+/// Регистрация гостя одной транзакцией: способы входа, атрибуты и недостающие роли
+/// добавляются к id гостя, default переходит на роль регистрации,
+/// а роль гостя и способ входа `anonymous` снимаются
+#[derive(Getters, Debug, Clone, PartialEq)]
+pub struct GuestUpgrade {
+    #[get = "pub"]
+    user_id: Uuid,
+    #[get = "pub"]
+    guest_auth_method_id: Uuid,
+    #[get = "pub"]
+    auth_methods: Vec<AuthMethod>,
+    /// Заменяют одноимённые атрибуты, заданные гостем
+    #[get = "pub"]
+    attributes: Vec<UserAttribute>,
+    /// Только роли, которых у гостя ещё нет
+    #[get = "pub"]
+    roles: Vec<UserRole>,
+    #[get = "pub"]
+    default_role: String,
+    #[get = "pub"]
+    removed_roles: Vec<String>,
+    /// См. [`NewUser::invite`]
+    #[get = "pub"]
+    invite: Option<String>,
+}
+
+impl GuestUpgrade {
+    /// `new_user` собран с id гостя. `None`, если у `new_user` нет роли по умолчанию
+    pub fn plan(guest: &ExtendedAuthMethod, new_user: NewUser, guest_role: Option<&String>) -> Option<Self> {
+        let default_role = new_user.roles.iter().find(|v| *v.is_default())?.role().clone();
+        let user_id = *guest.user_id();
+
+        let assigned = guest.user().user_roles();
+        let roles = new_user
+            .roles
+            .iter()
+            .filter(|role| !assigned.iter().any(|v| v.role() == role.role()))
+            .map(|role| UserRole::new(false, role.role().clone(), user_id))
+            .collect();
+
+        let removed_roles = guest_role
+            .filter(|guest_role| !new_user.roles.iter().any(|v| v.role() == *guest_role))
+            .cloned()
+            .into_iter()
+            .collect();
+
+        Some(Self {
+            user_id,
+            guest_auth_method_id: *guest.id(),
+            auth_methods: new_user.auth_methods,
+            attributes: new_user.attributes,
+            roles,
+            default_role,
+            removed_roles,
+            invite: new_user.invite,
+        })
+    }
+}
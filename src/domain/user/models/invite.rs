@@ -0,0 +1,84 @@
+use chrono::{DateTime, FixedOffset};
+use getset::Getters;
+use uuid::Uuid;
+
+/// Приглашение из `users.invite`: код для регистрации при закрытом `signup_mode`
+#[derive(Getters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Invite {
+    #[get = "pub"]
+    id: Option<Uuid>,
+    #[get = "pub"]
+    code: String,
+    /// Роль нового пользователя вместо `new_user_role`
+    #[get = "pub"]
+    role: Option<String>,
+    /// None - без ограничения
+    #[get = "pub"]
+    max_uses: Option<i32>,
+    /// Уменьшается при каждой регистрации, None - без ограничения
+    #[get = "pub"]
+    uses_left: Option<i32>,
+    #[get = "pub"]
+    #[serde(default)]
+    used_count: i32,
+    #[get = "pub"]
+    expires_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    created_at: Option<DateTime<FixedOffset>>,
+    #[get = "pub"]
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+impl Invite {
+    pub fn new(
+        code: String,
+        role: Option<String>,
+        max_uses: Option<i32>,
+        expires_at: Option<DateTime<FixedOffset>>,
+        comment: Option<String>,
+    ) -> Self {
+        Self {
+            id: None,
+            code,
+            role,
+            max_uses,
+            uses_left: max_uses,
+            used_count: 0,
+            expires_at,
+            created_at: None,
+            comment,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<FixedOffset>) -> bool {
+        self.expires_at.is_some_and(|v| v <= now)
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.uses_left.is_some_and(|v| v <= 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    #[test]
+    fn invite_limits() {
+        let now = chrono::Utc::now().fixed_offset();
+        let invite = Invite::new("code".to_string(), None, Some(1), Some(now + Duration::hours(1)), None);
+
+        assert!(!invite.is_expired(now));
+        assert!(invite.is_expired(now + Duration::hours(1)));
+        assert!(!invite.is_exhausted());
+
+        let used = Invite { uses_left: Some(0), used_count: 1, ..invite };
+        assert!(used.is_exhausted());
+
+        let unlimited = Invite::new("code".to_string(), None, None, None, None);
+        assert!(!unlimited.is_expired(now) && !unlimited.is_exhausted());
+    }
+}
@@ -0,0 +1,49 @@
+use getset::Getters;
+use uuid::Uuid;
+
+use super::extended::ExtendedUser;
+
+/// Что переносится со второстепенного пользователя на основной.
+/// Способы входа переносятся все, роли и атрибуты - только те,
+/// которых у основного пользователя нет. Второстепенный помечается удалённым
+#[derive(Getters, Debug, Clone, PartialEq)]
+pub struct UserMerge {
+    #[get = "pub"]
+    primary_id: Uuid,
+    #[get = "pub"]
+    secondary_id: Uuid,
+    #[get = "pub"]
+    role_ids: Vec<Uuid>,
+    #[get = "pub"]
+    attribute_ids: Vec<Uuid>,
+}
+
+impl UserMerge {
+    pub fn plan(primary: &ExtendedUser, secondary: &ExtendedUser) -> Self {
+        let role_ids = secondary
+            .user_roles()
+            .iter()
+            .filter(|role| !primary.user_roles().iter().any(|v| v.role() == role.role()))
+            .filter_map(|role| *role.id())
+            .collect();
+
+        let attribute_ids = secondary
+            .user_attributes()
+            .iter()
+            .filter(|attribute| {
+                !primary
+                    .user_attributes()
+                    .iter()
+                    .any(|v| v.attribute() == attribute.attribute())
+            })
+            .filter_map(|attribute| *attribute.id())
+            .collect();
+
+        Self {
+            primary_id: *primary.id(),
+            secondary_id: *secondary.id(),
+            role_ids,
+            attribute_ids,
+        }
+    }
+}
@@ -1,2 +1,10 @@
+pub mod attribute;
 pub mod base;
 pub mod extended;
+pub mod guest_upgrade;
+pub mod invite;
+pub mod merge;
+pub mod new_user;
+pub mod page;
+pub mod status;
+pub mod role;
@@ -0,0 +1,30 @@
+use uuid::Uuid;
+
+use super::base::{AuthMethod, UserAttribute, UserRole};
+
+/// Пользователь вместе со способами входа, атрибутами и ролями:
+/// создаётся одной мутацией, без пользователей-сирот при ошибке.
+/// `user_id` вложенных объектов не отправляется, его проставляет Hasura
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewUser {
+    pub auth_methods: Vec<AuthMethod>,
+    pub attributes: Vec<UserAttribute>,
+    pub roles: Vec<UserRole>,
+    /// Код приглашения: использование списывается той же мутацией,
+    /// поэтому при ошибке создания приглашение не тратится
+    pub invite: Option<String>,
+}
+
+impl NewUser {
+    /// `user_id` для вложенных объектов, пока пользователя нет
+    pub const PENDING_ID: Uuid = Uuid::nil();
+
+    pub fn new(auth_methods: Vec<AuthMethod>, attributes: Vec<UserAttribute>, roles: Vec<UserRole>) -> Self {
+        Self { auth_methods, attributes, roles, invite: None }
+    }
+
+    pub fn with_invite(mut self, invite: Option<String>) -> Self {
+        self.invite = invite;
+        self
+    }
+}
@@ -0,0 +1,27 @@
+use getset::Getters;
+
+use super::extended::ExtendedUser;
+
+/// Поиск пользователей для администратора
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserFilter {
+    /// Подстрока идентификатора способа входа или значения атрибута
+    pub search: Option<String>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Страница списка пользователей и общее число найденных
+#[derive(Getters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UserPage {
+    #[get = "pub"]
+    users: Vec<ExtendedUser>,
+    #[get = "pub"]
+    total: i64,
+}
+
+impl UserPage {
+    pub fn new(users: Vec<ExtendedUser>, total: i64) -> Self {
+        Self { users, total }
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use getset::Getters;
+
+/// Значение enum таблицы `users.role` с описанием
+#[derive(Getters, Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Role {
+    #[get = "pub"]
+    value: String,
+    #[get = "pub"]
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+impl Role {
+    pub fn new(value: String, comment: Option<String>) -> Self {
+        Self { value, comment }
+    }
+
+    /// Значения enum таблицы Hasura становятся значениями GraphQL enum
+    pub fn is_valid_name(value: &str) -> bool {
+        let mut chars = value.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    }
+}
+
+/// Роли вместе со всеми подразумеваемыми по иерархии (`admin -> [default]`).
+/// Порядок сохраняется, повторы и циклы не раскрываются дважды
+pub fn expand_roles(roles: &[String], hierarchy: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+    let mut expanded: Vec<String> = Vec::new();
+    let mut stack = roles.iter().rev().cloned().collect::<Vec<_>>();
+    while let Some(role) = stack.pop() {
+        if expanded.contains(&role) {
+            continue;
+        }
+        if let Some(implied) = hierarchy.get(&role) {
+            stack.extend(implied.iter().rev().cloned());
+        }
+        expanded.push(role);
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn hierarchy_is_expanded() {
+        let hierarchy = BTreeMap::from([
+            ("admin".to_string(), roles(&["manager"])),
+            ("manager".to_string(), roles(&["default", "admin"])),
+        ]);
+
+        assert_eq!(expand_roles(&roles(&["admin", "test"]), &hierarchy), roles(&["admin", "manager", "default", "test"]));
+        assert_eq!(expand_roles(&roles(&["test"]), &hierarchy), roles(&["test"]));
+    }
+
+    #[test]
+    fn role_names() {
+        assert!(Role::is_valid_name("support_2"));
+        assert!(!Role::is_valid_name("2nd"));
+        assert!(!Role::is_valid_name("Admin"));
+        assert!(!Role::is_valid_name(""));
+    }
+}
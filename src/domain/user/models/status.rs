@@ -0,0 +1,82 @@
+use chrono::{DateTime, FixedOffset, Utc};
+
+/// Состояние учётной записи. Токены выдаются только активным пользователям
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    /// Отключён администратором
+    Disabled,
+    /// Заблокирован до `locked_until`, без срока - до снятия блокировки
+    Locked,
+    /// Не подтвердил email или приглашение
+    PendingVerification,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Disabled => "disabled",
+            UserStatus::Locked => "locked",
+            UserStatus::PendingVerification => "pending_verification",
+        }
+    }
+}
+
+/// Новое состояние пользователя вместе с причиной
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserStatusChange {
+    pub status: UserStatus,
+    pub reason: Option<String>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl UserStatusChange {
+    pub fn active() -> Self {
+        Self { status: UserStatus::Active, reason: None, locked_until: None }
+    }
+
+    pub fn disabled(reason: Option<String>) -> Self {
+        Self { status: UserStatus::Disabled, reason, locked_until: None }
+    }
+
+    pub fn locked(until: Option<DateTime<Utc>>, reason: Option<String>) -> Self {
+        Self { status: UserStatus::Locked, reason, locked_until: until }
+    }
+}
+
+/// Блокировка с истёкшим сроком снимается сама
+pub fn effective_status(status: UserStatus, locked_until: Option<DateTime<FixedOffset>>, now: DateTime<Utc>) -> UserStatus {
+    match (status, locked_until) {
+        (UserStatus::Locked, Some(until)) if until <= now => UserStatus::Active,
+        (status, _) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    #[test]
+    fn expired_lock_is_active() {
+        let now = Utc::now();
+        let past = Some((now - Duration::minutes(1)).into());
+        let future = Some((now + Duration::minutes(1)).into());
+
+        assert_eq!(effective_status(UserStatus::Locked, past, now), UserStatus::Active);
+        assert_eq!(effective_status(UserStatus::Locked, future, now), UserStatus::Locked);
+        assert_eq!(effective_status(UserStatus::Locked, None, now), UserStatus::Locked);
+        assert_eq!(effective_status(UserStatus::Disabled, past, now), UserStatus::Disabled);
+    }
+
+    #[test]
+    fn status_json() {
+        let status: UserStatus = serde_json::from_str("\"pending_verification\"").unwrap();
+        assert_eq!(status, UserStatus::PendingVerification);
+        assert_eq!(serde_json::to_string(&UserStatus::Locked).unwrap(), "\"locked\"");
+    }
+}
@@ -1,30 +1,107 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use super::models::base::{AuthMethod, User, UserAttribute, UserRole};
-use super::models::extended::ExtendedAuthMethod;
+use super::models::base::{Attribute, AuthMethod, User, UserAttribute, UserRole};
+use super::models::extended::{ExtendedAuthMethod, ExtendedUser};
+use super::models::guest_upgrade::GuestUpgrade;
+use super::models::invite::Invite;
+use super::models::merge::UserMerge;
+use super::models::new_user::NewUser;
+use super::models::page::{UserFilter, UserPage};
+use super::models::role::Role;
+use super::models::status::UserStatusChange;
 use crate::domain::errors::service::AppErrorInfo;
 
 pub trait QueryUserService {
     type Error: std::fmt::Display + AppErrorInfo;
 
-    async fn get_user_by_identifier(
+    fn get_user_by_identifier(
         &self,
         identifier: &str,
         auth_type: &str,
 
-    ) -> Result<Option<ExtendedAuthMethod>, Self::Error>;
-    async fn get_user_by_id(&self, id: Uuid) -> Result<Vec<ExtendedAuthMethod>, Self::Error>;
+    ) -> impl Future<Output = Result<Option<ExtendedAuthMethod>, Self::Error>> + Send;
+    fn get_user_by_provider_identifier(
+        &self,
+        identifier: &str,
+        auth_type: &str,
+        provider: &str,
+        with_unassigned: bool,
+    ) -> impl Future<Output = Result<Option<ExtendedAuthMethod>, Self::Error>> + Send;
+    fn get_user_by_id(&self, id: Uuid) -> impl Future<Output = Result<Vec<ExtendedAuthMethod>, Self::Error>> + Send;
+    /// Пользователь с ролями и атрибутами, даже если у него нет способов входа
+    fn get_user(&self, id: Uuid) -> impl Future<Output = Result<Option<ExtendedUser>, Self::Error>> + Send;
+    /// Список пользователей для администратора, новые первыми
+    fn list_users(&self, filter: UserFilter) -> impl Future<Output = Result<UserPage, Self::Error>> + Send;
+    /// Все значения `users.role`
+    fn list_roles(&self) -> impl Future<Output = Result<Vec<Role>, Self::Error>> + Send;
+    /// Сколько пользователей с ролью; роль должна быть в `users.role`
+    fn count_role_users(&self, role: &str) -> impl Future<Output = Result<i64, Self::Error>> + Send;
+    /// Схема `users.attribute`: какие атрибуты обязательны и уникальны
+    fn get_attribute_schema(&self) -> impl Future<Output = Result<Vec<Attribute>, Self::Error>> + Send;
+    /// Нет ли такого значения атрибута у других пользователей, кроме `except_user`
+    fn attribute_value_is_free(
+        &self,
+        attribute: &str,
+        value: &str,
+        except_user: Option<Uuid>,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+    /// Приглашение по коду, в том числе истёкшее или исчерпанное
+    fn get_invite(&self, code: &str) -> impl Future<Output = Result<Option<Invite>, Self::Error>> + Send;
+    /// Все приглашения, новые первыми
+    fn list_invites(&self) -> impl Future<Output = Result<Vec<Invite>, Self::Error>> + Send;
+    /// Пользователи, у которых закончился срок до удаления
+    fn list_users_to_delete(&self, now: DateTime<Utc>) -> impl Future<Output = Result<Vec<Uuid>, Self::Error>> + Send;
 }
 
 pub trait CommandUserService {
     type Error: std::fmt::Display + AppErrorInfo;
 
-    async fn auth_identifier_is_free(&self, identifier: String, auth_type: &str) -> Result<bool, Self::Error>;
-    async fn add_user(&self) -> Result<User, Self::Error>;
-    async fn add_role(&self, user_role: UserRole) -> Result<UserRole, Self::Error>;
-    async fn add_user_attribute(
+    fn auth_identifier_is_free(&self, identifier: String, auth_type: &str) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+    fn add_user(&self) -> impl Future<Output = Result<User, Self::Error>> + Send;
+    /// Создаёт пользователя со способами входа, атрибутами и ролями одной транзакцией.
+    /// Возвращает первый способ входа вместе с пользователем
+    fn create_user_with(&self, new_user: NewUser) -> impl Future<Output = Result<ExtendedAuthMethod, Self::Error>> + Send;
+    fn add_role(&self, user_role: UserRole) -> impl Future<Output = Result<UserRole, Self::Error>> + Send;
+    /// Добавляет значение в `users.role`
+    fn create_role(&self, role: Role) -> impl Future<Output = Result<Role, Self::Error>> + Send;
+    /// Добавляет приглашение в `users.invite`
+    fn create_invite(&self, invite: Invite) -> impl Future<Output = Result<Invite, Self::Error>> + Send;
+    /// Снимает роль с пользователя, `None` если её не было
+    fn remove_role(&self, user_id: Uuid, role: &str) -> impl Future<Output = Result<Option<UserRole>, Self::Error>> + Send;
+    /// Делает роль ролью по умолчанию, `None` если роль не назначена
+    fn set_default_role(&self, user_id: Uuid, role: &str) -> impl Future<Output = Result<Option<UserRole>, Self::Error>> + Send;
+    /// Отключает, блокирует или снова делает пользователя активным
+    fn set_user_status(&self, user_id: Uuid, change: UserStatusChange) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Принудительный выход: refresh токены, выданные до `logged_out_at`, не принимаются
+    fn logout_user(&self, user_id: Uuid, logged_out_at: DateTime<Utc>) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    fn add_user_attribute(
         &self,
         user_attribute: Vec<UserAttribute>,
-    ) -> Result<Vec<UserAttribute>, Self::Error>;
-    async fn add_auth_method(&self, auth_method: AuthMethod) -> Result<AuthMethod, Self::Error>;
+    ) -> impl Future<Output = Result<Vec<UserAttribute>, Self::Error>> + Send;
+    /// Заменяет значения атрибутов пользователя (или добавляет, если их не было) одной транзакцией
+    fn update_user_attributes(&self, user_id: Uuid, attributes: Vec<UserAttribute>) -> impl Future<Output = Result<Vec<UserAttribute>, Self::Error>> + Send;
+    fn add_auth_method(&self, auth_method: AuthMethod) -> impl Future<Output = Result<AuthMethod, Self::Error>> + Send;
+    /// Удаляет способы входа пользователя одной мутацией, возвращает удалённые
+    fn remove_auth_methods(&self, ids: Vec<Uuid>, user_id: Uuid) -> impl Future<Output = Result<Vec<AuthMethod>, Self::Error>> + Send;
+    /// Отзывает способ входа, `None` если активного такого нет
+    fn revoke_auth_method(&self, identifier: &str, auth_type: &str, user_id: Uuid) -> impl Future<Output = Result<Option<AuthMethod>, Self::Error>> + Send;
+    /// Отмечает использование способа входа (last_used_at)
+    fn touch_auth_method(&self, id: Uuid) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Заменяет хэш секрета, например при переходе на другой алгоритм
+    fn update_auth_method_secret(&self, id: Uuid, secret: String) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Переносит данные второстепенного пользователя на основной одной транзакцией
+    fn merge_users(&self, merge: UserMerge) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Отключает пользователя и назначает удаление на `delete_after`.
+    /// Любая смена состояния администратором удаление отменяет
+    fn schedule_user_deletion(&self, user_id: Uuid, delete_after: DateTime<Utc>, requested_at: DateTime<Utc>) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Удаляет способы входа, атрибуты и роли одной транзакцией, `users.user` остаётся без данных
+    fn delete_user(&self, user_id: Uuid, deleted_at: DateTime<Utc>) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Регистрирует гостя одной транзакцией, возвращает добавленные способы входа
+    fn upgrade_guest(&self, upgrade: GuestUpgrade) -> impl Future<Output = Result<Vec<AuthMethod>, Self::Error>> + Send;
+    /// Добавляет существующему пользователю способы входа, атрибуты и роли одной транзакцией.
+    /// Одноимённые атрибуты заменяются, возвращает добавленные способы входа
+    fn link_credentials(&self, user_id: Uuid, credentials: NewUser) -> impl Future<Output = Result<Vec<AuthMethod>, Self::Error>> + Send;
 }
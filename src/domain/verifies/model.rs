@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TelegramData {
     pub id: i64,
@@ -7,4 +9,26 @@ pub struct TelegramData {
     pub photo_url: Option<String>,
     pub auth_date: i64,
     pub hash: String,
-}
\ No newline at end of file
+    /// Поля, которые Telegram добавил в payload, но которые мы не разбираем.
+    /// Они тоже входят в data-check-string.
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Подпись запроса из заголовка `Authorization: AWR-HMAC-SHA256 ...`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSignature {
+    /// Идентификатор API ключа (часть ключа до секрета)
+    pub identifier: String,
+    /// Unix time, когда клиент подписал запрос
+    pub timestamp: i64,
+    pub signature: String,
+}
+
+/// Части запроса, которые покрывает подпись
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedRequest {
+    pub method: String,
+    /// Путь вместе с query string, как он пришёл
+    pub path: String,
+    pub body: Vec<u8>,
+}
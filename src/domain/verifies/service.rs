@@ -1,6 +1,8 @@
 use crate::domain::errors::service::AppErrorInfo;
-use super::model::TelegramData;
+use super::model::{RequestSignature, SignedRequest, TelegramData};
+use crate::domain::user::models::extended::ExtendedAuthMethod;
 use std::fmt::Display;
+use uuid::Uuid;
 
 pub trait PasswordVerifierService {
     type Error: Display + AppErrorInfo;
@@ -14,10 +16,33 @@ pub trait ApiKeyVerifierService {
     fn generate(&self) -> String;
     fn extract_identifier(&self, api_key: &str) -> Result<String, Self::Error>;
     fn create_hash(&self, api_key: &str) -> Result<String, Self::Error>;
+    /// Хэш создан не текущим алгоритмом и его стоит пересчитать
+    fn needs_rehash(&self, api_key_hash: &str) -> bool;
+    /// Способ входа по недавно проверенному ключу
+    fn cached(&self, api_key: &str) -> Option<ExtendedAuthMethod>;
+    fn remember(&self, api_key: &str, auth_method: &ExtendedAuthMethod);
+    /// Сбрасывает закэшированные ключи пользователя: отзыв ключа, отключение, удаление
+    fn forget_user(&self, user_id: Uuid);
+    /// Зашифрованный ключ подписи запросов для хранения рядом с хэшем.
+    /// `None` - подпись запросов не настроена
+    fn signing_secret(&self, api_key: &str) -> Result<Option<String>, Self::Error>;
+    fn parse_signature(&self, authorization: &str) -> Result<RequestSignature, Self::Error>;
+    /// Проверяет подпись, свежесть timestamp и что подпись ещё не встречалась
+    fn is_signature_verified(
+        &self,
+        signing_secret: &str,
+        signature: &RequestSignature,
+        request: &SignedRequest,
+    ) -> Result<bool, Self::Error>;
 }
 
 
 pub trait TelegramVerifierService {
     type Error: AppErrorInfo;
-    fn is_verified(&self, telegram_data: TelegramData) -> Result<bool, Self::Error>;
+    /// `bot` - имя бота из настроек, `None` - бот по умолчанию.
+    /// Уже использованные данные отклоняются, но сама проверка их не запоминает
+    fn is_verified(&self, bot: Option<&str>, telegram_data: TelegramData) -> Result<bool, Self::Error>;
+    /// Запоминает данные после успешного входа, повторно они не пройдут `is_verified`.
+    /// Ошибка, если их уже использовал параллельный запрос
+    fn remember(&self, telegram_data: &TelegramData) -> Result<(), Self::Error>;
 }
\ No newline at end of file
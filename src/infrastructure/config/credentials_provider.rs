@@ -40,6 +40,7 @@ impl CredentialsService for CredentialsProvider {
                     stage: "deserialize",
                     source: e,
                 })?;
+        let credentials = credentials.with_legacy_bot_token();
 
         let mut cache_lock = CREDENTIALS_CACHE
             .write()
@@ -1,7 +1,10 @@
-use crate::domain::jwt::model::{Claims, HasuraClaims, RefreshClaims};
+use crate::domain::errors::locale::LOCALE_ATTRIBUTE;
+use crate::domain::jwt::model::{Claims, HasuraClaims, RefreshClaims, API_KEY_AMR};
 use crate::domain::jwt::service::JwtClaimsService;
 use crate::domain::settings::model::Credentials;
 use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::models::role::expand_roles;
+use crate::domain::user::models::status::UserStatus;
 
 use super::error::JwtError;
 
@@ -19,20 +22,42 @@ impl JwtClaimsService for ClaimsProvider {
     type Error = JwtError;
 
     fn access_claims(&self, user: &ExtendedAuthMethod) -> Result<Claims, Self::Error> {
-        let x_hasura_default_role = user
+        // Все способы входа и обновление токена проходят здесь
+        let user_id = user.user_id().to_string();
+        match user.user().status_at(chrono::Utc::now()) {
+            UserStatus::Active => {}
+            UserStatus::Disabled => return Err(JwtError::UserDisabled(user_id)),
+            UserStatus::Locked => return Err(JwtError::UserLocked(user_id)),
+            UserStatus::PendingVerification => return Err(JwtError::UserNotVerified(user_id)),
+        }
+
+        // Ключ с ограничением получает только пересечение со своими ролями
+        let user_roles = user
             .user()
             .user_roles()
+            .iter()
+            .filter(|v| match user.roles() {
+                Some(roles) => roles.contains(v.role()),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        if user.roles().is_some() && user_roles.is_empty() {
+            return Err(JwtError::AllowedRolesMissing);
+        }
+
+        let x_hasura_default_role = user_roles
             .iter()
             .find(|v| *v.is_default())
+            .or_else(|| user.roles().as_ref().and(user_roles.first()))
             .map(|v| Ok(v.role().clone()))
             .unwrap_or_else(|| Err(JwtError::DefaultRoleMissing))?;
 
-        let x_hasura_allowed_roles = user
-            .user()
-            .user_roles()
+        let x_hasura_allowed_roles = user_roles
             .iter()
             .map(|v| v.role().clone())
             .collect::<Vec<_>>();
+        let x_hasura_allowed_roles = expand_roles(&x_hasura_allowed_roles, self.credentials.role_hierarchy());
 
         let x_hasura_user_id = user.user_id().to_string();
 
@@ -48,12 +73,30 @@ impl JwtClaimsService for ClaimsProvider {
             .expect("valid timestamp")
             .timestamp() as usize;
 
-        Ok(Claims::new(
+        let now = chrono::Utc::now().timestamp() as usize;
+
+        let locale = user
+            .user()
+            .user_attributes()
+            .iter()
+            .find(|v| v.attribute() == LOCALE_ATTRIBUTE)
+            .map(|v| v.value().clone());
+
+        let claims = Claims::new(
             x_hasura_user_id,
             false,
             expiration,
             hasura_claims,
-        ))
+        )
+        .with_scopes(user.scopes().clone())
+        .with_amr(user.auth_type())
+        .with_locale(locale);
+
+        // Предъявление API ключа - не ввод учётных данных пользователем
+        if user.auth_type() == API_KEY_AMR {
+            return Ok(claims.with_iat(now));
+        }
+        Ok(claims.with_auth_time(now, now))
     }
 
     fn inner_access_claims(&self) -> Result<Claims, Self::Error> {
@@ -89,7 +132,8 @@ impl JwtClaimsService for ClaimsProvider {
             .checked_add_signed(chrono::Duration::hours(exp.into()))
             .expect("valid timestamp")
             .timestamp() as usize;
-        Ok(RefreshClaims::new(sub, expiration))
+        let now = chrono::Utc::now().timestamp() as usize;
+        Ok(RefreshClaims::new(sub, expiration).with_auth_time(now))
     }
 }
 
@@ -166,6 +210,64 @@ mod test {
 
     }
 
+    fn scoped_auth_method(roles: &[&str], scopes: &[&str]) -> ExtendedAuthMethod {
+        let mut value = serde_json::to_value(extended_auth_method()).unwrap();
+        value["roles"] = serde_json::json!(roles);
+        value["scopes"] = serde_json::json!(scopes);
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn scoped_access_claims() {
+        let provider = ClaimsProvider::new(Credentials::mock());
+        let user = scoped_auth_method(&["test"], &["reports:read"]);
+
+        let claims = provider.access_claims(&user).unwrap();
+
+        assert_eq!(claims.hasura_claims.x_hasura_allowed_roles, vec!["test".to_string()]);
+        assert_eq!(claims.hasura_claims.x_hasura_default_role, "test");
+        assert_eq!(claims.scopes, Some(vec!["reports:read".to_string()]));
+    }
+
+    #[test]
+    fn api_key_claims_have_amr_without_auth_time() {
+        let provider = ClaimsProvider::new(Credentials::mock());
+        let mut value = serde_json::to_value(extended_auth_method()).unwrap();
+        value["auth_type"] = serde_json::json!("apikey");
+        let user: ExtendedAuthMethod = serde_json::from_value(value).unwrap();
+
+        let claims = provider.access_claims(&user).unwrap();
+
+        assert_eq!(claims.amr, Some(vec!["apikey".to_string()]));
+        assert!(claims.is_api_key());
+        assert!(claims.iat.is_some());
+        assert_eq!(claims.auth_time, None);
+
+        let claims = provider.access_claims(&extended_auth_method()).unwrap();
+        assert_eq!(claims.amr, Some(vec!["email".to_string()]));
+        assert!(!claims.is_api_key());
+        assert!(claims.auth_time.is_some());
+    }
+
+    #[test]
+    fn implied_roles_are_allowed() {
+        let provider = ClaimsProvider::new(Credentials::mock());
+
+        let claims = provider.access_claims(&extended_auth_method()).unwrap();
+
+        assert_eq!(claims.hasura_claims.x_hasura_allowed_roles, vec!["user", "admin", "default", "test"]);
+    }
+
+    #[test]
+    fn scoped_access_claims_without_user_roles() {
+        let provider = ClaimsProvider::new(Credentials::mock());
+        let user = scoped_auth_method(&["not_assigned"], &[]);
+
+        let result = provider.access_claims(&user);
+
+        assert!(matches!(result, Err(JwtError::AllowedRolesMissing)));
+    }
+
     #[test]
     fn inner_access_claims() {
         let credentials = Credentials::mock();
@@ -15,6 +15,22 @@ pub enum JwtError {
     #[error("Default role is missing in the JWT claims")]
     DefaultRoleMissing,
 
+    /// None of the roles allowed for the credential is assigned to the user.
+    #[error("No allowed roles left for the credential")]
+    AllowedRolesMissing,
+
+    /// The user is disabled by an administrator.
+    #[error("User {0} is disabled")]
+    UserDisabled(String),
+
+    /// The user is locked, temporarily or until unlocked.
+    #[error("User {0} is locked")]
+    UserLocked(String),
+
+    /// The user has not confirmed the email or invitation yet.
+    #[error("User {0} is pending verification")]
+    UserNotVerified(String),
+
     /// A JWT-related error occurred during a specific stage (e.g., encoding, decoding).
     #[error("JWT error during '{stage}' stage: {source}")]
     JwtProcessingError {
@@ -34,6 +50,12 @@ impl AppErrorInfo for JwtError {
             JwtError::DefaultRoleMissing => {
                 format!("Missing default role")
             }
+            JwtError::AllowedRolesMissing => {
+                format!("Missing allowed roles")
+            }
+            JwtError::UserDisabled(_) => "Account is disabled".to_string(),
+            JwtError::UserLocked(_) => "Account is locked".to_string(),
+            JwtError::UserNotVerified(_) => "Account is not verified".to_string(),
             JwtError::JwtProcessingError { .. } => {
                 format!("Token is not correct")
             }
@@ -53,6 +75,12 @@ impl AppErrorInfo for JwtError {
             JwtError::DefaultRoleMissing => {
                 format!("JwtError::DefaultRoleMissing")
             }
+            JwtError::AllowedRolesMissing => {
+                format!("JwtError::AllowedRolesMissing")
+            }
+            JwtError::UserDisabled(user_id) => format!("JwtError::UserDisabled: {}", user_id),
+            JwtError::UserLocked(user_id) => format!("JwtError::UserLocked: {}", user_id),
+            JwtError::UserNotVerified(user_id) => format!("JwtError::UserNotVerified: {}", user_id),
             JwtError::JwtProcessingError { stage, source } => {
                 format!(
                     "JwtError::JwtProcessingError stage: {} source: {}",
@@ -61,6 +89,27 @@ impl AppErrorInfo for JwtError {
             }
         }
     }
+    fn status_code(&self) -> u16 {
+        match self {
+            JwtError::CredentialsUnavailable(_) => 500,
+            JwtError::DefaultRoleMissing
+            | JwtError::AllowedRolesMissing
+            | JwtError::UserDisabled(_)
+            | JwtError::UserNotVerified(_) => 403,
+            JwtError::UserLocked(_) => 423,
+            JwtError::JwtProcessingError { .. } => 401,
+        }
+    }
+    fn error_code(&self) -> &'static str {
+        match self {
+            JwtError::CredentialsUnavailable(_) => "internal_error",
+            JwtError::DefaultRoleMissing | JwtError::AllowedRolesMissing => "no_allowed_roles",
+            JwtError::UserDisabled(_) => "account_disabled",
+            JwtError::UserLocked(_) => "account_locked",
+            JwtError::UserNotVerified(_) => "account_not_verified",
+            JwtError::JwtProcessingError { .. } => "invalid_token",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,5 +1,9 @@
 pub mod config;
 pub mod jwt;
+#[cfg(feature = "hasura")]
 pub mod network;
+#[cfg(feature = "telegram")]
+pub mod telegram;
+#[cfg(feature = "hasura")]
 pub mod user;
-pub mod verifies;
+pub mod verifies;
\ No newline at end of file
@@ -1,10 +1,12 @@
+use std::future::Future;
+
 use super::error::HasuraClientError;
 use include_dir::Dir;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 pub trait HasuraInterface {
-    async fn execute<D, T>(&mut self, descriptor: &D) -> Result<T, HasuraClientError>
+    fn execute<D, T>(&mut self, descriptor: &D) -> impl Future<Output = Result<T, HasuraClientError>> + Send
     where
         D: StaticGQLDescriptor + ObjectGQLDescriptor + Sync,
         T: DeserializeOwned + Send;
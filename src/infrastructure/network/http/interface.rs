@@ -1,4 +1,6 @@
+use std::future::Future;
+
 pub trait HttpClientInterface: Send + Sync {
     type Error: ToString + Send + Sync;
-    async fn post(&mut self, body: String) -> Result<String, Self::Error>;
+    fn post(&mut self, body: String) -> impl Future<Output = Result<String, Self::Error>> + Send;
 }
@@ -0,0 +1,132 @@
+use serde::Deserialize;
+
+use crate::domain::telegram::service::TelegramBotService;
+use crate::infrastructure::network::http::interface::HttpClientInterface;
+
+use super::errors::TelegramBotError;
+
+#[derive(Debug, Deserialize)]
+struct BotApiResponse {
+    ok: bool,
+    description: Option<String>,
+}
+
+/// Клиент Bot API одного бота.
+/// `http` смотрит на `<telegram_api_url>/bot<token>/sendMessage`
+#[derive(Clone)]
+pub struct TelegramBotClient<T: HttpClientInterface> {
+    http: T,
+}
+
+impl<T: HttpClientInterface + Clone> TelegramBotClient<T> {
+    pub fn new(http: T) -> Self {
+        Self { http }
+    }
+
+    async fn send(&self, body: serde_json::Value) -> Result<(), TelegramBotError> {
+        let mut http = self.http.clone();
+
+        let response = http
+            .post(body.to_string())
+            .await
+            .map_err(|e| TelegramBotError::RequestFailed(e.to_string()))?;
+
+        let response: BotApiResponse = serde_json::from_str(&response)
+            .map_err(|e| TelegramBotError::ResponseParseError(e.to_string()))?;
+
+        if !response.ok {
+            return Err(TelegramBotError::ApiError(response.description.unwrap_or_default()));
+        }
+        Ok(())
+    }
+}
+
+impl<T: HttpClientInterface + Clone> TelegramBotService for TelegramBotClient<T> {
+    type Error = TelegramBotError;
+
+    async fn send_message(&self, chat_id: i64, text: &str) -> Result<(), Self::Error> {
+        let body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": text,
+        });
+        self.send(body).await
+    }
+
+    async fn send_button(&self, chat_id: i64, text: &str, button: &str, callback_data: &str) -> Result<(), Self::Error> {
+        let body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": text,
+            "reply_markup": {
+                "inline_keyboard": [[{ "text": button, "callback_data": callback_data }]],
+            },
+        });
+        self.send(body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::network::http::client::HttpClient;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{body_json, method, path};
+
+    #[tokio::test]
+    async fn send_message() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/botTOKEN/sendMessage"))
+            .and(body_json(serde_json::json!({"chat_id": 42, "text": "hello"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true, "result": {}})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http = HttpClient::new(format!("{}/botTOKEN/sendMessage", mock_server.uri()));
+        let result = TelegramBotClient::new(http).send_message(42, "hello").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn send_button() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/botTOKEN/sendMessage"))
+            .and(body_json(serde_json::json!({
+                "chat_id": 42,
+                "text": "confirm?",
+                "reply_markup": {"inline_keyboard": [[{"text": "Yes", "callback_data": "login:nonce"}]]}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true, "result": {}})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http = HttpClient::new(format!("{}/botTOKEN/sendMessage", mock_server.uri()));
+        let result = TelegramBotClient::new(http).send_button(42, "confirm?", "Yes", "login:nonce").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn send_message_api_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/botTOKEN/sendMessage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": false,
+                "description": "Bad Request: chat not found"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let http = HttpClient::new(format!("{}/botTOKEN/sendMessage", mock_server.uri()));
+        let result = TelegramBotClient::new(http).send_message(42, "hello").await;
+
+        assert!(matches!(result, Err(TelegramBotError::ApiError(v)) if v == "Bad Request: chat not found"));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::domain::errors::service::AppErrorInfo;
+use crate::domain::errors::service::ErrorLevel;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LoginSessionError {
+    #[error("Telegram login session not found or expired")]
+    NotFound,
+    #[error("Telegram login session is already bound")]
+    AlreadyBound,
+    #[error("Telegram login session belongs to bot {expected}, got {got}")]
+    BotMismatch { expected: String, got: String },
+    #[error("Failed to access login session store: {0}")]
+    StoreError(String),
+}
+
+impl AppErrorInfo for LoginSessionError {
+    fn client_message(&self) -> String {
+        match self {
+            LoginSessionError::StoreError(_) => self.internal_error(),
+            LoginSessionError::AlreadyBound => "Telegram login session is already used".to_string(),
+            _ => "Telegram login session not found or expired".to_string(),
+        }
+    }
+    fn level(&self) -> ErrorLevel {
+        match self {
+            LoginSessionError::StoreError(_) => ErrorLevel::Error,
+            _ => ErrorLevel::Info,
+        }
+    }
+    fn log_message(&self) -> String {
+        format!("LoginSessionError: {}", self)
+    }
+    fn status_code(&self) -> u16 {
+        match self {
+            LoginSessionError::StoreError(_) => 500,
+            LoginSessionError::AlreadyBound => 409,
+            _ => 404,
+        }
+    }
+    fn error_code(&self) -> &'static str {
+        match self {
+            LoginSessionError::StoreError(_) => "internal_error",
+            LoginSessionError::AlreadyBound => "login_session_used",
+            _ => "login_session_not_found",
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TelegramBotError {
+    #[error("Bot API request failed: {0}")]
+    RequestFailed(String),
+    #[error("Bot API response is not valid JSON: {0}")]
+    ResponseParseError(String),
+    #[error("Bot API returned an error: {0}")]
+    ApiError(String),
+}
+
+impl AppErrorInfo for TelegramBotError {
+    fn client_message(&self) -> String {
+        self.internal_error()
+    }
+    fn level(&self) -> ErrorLevel {
+        ErrorLevel::Warning
+    }
+    fn log_message(&self) -> String {
+        format!("TelegramBotError: {}", self)
+    }
+    fn status_code(&self) -> u16 {
+        502
+    }
+    fn error_code(&self) -> &'static str {
+        "telegram_unavailable"
+    }
+}
@@ -0,0 +1,42 @@
+use crate::domain::settings::model::Credentials;
+use crate::domain::telegram::factories::TelegramProviderFactory;
+use crate::infrastructure::network::http::client::HttpClient;
+
+use super::bot_client::TelegramBotClient;
+use super::login_session::LoginSessionStore;
+
+/// Ответ боту не критичен для входа, долго не ретраим
+const BOT_API_MAX_RETRY: u64 = 2;
+
+pub struct TelegramProvider {
+    credentials: Credentials,
+}
+
+impl TelegramProvider {
+    pub fn new(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+}
+
+impl TelegramProviderFactory for TelegramProvider {
+    type LoginSession = LoginSessionStore;
+    type Bot = TelegramBotClient<HttpClient>;
+
+    fn login_session(&self) -> Self::LoginSession {
+        LoginSessionStore::new(self.credentials.clone())
+    }
+
+    fn bot(&self, name: &str) -> Option<Self::Bot> {
+        let bot = self.credentials.telegram_bot(Some(name))?;
+        let uri = format!(
+            "{}/bot{}/sendMessage",
+            self.credentials.telegram_api_url(),
+            bot.token()
+        );
+
+        let mut http = HttpClient::new(uri)
+            .add_header(("content-type".to_string(), "application/json".to_string()));
+        http.set_max_retry(BOT_API_MAX_RETRY);
+        Some(TelegramBotClient::new(http))
+    }
+}
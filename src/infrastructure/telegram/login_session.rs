@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use subtle::ConstantTimeEq;
+
+use crate::domain::settings::model::Credentials;
+use crate::domain::telegram::model::{LoginSession, TelegramUser};
+use crate::domain::telegram::service::LoginSessionService;
+
+use super::errors::LoginSessionError;
+
+lazy_static! {
+    /// nonce -> сессия. Хранится в памяти процесса: при нескольких
+    /// инстансах вебхук и обмен должны попадать на один и тот же
+    static ref LOGIN_SESSIONS: Mutex<HashMap<String, LoginSession>> = Mutex::new(HashMap::new());
+}
+
+pub struct LoginSessionStore {
+    credentials: Credentials,
+}
+
+impl LoginSessionStore {
+    pub fn new(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+
+    fn sessions(&self) -> Result<std::sync::MutexGuard<'static, HashMap<String, LoginSession>>, LoginSessionError> {
+        let mut sessions = LOGIN_SESSIONS
+            .lock()
+            .map_err(|e| LoginSessionError::StoreError(e.to_string()))?;
+
+        let now = chrono::Utc::now().timestamp();
+        sessions.retain(|_, session| session.expires_at > now);
+        Ok(sessions)
+    }
+
+    /// Telegram принимает в start только [A-Za-z0-9_-] до 64 символов
+    fn generate_nonce() -> String {
+        hex::encode(rand::random::<[u8; 16]>())
+    }
+
+    fn generate_secret() -> String {
+        hex::encode(rand::random::<[u8; 32]>())
+    }
+
+    /// 6 цифр: пользователь сверяет их глазами, угадывать их незачем
+    fn generate_code() -> String {
+        format!("{:06}", rand::random::<u32>() % 1_000_000)
+    }
+
+    fn find_pending<'a>(
+        sessions: &'a mut HashMap<String, LoginSession>,
+        bot: &str,
+        nonce: &str,
+    ) -> Result<&'a mut LoginSession, LoginSessionError> {
+        let Some(session) = sessions.get_mut(nonce) else {
+            return Err(LoginSessionError::NotFound);
+        };
+        if session.bot != bot {
+            return Err(LoginSessionError::BotMismatch {
+                expected: session.bot.clone(),
+                got: bot.to_string(),
+            });
+        }
+        if session.user.is_some() {
+            return Err(LoginSessionError::AlreadyBound);
+        }
+        Ok(session)
+    }
+}
+
+impl LoginSessionService for LoginSessionStore {
+    type Error = LoginSessionError;
+
+    fn create(&self, bot: &str) -> Result<LoginSession, Self::Error> {
+        let mut sessions = self.sessions()?;
+
+        let session = LoginSession {
+            nonce: Self::generate_nonce(),
+            secret: Self::generate_secret(),
+            code: Self::generate_code(),
+            bot: bot.to_string(),
+            expires_at: chrono::Utc::now().timestamp() + self.credentials.telegram_login_ttl(),
+            user: None,
+        };
+        sessions.insert(session.nonce.clone(), session.clone());
+        Ok(session)
+    }
+
+    fn pending(&self, bot: &str, nonce: &str) -> Result<LoginSession, Self::Error> {
+        let mut sessions = self.sessions()?;
+        Self::find_pending(&mut sessions, bot, nonce).cloned()
+    }
+
+    fn bind(&self, bot: &str, nonce: &str, user: TelegramUser) -> Result<LoginSession, Self::Error> {
+        let mut sessions = self.sessions()?;
+
+        let session = Self::find_pending(&mut sessions, bot, nonce)?;
+        session.user = Some(user);
+        Ok(session.clone())
+    }
+
+    fn take_bound(&self, nonce: &str, secret: &str) -> Result<Option<LoginSession>, Self::Error> {
+        let mut sessions = self.sessions()?;
+
+        // Чужой secret неотличим от несуществующей сессии
+        match sessions.get(nonce) {
+            None => Err(LoginSessionError::NotFound),
+            Some(session) if !bool::from(session.secret.as_bytes().ct_eq(secret.as_bytes())) => {
+                Err(LoginSessionError::NotFound)
+            }
+            Some(session) if session.user.is_none() => Ok(None),
+            Some(_) => Ok(sessions.remove(nonce)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> TelegramUser {
+        TelegramUser {
+            id: 42,
+            first_name: Some("Mock".to_string()),
+            last_name: None,
+            username: Some("mock_user".to_string()),
+        }
+    }
+
+    #[test]
+    fn bound_session_is_taken_once() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+
+        assert_eq!(store.take_bound(&session.nonce, &session.secret).unwrap(), None);
+
+        store.bind("TEST", &session.nonce, user()).unwrap();
+        let taken = store.take_bound(&session.nonce, &session.secret).unwrap().unwrap();
+        assert_eq!(taken.user, Some(user()));
+
+        let result = store.take_bound(&session.nonce, &session.secret);
+        assert!(matches!(result, Err(LoginSessionError::NotFound)));
+    }
+
+    #[test]
+    fn bound_session_is_not_taken_by_nonce_alone() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+        store.bind("TEST", &session.nonce, user()).unwrap();
+
+        let result = store.take_bound(&session.nonce, "");
+        assert!(matches!(result, Err(LoginSessionError::NotFound)));
+
+        let result = store.take_bound(&session.nonce, &session.nonce);
+        assert!(matches!(result, Err(LoginSessionError::NotFound)));
+
+        assert!(store.take_bound(&session.nonce, &session.secret).unwrap().is_some());
+    }
+
+    #[test]
+    fn pending_session_is_not_bound() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+
+        let pending = store.pending("TEST", &session.nonce).unwrap();
+
+        assert_eq!(pending.code, session.code);
+        assert_eq!(pending.code.len(), 6);
+        assert_eq!(store.take_bound(&session.nonce, &session.secret).unwrap(), None);
+    }
+
+    #[test]
+    fn session_is_bound_only_once() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+
+        store.bind("TEST", &session.nonce, user()).unwrap();
+        let result = store.bind("TEST", &session.nonce, user());
+
+        assert!(matches!(result, Err(LoginSessionError::AlreadyBound)));
+    }
+
+    #[test]
+    fn session_is_bound_only_by_its_bot() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+
+        let result = store.bind("OTHER", &session.nonce, user());
+
+        assert!(matches!(result, Err(LoginSessionError::BotMismatch { .. })));
+    }
+
+    #[test]
+    fn expired_session_is_forgotten() {
+        let store = LoginSessionStore::new(Credentials::mock());
+        let session = store.create("TEST").unwrap();
+        LOGIN_SESSIONS
+            .lock()
+            .unwrap()
+            .get_mut(&session.nonce)
+            .unwrap()
+            .expires_at = chrono::Utc::now().timestamp() - 1;
+
+        let result = store.bind("TEST", &session.nonce, user());
+
+        assert!(matches!(result, Err(LoginSessionError::NotFound)));
+    }
+}
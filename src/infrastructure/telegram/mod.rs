@@ -0,0 +1,4 @@
+pub mod bot_client;
+pub mod errors;
+pub mod factory;
+pub mod login_session;
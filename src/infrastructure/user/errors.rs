@@ -22,6 +22,24 @@ pub enum UserManagerError {
 
     #[error("Failed create allowed roles")]
     FailedUpdateApiKey,
+
+    #[error("Failed merge users")]
+    FailedMergeUsers,
+
+    #[error("Failed update user attribute")]
+    FailedUpdateAttribute,
+
+    #[error("Failed create role")]
+    FailedCreateRole,
+
+    #[error("Failed create invite")]
+    FailedCreateInvite,
+
+    #[error("Failed delete user")]
+    FailedDeleteUser,
+
+    #[error("Failed upgrade guest")]
+    FailedUpgradeGuest,
 }
 
 impl AppErrorInfo for UserManagerError {
@@ -33,6 +51,12 @@ impl AppErrorInfo for UserManagerError {
             }
             UserManagerError::FailedUpdateApiKey => "Failed create api key try again".to_string(),
             UserManagerError::UserNotFound => "User not found".to_string(),
+            UserManagerError::FailedMergeUsers => "Failed merge accounts try again".to_string(),
+            UserManagerError::FailedUpdateAttribute => "Failed update profile try again".to_string(),
+            UserManagerError::FailedCreateRole => "Failed create role try again".to_string(),
+            UserManagerError::FailedCreateInvite => "Failed create invite try again".to_string(),
+            UserManagerError::FailedDeleteUser => "Failed delete account try again".to_string(),
+            UserManagerError::FailedUpgradeGuest => "Failed sign up try again".to_string(),
             _ => self.internal_error(),
         }
     }
@@ -57,6 +81,24 @@ impl AppErrorInfo for UserManagerError {
                 "Failed to create allowed roles.".to_string()
             }
             UserManagerError::FailedUpdateApiKey => "Failed to update API key.".to_string(),
+            UserManagerError::FailedMergeUsers => "Secondary user is not tombstoned after merge.".to_string(),
+            UserManagerError::FailedUpdateAttribute => "Attributes are not returned after update.".to_string(),
+            UserManagerError::FailedCreateRole => "Role is not returned after insert.".to_string(),
+            UserManagerError::FailedCreateInvite => "Invite is not returned after insert.".to_string(),
+            UserManagerError::FailedDeleteUser => "User is not anonymised after delete.".to_string(),
+            UserManagerError::FailedUpgradeGuest => "Guest auth method is not removed after upgrade.".to_string(),
+        }
+    }
+    fn status_code(&self) -> u16 {
+        match self {
+            UserManagerError::UserNotFound => 404,
+            _ => 500,
+        }
+    }
+    fn error_code(&self) -> &'static str {
+        match self {
+            UserManagerError::UserNotFound => "user_not_found",
+            _ => "internal_error",
         }
     }
 }
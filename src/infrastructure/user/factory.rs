@@ -6,15 +6,17 @@ use crate::infrastructure::network::http::interface::HttpClientInterface;
 
 
 
+use super::schema_cache::AttributeSchemaCache;
 use super::user_manager::{UserCommand, UserQuery};
 
 pub struct UserProvider {
     credentials: Credentials,
-    hasura_client: HasuraClient<HttpClient>
+    hasura_client: HasuraClient<HttpClient>,
+    attribute_schema: AttributeSchemaCache,
 }
 impl UserProvider {
     pub fn new(credentials: Credentials, hasura_client: HasuraClient<HttpClient>) -> Self {
-        Self { credentials, hasura_client }
+        Self { credentials, hasura_client, attribute_schema: AttributeSchemaCache::default() }
     }
 }
 
@@ -26,5 +28,6 @@ impl UserProviderFactory for UserProvider {
     }
     fn query_user(&self) -> Self::QueryUser {
         UserQuery::new(self.credentials.clone(), self.hasura_client.clone())
+            .with_attribute_schema_cache(self.attribute_schema.clone())
     }
 }
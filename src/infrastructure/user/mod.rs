@@ -1,6 +1,7 @@
 pub mod errors;
 pub mod factory;
 pub mod requests;
+pub mod schema_cache;
 pub mod user_manager;
 
 use super::network;
@@ -20,7 +20,13 @@ impl ObjectGQLDescriptor for AddAuthMethodDescriptor {
                 "auth_type": self.auth_method.auth_type(),
                 "secret": self.auth_method.secret(),
                 "user_id": self.auth_method.user_id(),
-                "identifier": self.auth_method.identifier()
+                "identifier": self.auth_method.identifier(),
+                "provider": self.auth_method.provider(),
+                "name": self.auth_method.name(),
+                "expires_at": self.auth_method.expires_at(),
+                "roles": self.auth_method.roles(),
+                "scopes": self.auth_method.scopes(),
+                "signing_secret": self.auth_method.signing_secret()
 
             }
         );
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+use super::list_users::Aggregate;
+
+/// Занято ли значение атрибута кем-то, кроме `except_user`.
+/// Без пользователя передаётся nil UUID, такого id в `users.user` нет
+pub struct CheckAttributeValueRequestDescriptor {
+    attribute: String,
+    value: String,
+    except_user: Uuid,
+}
+impl CheckAttributeValueRequestDescriptor {
+    pub fn new(attribute: String, value: String, except_user: Option<Uuid>) -> Self {
+        Self { attribute, value, except_user: except_user.unwrap_or(Uuid::nil()) }
+    }
+}
+
+impl ObjectGQLDescriptor for CheckAttributeValueRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "attribute": self.attribute,
+            "value": self.value,
+            "except_user": self.except_user,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for CheckAttributeValueRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_attribute_value.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "CheckAttributeValue"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CheckAttributeValueResponse {
+    pub users_user_attribute_aggregate: Aggregate,
+}
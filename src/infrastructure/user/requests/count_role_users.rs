@@ -0,0 +1,36 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+use super::list_users::Aggregate;
+
+/// Роль передаётся как users_role_enum: неизвестное значение Hasura отклонит
+pub struct CountRoleUsersRequestDescriptor {
+    role: String,
+}
+impl CountRoleUsersRequestDescriptor {
+    pub fn new(role: String) -> Self {
+        Self { role }
+    }
+}
+
+impl ObjectGQLDescriptor for CountRoleUsersRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "role": self.role })
+    }
+}
+
+impl StaticGQLDescriptor for CountRoleUsersRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "count_role_users.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "CountRoleUsers"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CountRoleUsersResponse {
+    pub users_user_role_aggregate: Aggregate,
+}
@@ -0,0 +1,43 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::invite::Invite;
+
+pub struct CreateInviteDescriptor {
+    invite: Invite,
+}
+impl CreateInviteDescriptor {
+    pub fn new(invite: Invite) -> Self {
+        Self { invite }
+    }
+}
+
+impl ObjectGQLDescriptor for CreateInviteDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.invite.code(),
+            "role": self.invite.role(),
+            "max_uses": self.invite.max_uses(),
+            "uses_left": self.invite.uses_left(),
+            "expires_at": self.invite.expires_at(),
+            "comment": self.invite.comment(),
+        })
+    }
+}
+
+impl StaticGQLDescriptor for CreateInviteDescriptor {
+    fn filename(&self) -> &'static str {
+        "insert_invite.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "InsertInvite"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CreateInviteResponse {
+    pub insert_users_invite_one: Option<Invite>,
+}
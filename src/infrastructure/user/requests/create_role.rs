@@ -0,0 +1,36 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::role::Role;
+
+pub struct CreateRoleDescriptor {
+    role: Role,
+}
+impl CreateRoleDescriptor {
+    pub fn new(role: Role) -> Self {
+        Self { role }
+    }
+}
+
+impl ObjectGQLDescriptor for CreateRoleDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "value": self.role.value(), "comment": self.role.comment() })
+    }
+}
+
+impl StaticGQLDescriptor for CreateRoleDescriptor {
+    fn filename(&self) -> &'static str {
+        "insert_role.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "InsertRole"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CreateRoleResponse {
+    pub insert_users_role_one: Option<Role>,
+}
@@ -0,0 +1,86 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::AuthMethod;
+use crate::domain::user::models::extended::ExtendedUser;
+use crate::domain::user::models::new_user::NewUser;
+
+/// Вложенная вставка: Hasura создаёт всё в одной транзакции.
+/// Там же списывается приглашение: если его успели исчерпать, `uses_left >= 0`
+/// отклоняет UPDATE и пользователь не создаётся
+pub struct CreateUserDescriptor {
+    new_user: NewUser,
+}
+impl CreateUserDescriptor {
+    pub fn new(new_user: NewUser) -> Self {
+        Self { new_user }
+    }
+}
+
+impl ObjectGQLDescriptor for CreateUserDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        // user_id не передаём: его проставляет связь с users.user
+        let auth_methods = self
+            .new_user
+            .auth_methods
+            .iter()
+            .map(|v| serde_json::json!({
+                "auth_type": v.auth_type(),
+                "identifier": v.identifier(),
+                "secret": v.secret(),
+                "provider": v.provider(),
+                "name": v.name(),
+                "expires_at": v.expires_at(),
+                "roles": v.roles(),
+                "scopes": v.scopes(),
+                "signing_secret": v.signing_secret(),
+            }))
+            .collect::<Vec<_>>();
+        let attributes = self
+            .new_user
+            .attributes
+            .iter()
+            .map(|v| serde_json::json!({ "attribute": v.attribute(), "value": v.value() }))
+            .collect::<Vec<_>>();
+        let roles = self
+            .new_user
+            .roles
+            .iter()
+            .map(|v| serde_json::json!({ "role": v.role(), "is_default": v.is_default() }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "object": {
+                "auth_methods": { "data": auth_methods },
+                "user_attributes": { "data": attributes },
+                "user_roles": { "data": roles },
+            },
+            "invite": self.new_user.invite.clone().unwrap_or_default(),
+            "with_invite": self.new_user.invite.is_some(),
+        })
+    }
+}
+
+impl StaticGQLDescriptor for CreateUserDescriptor {
+    fn filename(&self) -> &'static str {
+        "insert_user_with.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "InsertUserWith"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CreateUserResponse {
+    pub insert_users_user_one: Option<CreatedUser>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct CreatedUser {
+    #[serde(flatten)]
+    pub user: ExtendedUser,
+    pub auth_methods: Vec<AuthMethod>,
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::status::UserStatus;
+
+/// Способы входа, атрибуты и роли удаляются одним mutation-запросом (одна транзакция).
+/// Строка `users.user` остаётся без персональных данных: на её id ссылаются данные приложения
+pub struct DeleteUserDescriptor {
+    id: Uuid,
+    deleted_at: DateTime<Utc>,
+}
+impl DeleteUserDescriptor {
+    pub fn new(id: Uuid, deleted_at: DateTime<Utc>) -> Self {
+        Self { id, deleted_at }
+    }
+}
+
+impl ObjectGQLDescriptor for DeleteUserDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "status": UserStatus::Disabled.as_str(),
+            "deleted_at": self.deleted_at,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for DeleteUserDescriptor {
+    fn filename(&self) -> &'static str {
+        "delete_user.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "DeleteUser"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct DeleteUserResponse {
+    pub delete_users_auth_method: AffectedRows,
+    pub delete_users_user_attribute: AffectedRows,
+    pub delete_users_user_role: AffectedRows,
+    pub update_users_user_by_pk: Option<DeletedUser>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct AffectedRows {
+    pub affected_rows: i64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct DeletedUser {
+    pub id: Uuid,
+}
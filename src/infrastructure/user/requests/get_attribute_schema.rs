@@ -0,0 +1,29 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::Attribute;
+
+pub struct GetAttributeSchemaRequestDescriptor;
+
+impl ObjectGQLDescriptor for GetAttributeSchemaRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+impl StaticGQLDescriptor for GetAttributeSchemaRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_attribute.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "GetAttribute"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct GetAttributeSchemaResponse {
+    pub users_attribute: Vec<Attribute>,
+}
@@ -0,0 +1,36 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::invite::Invite;
+
+pub struct GetInviteRequestDescriptor {
+    code: String,
+}
+impl GetInviteRequestDescriptor {
+    pub fn new(code: String) -> Self {
+        Self { code }
+    }
+}
+
+impl ObjectGQLDescriptor for GetInviteRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "code": self.code })
+    }
+}
+
+impl StaticGQLDescriptor for GetInviteRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_invite.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "GetInvite"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct GetInviteResponse {
+    pub users_invite: Vec<Invite>,
+}
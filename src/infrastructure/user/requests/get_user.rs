@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::extended::ExtendedUser;
+
+pub struct GetUserRequestDescriptor {
+    id: Uuid,
+}
+impl GetUserRequestDescriptor {
+    pub fn new(id: Uuid) -> Self {
+        Self { id }
+    }
+}
+
+impl ObjectGQLDescriptor for GetUserRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "id": self.id })
+    }
+}
+
+impl StaticGQLDescriptor for GetUserRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_user_by_id.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "GetUserById"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct GetUserResponse {
+    pub users_user_by_pk: Option<ExtendedUser>,
+}
@@ -0,0 +1,47 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+
+pub struct GetUserByProviderRequestDescriptor {
+    identifier: String,
+    auth_type: String,
+    provider: String,
+    with_unassigned: bool,
+}
+impl GetUserByProviderRequestDescriptor {
+    pub fn new(identifier: String, auth_type: String, provider: String, with_unassigned: bool) -> Self {
+        Self { identifier, auth_type, provider, with_unassigned }
+    }
+}
+
+impl ObjectGQLDescriptor for GetUserByProviderRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "identifier": self.identifier,
+            "auth_type": self.auth_type,
+            "provider": self.provider,
+            "with_unassigned": self.with_unassigned
+        })
+    }
+}
+
+impl StaticGQLDescriptor for GetUserByProviderRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_auth_methods_by_provider.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "GetAuthMethodByProvider"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct GetUserByProviderResponse {
+    pub users_auth_method: Vec<ExtendedAuthMethod>,
+    /// Записи без `provider`, созданные до поддержки нескольких ботов
+    #[serde(default)]
+    pub unassigned_auth_method: Vec<ExtendedAuthMethod>,
+}
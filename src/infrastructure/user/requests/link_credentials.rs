@@ -0,0 +1,85 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::AuthMethod;
+use crate::domain::user::models::new_user::NewUser;
+
+/// Способы входа, атрибуты и роли добавляются одним mutation-запросом,
+/// при ошибке у пользователя не остаётся части учётных данных
+pub struct LinkCredentialsDescriptor {
+    user_id: Uuid,
+    credentials: NewUser,
+}
+impl LinkCredentialsDescriptor {
+    pub fn new(user_id: Uuid, credentials: NewUser) -> Self {
+        Self { user_id, credentials }
+    }
+}
+
+impl ObjectGQLDescriptor for LinkCredentialsDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        let user_id = self.user_id;
+        let auth_methods = self
+            .credentials
+            .auth_methods
+            .iter()
+            .map(|v| serde_json::json!({
+                "user_id": user_id,
+                "auth_type": v.auth_type(),
+                "identifier": v.identifier(),
+                "secret": v.secret(),
+                "provider": v.provider(),
+                "name": v.name(),
+                "expires_at": v.expires_at(),
+                "roles": v.roles(),
+                "scopes": v.scopes(),
+                "signing_secret": v.signing_secret(),
+            }))
+            .collect::<Vec<_>>();
+        let attribute_names = self.credentials.attributes.iter().map(|v| v.attribute()).collect::<Vec<_>>();
+        let attributes = self
+            .credentials
+            .attributes
+            .iter()
+            .map(|v| serde_json::json!({ "user_id": user_id, "attribute": v.attribute(), "value": v.value() }))
+            .collect::<Vec<_>>();
+        let roles = self
+            .credentials
+            .roles
+            .iter()
+            .map(|v| serde_json::json!({ "user_id": user_id, "role": v.role(), "is_default": v.is_default() }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "user_id": user_id,
+            "auth_methods": auth_methods,
+            "attribute_names": attribute_names,
+            "attributes": attributes,
+            "roles": roles,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for LinkCredentialsDescriptor {
+    fn filename(&self) -> &'static str {
+        "link_credentials.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "LinkCredentials"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct LinkCredentialsResponse {
+    pub insert_users_auth_method: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<AuthMethod>,
+}
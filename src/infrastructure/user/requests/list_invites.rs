@@ -0,0 +1,29 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::invite::Invite;
+
+pub struct ListInvitesRequestDescriptor;
+
+impl ObjectGQLDescriptor for ListInvitesRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+impl StaticGQLDescriptor for ListInvitesRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_invites.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "ListInvites"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ListInvitesResponse {
+    pub users_invite: Vec<Invite>,
+}
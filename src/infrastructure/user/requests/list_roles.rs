@@ -0,0 +1,29 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::role::Role;
+
+pub struct ListRolesRequestDescriptor;
+
+impl ObjectGQLDescriptor for ListRolesRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+impl StaticGQLDescriptor for ListRolesRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_roles.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "ListRoles"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ListRolesResponse {
+    pub users_role: Vec<Role>,
+}
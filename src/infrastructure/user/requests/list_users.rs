@@ -0,0 +1,61 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::extended::ExtendedUser;
+use crate::domain::user::models::page::UserFilter;
+
+pub struct ListUsersRequestDescriptor {
+    filter: UserFilter,
+}
+impl ListUsersRequestDescriptor {
+    pub fn new(filter: UserFilter) -> Self {
+        Self { filter }
+    }
+}
+
+impl ObjectGQLDescriptor for ListUsersRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        // Объединённые аккаунты помечены deleted_at и в списке не нужны
+        let mut filter = serde_json::json!({ "deleted_at": { "_is_null": true } });
+        if let Some(search) = &self.filter.search {
+            let pattern = format!("%{}%", search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            filter["_or"] = serde_json::json!([
+                { "auth_methods": { "identifier": { "_ilike": pattern } } },
+                { "user_attributes": { "value": { "_ilike": pattern } } }
+            ]);
+        }
+        serde_json::json!({
+            "where": filter,
+            "limit": self.filter.limit,
+            "offset": self.filter.offset
+        })
+    }
+}
+
+impl StaticGQLDescriptor for ListUsersRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_users.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "ListUsers"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ListUsersResponse {
+    pub users_user: Vec<ExtendedUser>,
+    pub users_user_aggregate: Aggregate,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Aggregate {
+    pub aggregate: Count,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Count {
+    pub count: i64,
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+pub struct ListUsersToDeleteRequestDescriptor {
+    now: DateTime<Utc>,
+}
+impl ListUsersToDeleteRequestDescriptor {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now }
+    }
+}
+
+impl ObjectGQLDescriptor for ListUsersToDeleteRequestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "now": self.now })
+    }
+}
+
+impl StaticGQLDescriptor for ListUsersToDeleteRequestDescriptor {
+    fn filename(&self) -> &'static str {
+        "query_users_to_delete.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "ListUsersToDelete"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct ListUsersToDeleteResponse {
+    pub users_user: Vec<UserToDelete>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UserToDelete {
+    pub id: Uuid,
+}
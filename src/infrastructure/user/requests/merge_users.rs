@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::merge::UserMerge;
+
+/// Все изменения идут одним mutation-запросом, Hasura выполняет его в одной транзакции
+pub struct MergeUsersDescriptor {
+    merge: UserMerge,
+    merged_at: DateTime<Utc>,
+}
+impl MergeUsersDescriptor {
+    pub fn new(merge: UserMerge, merged_at: DateTime<Utc>) -> Self {
+        Self { merge, merged_at }
+    }
+}
+
+impl ObjectGQLDescriptor for MergeUsersDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "primary_id": self.merge.primary_id(),
+            "secondary_id": self.merge.secondary_id(),
+            "role_ids": self.merge.role_ids(),
+            "attribute_ids": self.merge.attribute_ids(),
+            "merged_at": self.merged_at,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for MergeUsersDescriptor {
+    fn filename(&self) -> &'static str {
+        "merge_users.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "MergeUsers"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct MergeUsersResponse {
+    pub update_users_auth_method: AffectedRows,
+    pub update_users_user_attribute: AffectedRows,
+    pub update_users_user_role: AffectedRows,
+    pub update_users_user_by_pk: Option<MergedUser>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct AffectedRows {
+    pub affected_rows: i64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct MergedUser {
+    pub id: uuid::Uuid,
+}
@@ -2,7 +2,32 @@ pub mod add_auth_method;
 pub mod add_roles;
 pub mod add_user;
 pub mod add_user_attribute;
+pub mod check_attribute_value;
 pub mod check_auth_method;
+pub mod count_role_users;
+pub mod create_invite;
+pub mod create_role;
+pub mod create_user;
+pub mod delete_user;
+pub mod get_attribute_schema;
+pub mod get_invite;
+pub mod get_user;
 pub mod get_user_by_id;
 pub mod get_user_by_identifier;
+pub mod get_user_by_provider;
 pub mod gql_dir;
+pub mod link_credentials;
+pub mod list_invites;
+pub mod list_roles;
+pub mod list_users;
+pub mod list_users_to_delete;
+pub mod merge_users;
+pub mod remove_auth_method;
+pub mod remove_role;
+pub mod revoke_auth_method;
+pub mod set_default_role;
+pub mod touch_auth_method;
+pub mod update_auth_method_secret;
+pub mod update_user;
+pub mod update_user_attribute;
+pub mod upgrade_guest;
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::AuthMethod;
+
+/// Все способы входа удаляются одной мутацией
+pub struct RemoveAuthMethodDescriptor {
+    ids: Vec<Uuid>,
+    user_id: Uuid,
+}
+impl RemoveAuthMethodDescriptor {
+    pub fn new(ids: Vec<Uuid>, user_id: Uuid) -> Self {
+        Self { ids, user_id }
+    }
+}
+
+impl ObjectGQLDescriptor for RemoveAuthMethodDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "ids": self.ids, "user_id": self.user_id })
+    }
+}
+
+impl StaticGQLDescriptor for RemoveAuthMethodDescriptor {
+    fn filename(&self) -> &'static str {
+        "delete_user_auth_method.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "DeleteUsersAuthMethod"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct RemoveAuthMethodResponse {
+    pub delete_users_auth_method: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<AuthMethod>,
+}
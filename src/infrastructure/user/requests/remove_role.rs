@@ -0,0 +1,44 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::UserRole;
+
+pub struct RemoveRoleDescriptor {
+    user_id: Uuid,
+    role: String,
+}
+impl RemoveRoleDescriptor {
+    pub fn new(user_id: Uuid, role: String) -> Self {
+        Self { user_id, role }
+    }
+}
+
+impl ObjectGQLDescriptor for RemoveRoleDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "user_id": self.user_id, "role": self.role })
+    }
+}
+
+impl StaticGQLDescriptor for RemoveRoleDescriptor {
+    fn filename(&self) -> &'static str {
+        "delete_user_role.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "DeleteUsersRole"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct RemoveRoleResponse {
+    pub delete_users_user_role: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<UserRole>,
+}
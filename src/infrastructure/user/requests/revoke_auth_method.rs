@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::AuthMethod;
+
+pub struct RevokeAuthMethodDescriptor {
+    identifier: String,
+    auth_type: String,
+    user_id: Uuid,
+    revoked_at: DateTime<Utc>,
+}
+impl RevokeAuthMethodDescriptor {
+    pub fn new(identifier: String, auth_type: String, user_id: Uuid, revoked_at: DateTime<Utc>) -> Self {
+        Self { identifier, auth_type, user_id, revoked_at }
+    }
+}
+
+impl ObjectGQLDescriptor for RevokeAuthMethodDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "identifier": self.identifier,
+            "auth_type": self.auth_type,
+            "user_id": self.user_id,
+            "revoked_at": self.revoked_at,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for RevokeAuthMethodDescriptor {
+    fn filename(&self) -> &'static str {
+        "revoke_user_auth_method.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "RevokeUsersAuthMethod"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct RevokeAuthMethodResponse {
+    pub update_users_auth_method: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<AuthMethod>,
+}
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::UserRole;
+
+/// Снимает флаг по умолчанию с остальных ролей и ставит выбранной - одной транзакцией
+pub struct SetDefaultRoleDescriptor {
+    user_id: Uuid,
+    role: String,
+}
+impl SetDefaultRoleDescriptor {
+    pub fn new(user_id: Uuid, role: String) -> Self {
+        Self { user_id, role }
+    }
+}
+
+impl ObjectGQLDescriptor for SetDefaultRoleDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "user_id": self.user_id, "role": self.role })
+    }
+}
+
+impl StaticGQLDescriptor for SetDefaultRoleDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_default_role.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpdateDefaultRole"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct SetDefaultRoleResponse {
+    pub assign: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<UserRole>,
+}
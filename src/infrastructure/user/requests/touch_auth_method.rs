@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+pub struct TouchAuthMethodDescriptor {
+    id: Uuid,
+    last_used_at: DateTime<Utc>,
+}
+impl TouchAuthMethodDescriptor {
+    pub fn new(id: Uuid, last_used_at: DateTime<Utc>) -> Self {
+        Self { id, last_used_at }
+    }
+}
+
+impl ObjectGQLDescriptor for TouchAuthMethodDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "id": self.id, "last_used_at": self.last_used_at })
+    }
+}
+
+impl StaticGQLDescriptor for TouchAuthMethodDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_auth_method_last_used.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "TouchUsersAuthMethod"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct TouchAuthMethodResponse {
+    pub update_users_auth_method_by_pk: Option<TouchedAuthMethod>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct TouchedAuthMethod {
+    pub id: Uuid,
+}
@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+pub struct UpdateAuthMethodSecretDescriptor {
+    id: Uuid,
+    secret: String,
+}
+impl UpdateAuthMethodSecretDescriptor {
+    pub fn new(id: Uuid, secret: String) -> Self {
+        Self { id, secret }
+    }
+}
+
+impl ObjectGQLDescriptor for UpdateAuthMethodSecretDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "id": self.id, "secret": self.secret })
+    }
+}
+
+impl StaticGQLDescriptor for UpdateAuthMethodSecretDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_auth_method_secret.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpdateUsersAuthMethodSecret"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpdateAuthMethodSecretResponse {
+    pub update_users_auth_method_by_pk: Option<UpdatedAuthMethod>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpdatedAuthMethod {
+    pub id: Uuid,
+}
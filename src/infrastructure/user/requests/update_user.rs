@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::status::{UserStatus, UserStatusChange};
+
+/// Меняет состояние пользователя; `disabled_at` заполняется только при отключении
+pub struct UpdateUserStatusDescriptor {
+    id: Uuid,
+    change: UserStatusChange,
+    now: DateTime<Utc>,
+}
+impl UpdateUserStatusDescriptor {
+    pub fn new(id: Uuid, change: UserStatusChange, now: DateTime<Utc>) -> Self {
+        Self { id, change, now }
+    }
+}
+
+impl ObjectGQLDescriptor for UpdateUserStatusDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        let disabled_at = (self.change.status == UserStatus::Disabled).then_some(self.now);
+        serde_json::json!({
+            "id": self.id,
+            "status": self.change.status.as_str(),
+            "status_reason": self.change.reason,
+            "locked_until": self.change.locked_until,
+            "disabled_at": disabled_at
+        })
+    }
+}
+
+impl StaticGQLDescriptor for UpdateUserStatusDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_user_status.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpdateUserStatus"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+pub struct UpdateUserLoggedOutDescriptor {
+    id: Uuid,
+    logged_out_at: DateTime<Utc>,
+}
+impl UpdateUserLoggedOutDescriptor {
+    pub fn new(id: Uuid, logged_out_at: DateTime<Utc>) -> Self {
+        Self { id, logged_out_at }
+    }
+}
+
+impl ObjectGQLDescriptor for UpdateUserLoggedOutDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({ "id": self.id, "logged_out_at": self.logged_out_at })
+    }
+}
+
+impl StaticGQLDescriptor for UpdateUserLoggedOutDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_user_logged_out.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpdateUserLoggedOut"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+/// Отключает пользователя до удаления: вход и refresh токены перестают работать
+pub struct ScheduleUserDeletionDescriptor {
+    id: Uuid,
+    delete_after: DateTime<Utc>,
+    requested_at: DateTime<Utc>,
+}
+impl ScheduleUserDeletionDescriptor {
+    pub fn new(id: Uuid, delete_after: DateTime<Utc>, requested_at: DateTime<Utc>) -> Self {
+        Self { id, delete_after, requested_at }
+    }
+}
+
+impl ObjectGQLDescriptor for ScheduleUserDeletionDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "status": UserStatus::Disabled.as_str(),
+            "delete_after": self.delete_after,
+            "requested_at": self.requested_at
+        })
+    }
+}
+
+impl StaticGQLDescriptor for ScheduleUserDeletionDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_user_delete_after.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "ScheduleUserDeletion"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpdateUserResponse {
+    pub update_users_user_by_pk: Option<UpdatedUser>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpdatedUser {
+    pub id: Uuid,
+}
@@ -0,0 +1,56 @@
+use uuid::Uuid;
+
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::UserAttribute;
+
+/// Заменяет значения атрибутов: старые значения удаляются в той же транзакции
+pub struct UpdateUserAttributeDescriptor {
+    user_id: Uuid,
+    attributes: Vec<UserAttribute>,
+}
+impl UpdateUserAttributeDescriptor {
+    pub fn new(user_id: Uuid, attributes: Vec<UserAttribute>) -> Self {
+        Self { user_id, attributes }
+    }
+}
+
+impl ObjectGQLDescriptor for UpdateUserAttributeDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        let attribute_names = self.attributes.iter().map(|v| v.attribute()).collect::<Vec<_>>();
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|v| serde_json::json!({ "user_id": self.user_id, "attribute": v.attribute(), "value": v.value() }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "user_id": self.user_id,
+            "attribute_names": attribute_names,
+            "attributes": attributes,
+        })
+    }
+}
+
+impl StaticGQLDescriptor for UpdateUserAttributeDescriptor {
+    fn filename(&self) -> &'static str {
+        "update_user_attribute.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpdateUserAttributes"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpdateUserAttributeResponse {
+    pub insert_users_user_attribute: Returning,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<UserAttribute>,
+}
@@ -0,0 +1,93 @@
+use super::super::network::hasura::interface::{ObjectGQLDescriptor, StaticGQLDescriptor};
+use super::gql_dir::GQL_DIR;
+
+use crate::domain::user::models::base::AuthMethod;
+use crate::domain::user::models::guest_upgrade::GuestUpgrade;
+
+/// Все изменения идут одним mutation-запросом, Hasura выполняет его в одной транзакции:
+/// при ошибке гость остаётся гостем
+pub struct UpgradeGuestDescriptor {
+    upgrade: GuestUpgrade,
+}
+impl UpgradeGuestDescriptor {
+    pub fn new(upgrade: GuestUpgrade) -> Self {
+        Self { upgrade }
+    }
+}
+
+impl ObjectGQLDescriptor for UpgradeGuestDescriptor {
+    fn variables(&self) -> serde_json::Value {
+        let user_id = self.upgrade.user_id();
+        let auth_methods = self
+            .upgrade
+            .auth_methods()
+            .iter()
+            .map(|v| serde_json::json!({
+                "user_id": user_id,
+                "auth_type": v.auth_type(),
+                "identifier": v.identifier(),
+                "secret": v.secret(),
+                "provider": v.provider(),
+                "name": v.name(),
+                "expires_at": v.expires_at(),
+                "roles": v.roles(),
+                "scopes": v.scopes(),
+                "signing_secret": v.signing_secret(),
+            }))
+            .collect::<Vec<_>>();
+        let attribute_names = self.upgrade.attributes().iter().map(|v| v.attribute()).collect::<Vec<_>>();
+        let attributes = self
+            .upgrade
+            .attributes()
+            .iter()
+            .map(|v| serde_json::json!({ "user_id": user_id, "attribute": v.attribute(), "value": v.value() }))
+            .collect::<Vec<_>>();
+        let roles = self
+            .upgrade
+            .roles()
+            .iter()
+            .map(|v| serde_json::json!({ "user_id": user_id, "role": v.role(), "is_default": v.is_default() }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "user_id": user_id,
+            "guest_auth_method_id": self.upgrade.guest_auth_method_id(),
+            "auth_methods": auth_methods,
+            "attribute_names": attribute_names,
+            "attributes": attributes,
+            "roles": roles,
+            "default_role": self.upgrade.default_role(),
+            "removed_roles": self.upgrade.removed_roles(),
+            "invite": self.upgrade.invite().clone().unwrap_or_default(),
+            "with_invite": self.upgrade.invite().is_some(),
+        })
+    }
+}
+
+impl StaticGQLDescriptor for UpgradeGuestDescriptor {
+    fn filename(&self) -> &'static str {
+        "upgrade_guest.graphql"
+    }
+    fn operation_name(&self) -> &'static str {
+        "UpgradeGuest"
+    }
+    fn path(&self) -> include_dir::Dir<'static> {
+        GQL_DIR.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct UpgradeGuestResponse {
+    pub insert_users_auth_method: Returning,
+    pub delete_users_auth_method: AffectedRows,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct Returning {
+    pub returning: Vec<AuthMethod>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
+pub struct AffectedRows {
+    pub affected_rows: i64,
+}
@@ -0,0 +1,45 @@
+use std::sync::{Arc, Mutex};
+
+use crate::domain::user::models::base::Attribute;
+
+/// Схема и момент (unix time), когда её нужно перечитать
+type CachedSchema = Option<(Vec<Attribute>, i64)>;
+
+/// Схема `users.attribute`, общая для всех `UserQuery` одного провайдера:
+/// use case создают свой `UserQuery`, а схема меняется редко
+#[derive(Debug, Clone, Default)]
+pub struct AttributeSchemaCache {
+    inner: Arc<Mutex<CachedSchema>>,
+}
+
+impl AttributeSchemaCache {
+    pub fn get(&self, now: i64) -> Option<Vec<Attribute>> {
+        let cache = self.inner.lock().ok()?;
+        match cache.as_ref() {
+            Some((schema, forget_at)) if *forget_at > now => Some(schema.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set(&self, schema: Vec<Attribute>, forget_at: i64) {
+        if let Ok(mut cache) = self.inner.lock() {
+            *cache = Some((schema, forget_at));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_schema_is_forgotten() {
+        let cache = AttributeSchemaCache::default();
+        assert!(cache.get(100).is_none());
+
+        cache.set(Vec::new(), 200);
+
+        assert_eq!(cache.clone().get(199), Some(Vec::new()));
+        assert!(cache.get(200).is_none());
+    }
+}
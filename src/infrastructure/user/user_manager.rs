@@ -14,8 +14,31 @@ use super::requests::add_user_attribute::{AddAttributesRequestDescriptor, AddAtt
 use super::requests::check_auth_method::{
     CheckAuthMethodRequestDescriptor, CheckAuthMethodResponse,
 };
+use super::requests::create_invite::{CreateInviteDescriptor, CreateInviteResponse};
+use super::requests::create_role::{CreateRoleDescriptor, CreateRoleResponse};
+use super::requests::create_user::{CreateUserDescriptor, CreateUserResponse};
+use super::requests::delete_user::{DeleteUserDescriptor, DeleteUserResponse};
+use super::requests::link_credentials::{LinkCredentialsDescriptor, LinkCredentialsResponse};
+use super::requests::merge_users::{MergeUsersDescriptor, MergeUsersResponse};
+use super::requests::remove_role::{RemoveRoleDescriptor, RemoveRoleResponse};
+use super::requests::set_default_role::{SetDefaultRoleDescriptor, SetDefaultRoleResponse};
+use super::requests::update_user::{
+    ScheduleUserDeletionDescriptor, UpdateUserLoggedOutDescriptor, UpdateUserStatusDescriptor, UpdateUserResponse,
+};
+use super::requests::remove_auth_method::{RemoveAuthMethodDescriptor, RemoveAuthMethodResponse};
+use super::requests::revoke_auth_method::{RevokeAuthMethodDescriptor, RevokeAuthMethodResponse};
+use super::requests::touch_auth_method::{TouchAuthMethodDescriptor, TouchAuthMethodResponse};
+use super::requests::update_auth_method_secret::{UpdateAuthMethodSecretDescriptor, UpdateAuthMethodSecretResponse};
+use super::requests::update_user_attribute::{UpdateUserAttributeDescriptor, UpdateUserAttributeResponse};
+use super::requests::upgrade_guest::{UpgradeGuestDescriptor, UpgradeGuestResponse};
 
 use crate::domain::user::models::base::{AuthMethod, User, UserAttribute, UserRole};
+use crate::domain::user::models::guest_upgrade::GuestUpgrade;
+use crate::domain::user::models::invite::Invite;
+use crate::domain::user::models::merge::UserMerge;
+use crate::domain::user::models::new_user::NewUser;
+use crate::domain::user::models::status::UserStatusChange;
+use chrono::{DateTime, Utc};
 
 pub struct UserCommand<T: HttpClientInterface> {
     credentials: Credentials,
@@ -90,6 +113,26 @@ impl <T: HttpClientInterface + Clone> CommandUserService for UserCommand <T> {
             None => Err(UserManagerError::FailedCreateUser),
         }
     }
+
+    async fn create_user_with(&self, new_user: NewUser) -> Result<ExtendedAuthMethod, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = CreateUserDescriptor::new(new_user);
+
+        let result = client
+            .execute::<CreateUserDescriptor, CreateUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        let Some(created) = result.insert_users_user_one else {
+            return Err(UserManagerError::FailedCreateUser);
+        };
+        match created.auth_methods.first() {
+            Some(auth_method) => Ok(ExtendedAuthMethod::new(auth_method.clone(), created.user)),
+            None => Err(UserManagerError::FailedCreateUser),
+        }
+    }
+
     async fn add_user_attribute(
         &self,
         attributes: Vec<UserAttribute>,
@@ -105,25 +148,290 @@ impl <T: HttpClientInterface + Clone> CommandUserService for UserCommand <T> {
 
         Ok(result.insert_users_user_attribute.returning)
     }
+
+    async fn update_user_attributes(&self, user_id: uuid::Uuid, attributes: Vec<UserAttribute>) -> Result<Vec<UserAttribute>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let expected = attributes.len();
+        let descriptor = UpdateUserAttributeDescriptor::new(user_id, attributes);
+
+        let result = client
+            .execute::<UpdateUserAttributeDescriptor, UpdateUserAttributeResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        let updated = result.insert_users_user_attribute.returning;
+        match updated.len() == expected {
+            true => Ok(updated),
+            false => Err(UserManagerError::FailedUpdateAttribute),
+        }
+    }
+
+    async fn create_role(&self, role: Role) -> Result<Role, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = CreateRoleDescriptor::new(role);
+
+        let result = client
+            .execute::<CreateRoleDescriptor, CreateRoleResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        result.insert_users_role_one.ok_or(UserManagerError::FailedCreateRole)
+    }
+
+    async fn create_invite(&self, invite: Invite) -> Result<Invite, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = CreateInviteDescriptor::new(invite);
+
+        let result = client
+            .execute::<CreateInviteDescriptor, CreateInviteResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        result.insert_users_invite_one.ok_or(UserManagerError::FailedCreateInvite)
+    }
+
+    async fn remove_role(&self, user_id: uuid::Uuid, role: &str) -> Result<Option<UserRole>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = RemoveRoleDescriptor::new(user_id, role.to_owned());
+
+        let result = client
+            .execute::<RemoveRoleDescriptor, RemoveRoleResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.delete_users_user_role.returning.first().cloned())
+    }
+
+    async fn set_default_role(&self, user_id: uuid::Uuid, role: &str) -> Result<Option<UserRole>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = SetDefaultRoleDescriptor::new(user_id, role.to_owned());
+
+        let result = client
+            .execute::<SetDefaultRoleDescriptor, SetDefaultRoleResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.assign.returning.first().cloned())
+    }
+
+    async fn set_user_status(&self, user_id: uuid::Uuid, change: UserStatusChange) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = UpdateUserStatusDescriptor::new(user_id, change, Utc::now());
+
+        let result = client
+            .execute::<UpdateUserStatusDescriptor, UpdateUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        result.update_users_user_by_pk.map(|_| ()).ok_or(UserManagerError::UserNotFound)
+    }
+
+    async fn logout_user(&self, user_id: uuid::Uuid, logged_out_at: DateTime<Utc>) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = UpdateUserLoggedOutDescriptor::new(user_id, logged_out_at);
+
+        let result = client
+            .execute::<UpdateUserLoggedOutDescriptor, UpdateUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        result.update_users_user_by_pk.map(|_| ()).ok_or(UserManagerError::UserNotFound)
+    }
+
+    async fn remove_auth_methods(&self, ids: Vec<uuid::Uuid>, user_id: uuid::Uuid) -> Result<Vec<AuthMethod>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = RemoveAuthMethodDescriptor::new(ids, user_id);
+
+        let result = client
+            .execute::<RemoveAuthMethodDescriptor, RemoveAuthMethodResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.delete_users_auth_method.returning)
+    }
+
+    async fn revoke_auth_method(&self, identifier: &str, auth_type: &str, user_id: uuid::Uuid) -> Result<Option<AuthMethod>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = RevokeAuthMethodDescriptor::new(
+            identifier.to_owned(),
+            auth_type.to_owned(),
+            user_id,
+            chrono::Utc::now(),
+        );
+
+        let result = client
+            .execute::<RevokeAuthMethodDescriptor, RevokeAuthMethodResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.update_users_auth_method.returning.first().cloned())
+    }
+
+    async fn touch_auth_method(&self, id: uuid::Uuid) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = TouchAuthMethodDescriptor::new(id, chrono::Utc::now());
+
+        let result = client
+            .execute::<TouchAuthMethodDescriptor, TouchAuthMethodResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        match result.update_users_auth_method_by_pk {
+            Some(_) => Ok(()),
+            None => Err(UserManagerError::UserNotFound),
+        }
+    }
+
+    async fn update_auth_method_secret(&self, id: uuid::Uuid, secret: String) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = UpdateAuthMethodSecretDescriptor::new(id, secret);
+
+        let result = client
+            .execute::<UpdateAuthMethodSecretDescriptor, UpdateAuthMethodSecretResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        match result.update_users_auth_method_by_pk {
+            Some(_) => Ok(()),
+            None => Err(UserManagerError::UserNotFound),
+        }
+    }
+
+    async fn merge_users(&self, merge: UserMerge) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = MergeUsersDescriptor::new(merge, chrono::Utc::now());
+
+        let result = client
+            .execute::<MergeUsersDescriptor, MergeUsersResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        match result.update_users_user_by_pk {
+            Some(_) => Ok(()),
+            None => Err(UserManagerError::FailedMergeUsers),
+        }
+    }
+
+    async fn schedule_user_deletion(
+        &self,
+        user_id: uuid::Uuid,
+        delete_after: DateTime<Utc>,
+        requested_at: DateTime<Utc>,
+    ) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = ScheduleUserDeletionDescriptor::new(user_id, delete_after, requested_at);
+
+        let result = client
+            .execute::<ScheduleUserDeletionDescriptor, UpdateUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        result.update_users_user_by_pk.map(|_| ()).ok_or(UserManagerError::UserNotFound)
+    }
+
+    async fn delete_user(&self, user_id: uuid::Uuid, deleted_at: DateTime<Utc>) -> Result<(), Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = DeleteUserDescriptor::new(user_id, deleted_at);
+
+        let result = client
+            .execute::<DeleteUserDescriptor, DeleteUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        match result.update_users_user_by_pk {
+            Some(_) => Ok(()),
+            None => Err(UserManagerError::FailedDeleteUser),
+        }
+    }
+
+    async fn upgrade_guest(&self, upgrade: GuestUpgrade) -> Result<Vec<AuthMethod>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = UpgradeGuestDescriptor::new(upgrade);
+
+        let result = client
+            .execute::<UpgradeGuestDescriptor, UpgradeGuestResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        match result.delete_users_auth_method.affected_rows {
+            0 => Err(UserManagerError::FailedUpgradeGuest),
+            _ => Ok(result.insert_users_auth_method.returning),
+        }
+    }
+
+    async fn link_credentials(&self, user_id: uuid::Uuid, credentials: NewUser) -> Result<Vec<AuthMethod>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = LinkCredentialsDescriptor::new(user_id, credentials);
+
+        let result = client
+            .execute::<LinkCredentialsDescriptor, LinkCredentialsResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.insert_users_auth_method.returning)
+    }
 }
 
-use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::user::models::extended::{ExtendedAuthMethod, ExtendedUser};
+use crate::domain::user::models::page::{UserFilter, UserPage};
+use crate::domain::user::models::role::Role;
+use crate::domain::user::models::base::Attribute;
 
+use super::schema_cache::AttributeSchemaCache;
+use super::requests::check_attribute_value::{
+    CheckAttributeValueRequestDescriptor, CheckAttributeValueResponse,
+};
+use super::requests::get_attribute_schema::{
+    GetAttributeSchemaRequestDescriptor, GetAttributeSchemaResponse,
+};
+use super::requests::get_invite::{GetInviteRequestDescriptor, GetInviteResponse};
+use super::requests::get_user::{GetUserRequestDescriptor, GetUserResponse};
+use super::requests::count_role_users::{CountRoleUsersRequestDescriptor, CountRoleUsersResponse};
+use super::requests::list_invites::{ListInvitesRequestDescriptor, ListInvitesResponse};
+use super::requests::list_users_to_delete::{ListUsersToDeleteRequestDescriptor, ListUsersToDeleteResponse};
+use super::requests::list_roles::{ListRolesRequestDescriptor, ListRolesResponse};
+use super::requests::list_users::{ListUsersRequestDescriptor, ListUsersResponse};
 use super::requests::get_user_by_id::{
     GetUserByByUserIdResponse, GetUserByUserIdRequestDescriptor,
 };
 use super::requests::get_user_by_identifier::{
     GetUserByByIdentifierResponse, GetUserByIdentifierRequestDescriptor,
 };
+use super::requests::get_user_by_provider::{
+    GetUserByProviderRequestDescriptor, GetUserByProviderResponse,
+};
 
 pub struct UserQuery<T: HttpClientInterface> {
     credentials: Credentials,
-    hasura_client: HasuraClient<T>
+    hasura_client: HasuraClient<T>,
+    attribute_schema: AttributeSchemaCache,
 }
 
 impl <T: HttpClientInterface + Clone> UserQuery<T> {
     pub fn new(credentials: Credentials, hasura_client: HasuraClient<T>) -> Self {
-        Self { credentials,  hasura_client}
+        Self { credentials,  hasura_client, attribute_schema: AttributeSchemaCache::default() }
+    }
+
+    /// Общий кеш схемы атрибутов, см. `AttributeSchemaCache`
+    pub fn with_attribute_schema_cache(mut self, cache: AttributeSchemaCache) -> Self {
+        self.attribute_schema = cache;
+        self
     }
 }
 
@@ -152,6 +460,32 @@ impl <T: HttpClientInterface + Clone> QueryUserService for UserQuery<T> {
             .and_then(|v| Some(v.clone())))
     }
 
+    async fn get_user_by_provider_identifier(
+        &self,
+        identifier: &str,
+        auth_type: &str,
+        provider: &str,
+        with_unassigned: bool,
+    ) -> Result<Option<ExtendedAuthMethod>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+
+        let descriptor = GetUserByProviderRequestDescriptor::new(
+            identifier.to_owned(),
+            auth_type.to_owned(),
+            provider.to_owned(),
+            with_unassigned,
+        );
+
+        let result = client
+            .execute::<GetUserByProviderRequestDescriptor, GetUserByProviderResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_auth_method.first()
+            .or(result.unassigned_auth_method.first())
+            .cloned())
+    }
+
     async fn get_user_by_id(
         &self,
         id: uuid::Uuid,
@@ -166,6 +500,128 @@ impl <T: HttpClientInterface + Clone> QueryUserService for UserQuery<T> {
 
         Ok(result.users_auth_method)
     }
+
+    async fn get_user(&self, id: uuid::Uuid) -> Result<Option<ExtendedUser>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = GetUserRequestDescriptor::new(id);
+
+        let result = client
+            .execute::<GetUserRequestDescriptor, GetUserResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_user_by_pk)
+    }
+
+    async fn list_users(&self, filter: UserFilter) -> Result<UserPage, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = ListUsersRequestDescriptor::new(filter);
+
+        let result = client
+            .execute::<ListUsersRequestDescriptor, ListUsersResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(UserPage::new(result.users_user, result.users_user_aggregate.aggregate.count))
+    }
+
+    async fn list_roles(&self) -> Result<Vec<Role>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = ListRolesRequestDescriptor;
+
+        let result = client
+            .execute::<ListRolesRequestDescriptor, ListRolesResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_role)
+    }
+
+    async fn count_role_users(&self, role: &str) -> Result<i64, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = CountRoleUsersRequestDescriptor::new(role.to_owned());
+
+        let result = client
+            .execute::<CountRoleUsersRequestDescriptor, CountRoleUsersResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_user_role_aggregate.aggregate.count)
+    }
+
+    async fn get_attribute_schema(&self) -> Result<Vec<Attribute>, Self::Error> {
+        let now = Utc::now().timestamp();
+        if let Some(schema) = self.attribute_schema.get(now) {
+            return Ok(schema);
+        }
+
+        let mut client = self.hasura_client.clone();
+        let descriptor = GetAttributeSchemaRequestDescriptor;
+
+        let result = client
+            .execute::<GetAttributeSchemaRequestDescriptor, GetAttributeSchemaResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        let ttl = *self.credentials.attribute_schema_cache_ttl();
+        if ttl > 0 {
+            self.attribute_schema.set(result.users_attribute.clone(), now + ttl);
+        }
+        Ok(result.users_attribute)
+    }
+
+    async fn attribute_value_is_free(
+        &self,
+        attribute: &str,
+        value: &str,
+        except_user: Option<uuid::Uuid>,
+    ) -> Result<bool, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = CheckAttributeValueRequestDescriptor::new(attribute.to_owned(), value.to_owned(), except_user);
+
+        let result = client
+            .execute::<CheckAttributeValueRequestDescriptor, CheckAttributeValueResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_user_attribute_aggregate.aggregate.count == 0)
+    }
+
+    async fn get_invite(&self, code: &str) -> Result<Option<Invite>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = GetInviteRequestDescriptor::new(code.to_owned());
+
+        let result = client
+            .execute::<GetInviteRequestDescriptor, GetInviteResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_invite.first().cloned())
+    }
+
+    async fn list_invites(&self) -> Result<Vec<Invite>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = ListInvitesRequestDescriptor;
+
+        let result = client
+            .execute::<ListInvitesRequestDescriptor, ListInvitesResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_invite)
+    }
+
+    async fn list_users_to_delete(&self, now: DateTime<Utc>) -> Result<Vec<uuid::Uuid>, Self::Error> {
+        let mut client = self.hasura_client.clone();
+        let descriptor = ListUsersToDeleteRequestDescriptor::new(now);
+
+        let result = client
+            .execute::<ListUsersToDeleteRequestDescriptor, ListUsersToDeleteResponse>(&descriptor)
+            .await
+            .map_err(|e| UserManagerError::HasuraClientError(e))?;
+
+        Ok(result.users_user.into_iter().map(|v| v.id).collect())
+    }
 }
 
 
@@ -257,6 +713,31 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn user_command_create_user_with() {
+        let http_client = create_mock_http_client("InsertUserWith".to_owned(), "insert_user_with.json");
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let user_command = UserCommand::new(mock_credentials(), hasura_client);
+        let new_user = NewUser::new(
+            vec![AuthMethod::new(NewUser::PENDING_ID, "email".to_string(), "test@test.com".to_string(), None)],
+            vec![UserAttribute::new(NewUser::PENDING_ID, "email".to_string(), "test@test.com".to_string())],
+            vec![UserRole::new(true, "user".to_string(), NewUser::PENDING_ID)],
+        );
+
+        let auth_method = user_command.create_user_with(new_user).await.unwrap();
+
+        assert_eq!(auth_method.identifier(), "test@test.com");
+        assert_eq!(auth_method.user_id(), auth_method.user().id());
+        assert_eq!(auth_method.user().user_roles().len(), 1);
+        assert_eq!(auth_method.user().user_attributes().len(), 2);
+
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        let object = &body["variables"]["object"];
+        assert_eq!(object["user_roles"]["data"][0]["role"], "user");
+        assert!(object["auth_methods"]["data"][0].get("user_id").is_none());
+    }
+
     #[tokio::test]
     async fn user_command_add_user_attribute(){
         let query_name = "InsertMultipleAttributes";
@@ -279,6 +760,157 @@ mod tests {
 
     }
 
+    #[tokio::test]
+    async fn user_command_remove_auth_methods() {
+        let query_name = "DeleteUsersAuthMethod";
+        let response_file = "delete_user_auth_method.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials, hasura_client);
+        let ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let user_id = Uuid::new_v4();
+
+        let result = user_command.remove_auth_methods(ids.clone(), user_id).await;
+
+        assert_eq!(result.unwrap().len(), 2);
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["user_id"], user_id.to_string());
+        assert_eq!(body["variables"]["ids"], serde_json::json!(ids));
+    }
+
+    #[tokio::test]
+    async fn user_command_revoke_auth_method() {
+        let query_name = "RevokeUsersAuthMethod";
+        let response_file = "revoke_user_auth_method.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials, hasura_client);
+
+        let result = user_command.revoke_auth_method("FNGFb2Px6cox1wvR98KmU6Fl8IXkBU1x", "apikey", Uuid::new_v4()).await;
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().unwrap().revoked_at().is_some());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["auth_type"], "apikey");
+    }
+
+    #[tokio::test]
+    async fn user_command_update_auth_method_secret() {
+        let query_name = "UpdateUsersAuthMethodSecret";
+        let response_file = "update_auth_method_secret.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials, hasura_client);
+
+        let result = user_command.update_auth_method_secret(Uuid::new_v4(), "hmac-sha256$00".to_string()).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["secret"], "hmac-sha256$00");
+    }
+
+    #[tokio::test]
+    async fn user_command_update_user_attributes() {
+        let query_name = "UpdateUserAttributes";
+        let response_file = "update_user_attribute.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials, hasura_client);
+        let user_id = Uuid::new_v4();
+        let attribute = UserAttribute::new(user_id, "locale".to_string(), "ru".to_string());
+
+        let result = user_command.update_user_attributes(user_id, vec![attribute]).await;
+
+        assert_eq!(result.unwrap()[0].value(), "ru");
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["attribute_names"], serde_json::json!(["locale"]));
+        assert_eq!(body["variables"]["attributes"][0]["user_id"], user_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn user_command_merge_users() {
+        let query_name = "MergeUsers";
+        let response_file = "merge_users.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client.clone()));
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials.clone(), hasura_client);
+
+        let primary = create_mock_http_client("GetAuthMethodByUserId".to_owned(), "query_auth_methods_email.json");
+        let primary = UserQuery::new(credentials.clone(), HasuraClient::new(Box::new(primary)))
+            .get_user_by_id(Uuid::new_v4()).await.unwrap();
+        let secondary = create_mock_http_client("GetAuthMethodByUserId".to_owned(), "query_auth_methods_telegram.json");
+        let secondary = UserQuery::new(credentials, HasuraClient::new(Box::new(secondary)))
+            .get_user_by_id(Uuid::new_v4()).await.unwrap();
+        let merge = UserMerge::plan(primary[0].user(), secondary[0].user());
+
+        let result = user_command.merge_users(merge).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        // Роль "test" и атрибут "test" уже есть у основного пользователя
+        assert_eq!(body["variables"]["role_ids"], serde_json::json!(["d11bd045-a367-4683-9234-293580264e39"]));
+        assert_eq!(body["variables"]["attribute_ids"], serde_json::json!(["e11bd045-a367-4683-9234-293580264e39"]));
+        assert_eq!(body["variables"]["secondary_id"], "b01bd045-a367-4683-9234-293580264e39");
+    }
+
+    #[tokio::test]
+    async fn user_command_upgrade_guest() {
+        let http_client = create_mock_http_client("UpgradeGuest".to_owned(), "upgrade_guest.json");
+        let recorder = http_client.recorder();
+        let credentials = mock_credentials();
+        let user_command = UserCommand::new(credentials.clone(), HasuraClient::new(Box::new(http_client)));
+
+        let guest = create_mock_http_client("GetAuthMethodByUserId".to_owned(), "query_auth_methods_anonymous.json");
+        let guest = UserQuery::new(credentials.clone(), HasuraClient::new(Box::new(guest)))
+            .get_user_by_id(Uuid::new_v4()).await.unwrap();
+        let user_id = *guest[0].user_id();
+        let auth_method = AuthMethod::new(user_id, "email".to_string(), "guest@test.com".to_string(), None);
+        let roles = vec![UserRole::new(true, "default".to_string(), user_id)];
+        let new_user = NewUser::new(vec![auth_method], Vec::new(), roles);
+        let upgrade = GuestUpgrade::plan(&guest[0], new_user, credentials.guest_role().as_ref()).unwrap();
+
+        let result = user_command.upgrade_guest(upgrade).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        let variables = &body["variables"];
+        assert_eq!(variables["guest_auth_method_id"], "a01bd045-a367-4683-9234-297586264e39");
+        assert_eq!(variables["auth_methods"][0]["user_id"], user_id.to_string());
+        assert_eq!(variables["roles"], serde_json::json!([{ "user_id": user_id, "role": "default", "is_default": false }]));
+        assert_eq!(variables["default_role"], "default");
+        assert_eq!(variables["removed_roles"], serde_json::json!(["guest"]));
+    }
+
+    #[tokio::test]
+    async fn user_command_link_credentials() {
+        let http_client = create_mock_http_client("LinkCredentials".to_owned(), "link_credentials.json");
+        let recorder = http_client.recorder();
+        let user_command = UserCommand::new(mock_credentials(), HasuraClient::new(Box::new(http_client)));
+        let user_id = Uuid::new_v4();
+        let auth_method = AuthMethod::new(user_id, "telegram".to_string(), "42".to_string(), None);
+        let attribute = UserAttribute::new(user_id, "telegram_first_name".to_string(), "Mock".to_string());
+        let credentials = NewUser::new(vec![auth_method], vec![attribute], Vec::new());
+
+        let result = user_command.link_credentials(user_id, credentials).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        let variables = &body["variables"];
+        assert_eq!(variables["auth_methods"][0]["user_id"], user_id.to_string());
+        assert_eq!(variables["attribute_names"], serde_json::json!(["telegram_first_name"]));
+        assert_eq!(variables["roles"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn query_user_get_user_by_identifier (){
         let query_name = "GetAuthMethodByIdentifier";
@@ -296,6 +928,39 @@ mod tests {
     }
 
 
+    #[tokio::test]
+    async fn query_user_get_user_by_provider_identifier() {
+        let query_name = "GetAuthMethodByProvider";
+        let response_file = "query_auth_methods_email.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_query = UserQuery::new(credentials, hasura_client);
+
+        let result = user_query.get_user_by_provider_identifier("42", "telegram", "staging", false).await;
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_some());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["provider"], "staging");
+        assert_eq!(body["variables"]["with_unassigned"], false);
+    }
+
+    #[tokio::test]
+    async fn query_user_get_user_by_provider_identifier_without_provider() {
+        let query_name = "GetAuthMethodByProvider";
+        let response_file = "query_auth_methods_telegram_unassigned.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let credentials = mock_credentials();
+        let user_query = UserQuery::new(credentials, hasura_client);
+
+        let result = user_query.get_user_by_provider_identifier("42", "telegram", "TEST", true).await;
+
+        assert_eq!(result.unwrap().unwrap().identifier(), "42");
+    }
+
     #[tokio::test]
     async fn query_user_get_user_by_id (){
         let query_name = "GetAuthMethodByUserId";
@@ -312,7 +977,88 @@ mod tests {
         assert!(result.unwrap().first().is_some());
     }
 
+    #[tokio::test]
+    async fn user_command_set_default_role() {
+        let query_name = "UpdateDefaultRole";
+        let response_file = "update_default_role.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let user_command = UserCommand::new(mock_credentials(), hasura_client);
+
+        let result = user_command.set_default_role(Uuid::new_v4(), "user").await;
+
+        assert!(*result.unwrap().unwrap().is_default());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["role"], "user");
+    }
+
+    #[tokio::test]
+    async fn user_command_set_user_status() {
+        let query_name = "UpdateUserStatus";
+        let response_file = "update_user.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let user_command = UserCommand::new(mock_credentials(), hasura_client);
+
+        let result = user_command.set_user_status(Uuid::new_v4(), UserStatusChange::disabled(Some("spam".to_string()))).await;
+
+        assert!(result.is_ok());
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        assert_eq!(body["variables"]["status"], "disabled");
+        assert_eq!(body["variables"]["status_reason"], "spam");
+        assert!(body["variables"]["disabled_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn query_user_get_user() {
+        let query_name = "GetUserById";
+        let response_file = "query_user_by_id.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let user_query = UserQuery::new(mock_credentials(), hasura_client);
+
+        let result = user_query.get_user(Uuid::new_v4()).await;
 
+        assert_eq!(result.unwrap().unwrap().user_roles().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn query_user_attribute_schema_is_cached() {
+        let http_client = create_mock_http_client("GetAttribute".to_owned(), "query_attribute.json");
+        let cache = AttributeSchemaCache::default();
+        let user_query = UserQuery::new(mock_credentials(), HasuraClient::new(Box::new(http_client)))
+            .with_attribute_schema_cache(cache.clone());
 
-            
-}
\ No newline at end of file
+        let schema = user_query.get_attribute_schema().await.unwrap();
+        assert_eq!(schema.len(), 9);
+
+        // Второй UserQuery без ответа на GetAttribute берёт схему из общего кеша
+        let http_client = MockHttpClient::new(ResponseMode::File);
+        let user_query = UserQuery::new(mock_credentials(), HasuraClient::new(Box::new(http_client)))
+            .with_attribute_schema_cache(cache);
+
+        assert_eq!(user_query.get_attribute_schema().await.unwrap(), schema);
+    }
+
+    #[tokio::test]
+    async fn query_user_list_users() {
+        let query_name = "ListUsers";
+        let response_file = "query_users.json";
+        let http_client = create_mock_http_client(query_name.to_owned(), response_file);
+        let recorder = http_client.recorder();
+        let hasura_client = HasuraClient::new(Box::new(http_client));
+        let user_query = UserQuery::new(mock_credentials(), hasura_client);
+        let filter = UserFilter { search: Some("te_st".to_string()), limit: 2, offset: 0 };
+
+        let page = user_query.list_users(filter).await.unwrap();
+
+        assert_eq!(page.users().len(), 2);
+        assert_eq!(*page.total(), 12);
+        let body: serde_json::Value = serde_json::from_str(&recorder.read_data().await.unwrap()).unwrap();
+        let filter = &body["variables"]["where"];
+        assert_eq!(filter["deleted_at"]["_is_null"], true);
+        assert_eq!(filter["_or"][0]["auth_methods"]["identifier"]["_ilike"], "%te\\_st%");
+    }
+}
@@ -1,18 +1,64 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
 use super::errors::ApiKeyVerifierError;
+use super::request_signature;
 use crate::domain::settings::model::Credentials;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::verifies::model::{RequestSignature, SignedRequest};
 use crate::domain::verifies::service::ApiKeyVerifierService;
 use rand::{rngs::OsRng, TryRngCore};
+use uuid::Uuid;
 
 const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
 const BASE: usize = 62;
+/// u32 в base62 занимает не больше 6 символов
+const CHECKSUM_LENGTH: usize = 6;
+const SEPARATOR: char = '_';
+/// Разделитель ключей старого формата `<identifier>-<secret>`
+const LEGACY_SEPARATOR: char = '-';
+/// Ключи случайные и длинные, поэтому медленный bcrypt им не нужен
+const HMAC_HASH_PREFIX: &str = "hmac-sha256$";
+
+/// sha256(api_key) -> (способ входа, до какого момента верить)
+pub type ApiKeyCache = Arc<Mutex<HashMap<String, (ExtendedAuthMethod, i64)>>>;
 
 pub struct ApiKeyVerifier {
     pub credentials: Credentials,
+    cache: ApiKeyCache,
 }
 
 impl ApiKeyVerifier {
     pub fn new(credentials: Credentials) -> Self {
-        Self { credentials }
+        Self { credentials, cache: ApiKeyCache::default() }
+    }
+
+    /// Общий кэш: `forget_user` в одном use case сбрасывает ключи во всех
+    pub fn with_cache(mut self, cache: ApiKeyCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    fn mac(&self, secret: &str, api_key: &str) -> Hmac<Sha256> {
+        // HMAC принимает ключ любой длины
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(api_key.as_bytes());
+        mac
+    }
+
+    fn hash_secret(&self) -> Result<&str, ApiKeyVerifierError> {
+        self.credentials
+            .api_key_hash_secret()
+            .as_deref()
+            .ok_or_else(|| ApiKeyVerifierError::EncryptionError("api_key_hash_secret is not set".to_string()))
+    }
+
+    /// Сам ключ в памяти не держим
+    fn cache_key(&self, api_key: &str) -> String {
+        hex::encode(Sha256::digest(api_key.as_bytes()))
     }
 
     fn bytes_to_base62(&self, mut bytes: Vec<u8>) -> String {
@@ -44,6 +90,25 @@ impl ApiKeyVerifier {
         random.chars().take(length).collect()
     }
 
+    /// CRC32 всего ключа до контрольной суммы, base62 фиксированной длины
+    fn checksum(&self, body: &str) -> String {
+        let crc = crc32fast::hash(body.as_bytes());
+        let encoded = self.bytes_to_base62(crc.to_be_bytes().to_vec());
+        // 'A' - ноль в CHARSET
+        format!("{:A>width$}", encoded, width = CHECKSUM_LENGTH)
+    }
+
+    fn extract_legacy_identifier(&self, api_key: &str) -> Result<String, ApiKeyVerifierError> {
+        let parts: Vec<&str> = api_key.split(LEGACY_SEPARATOR).collect();
+
+        if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
+            return Err(ApiKeyVerifierError::InvalidFormat(
+                "Token format invalid".to_string(),
+            ));
+        }
+
+        Ok(parts[0].to_string())
+    }
 }
 
 impl ApiKeyVerifierService for ApiKeyVerifier {
@@ -53,22 +118,46 @@ impl ApiKeyVerifierService for ApiKeyVerifier {
         let identifier = self.generate_random_str(*self.credentials.api_key_length() as usize);
         let secret = self.generate_random_str(*self.credentials.api_key_length() as usize);
 
-        format!("{}-{}", identifier, secret)
+        let body = format!(
+            "{}{}{}{}",
+            self.credentials.api_key_prefix(), identifier, SEPARATOR, secret
+        );
+        let checksum = self.checksum(&body);
+
+        format!("{}{}{}", body, SEPARATOR, checksum)
     }
 
+    /// Проверяет формат и контрольную сумму без обращения к БД
     fn extract_identifier(&self, api_key: &str) -> Result<String, Self::Error> {
-        let parts: Vec<&str> = api_key.split('-').collect();
+        let Some(rest) = api_key.strip_prefix(self.credentials.api_key_prefix().as_str()) else {
+            return self.extract_legacy_identifier(api_key);
+        };
 
-        if parts.len() != 2 || api_key == "-" {
-            return Err(ApiKeyVerifierError::DecryptionError(
-                "Token format invalid".to_string(),
-            ));
+        let Some((body, checksum)) = api_key.rsplit_once(SEPARATOR) else {
+            return Err(ApiKeyVerifierError::InvalidFormat("Token format invalid".to_string()));
+        };
+
+        let parts: Vec<&str> = rest.split(SEPARATOR).collect();
+        if parts.len() != 3 || parts.iter().any(|v| v.is_empty()) {
+            return Err(ApiKeyVerifierError::InvalidFormat("Token format invalid".to_string()));
+        }
+
+        if self.checksum(body) != checksum {
+            return Err(ApiKeyVerifierError::ChecksumMismatch);
         }
 
         Ok(parts[0].to_string())
     }
 
     fn is_verified(&self, api_key_hash: &str, api_key: &str) -> Result<bool, Self::Error> {
+        if let Some(expected) = api_key_hash.strip_prefix(HMAC_HASH_PREFIX) {
+            let Ok(expected) = hex::decode(expected) else {
+                return Ok(false);
+            };
+            // verify_slice сравнивает за постоянное время
+            return Ok(self.mac(self.hash_secret()?, api_key).verify_slice(&expected).is_ok());
+        }
+
         bcrypt::verify(api_key, api_key_hash).map_err(|e| {
             ApiKeyVerifierError::HashPasswordCryptError {
                 stage: "bcrypt::verify",
@@ -78,6 +167,11 @@ impl ApiKeyVerifierService for ApiKeyVerifier {
     }
 
     fn create_hash(&self, api_key: &str) -> Result<String, Self::Error> {
+        if let Some(secret) = self.credentials.api_key_hash_secret() {
+            let digest = self.mac(secret, api_key).finalize().into_bytes();
+            return Ok(format!("{}{}", HMAC_HASH_PREFIX, hex::encode(digest)));
+        }
+
         bcrypt::hash(api_key, bcrypt::DEFAULT_COST).map_err(|e| {
             ApiKeyVerifierError::HashPasswordCryptError {
                 stage: "bcrypt::hash",
@@ -85,6 +179,69 @@ impl ApiKeyVerifierService for ApiKeyVerifier {
             }
         })
     }
+
+    fn needs_rehash(&self, api_key_hash: &str) -> bool {
+        self.credentials.api_key_hash_secret().is_some() && !api_key_hash.starts_with(HMAC_HASH_PREFIX)
+    }
+
+    fn cached(&self, api_key: &str) -> Option<ExtendedAuthMethod> {
+        let now = chrono::Utc::now().timestamp();
+        let mut cache = self.cache.lock().ok()?;
+
+        cache.retain(|_, (_, forget_at)| *forget_at > now);
+        cache.get(&self.cache_key(api_key)).map(|(v, _)| v.clone())
+    }
+
+    fn remember(&self, api_key: &str, auth_method: &ExtendedAuthMethod) {
+        let ttl = *self.credentials.api_key_cache_ttl();
+        if ttl <= 0 {
+            return;
+        }
+        let forget_at = chrono::Utc::now().timestamp() + ttl;
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(self.cache_key(api_key), (auth_method.clone(), forget_at));
+        }
+    }
+
+    fn forget_user(&self, user_id: Uuid) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.retain(|_, (auth_method, _)| *auth_method.user_id() != user_id);
+        }
+    }
+
+    fn signing_secret(&self, api_key: &str) -> Result<Option<String>, Self::Error> {
+        let Some(encryption_key) = self.credentials.request_signing_key() else {
+            return Ok(None);
+        };
+        let signing_key = request_signature::signing_key(api_key);
+        request_signature::encrypt(encryption_key, &signing_key).map(Some)
+    }
+
+    fn parse_signature(&self, authorization: &str) -> Result<RequestSignature, Self::Error> {
+        request_signature::parse(authorization)
+    }
+
+    fn is_signature_verified(
+        &self,
+        signing_secret: &str,
+        signature: &RequestSignature,
+        request: &SignedRequest,
+    ) -> Result<bool, Self::Error> {
+        let encryption_key = self
+            .credentials
+            .request_signing_key()
+            .as_deref()
+            .ok_or_else(|| ApiKeyVerifierError::EncryptionError("request_signing_key is not set".to_string()))?;
+        let signing_key = request_signature::decrypt(encryption_key, signing_secret)?;
+
+        request_signature::verify(
+            &signing_key,
+            signature,
+            request,
+            *self.credentials.request_signature_max_skew(),
+            chrono::Utc::now().timestamp(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -148,8 +305,10 @@ mod tests {
         let api_key = verifier.generate();
         println!("Generated API key: {}", api_key);
 
-        let parts: Vec<&str> = api_key.split('-').collect();
-        assert_eq!(parts.len(), 2, "API key should have 2 parts separated by '-'");
+        let rest = api_key.strip_prefix(credentials.api_key_prefix().as_str()).unwrap();
+        let parts: Vec<&str> = rest.split('_').collect();
+        assert_eq!(parts.len(), 3, "API key should have 3 parts separated by '_'");
+        assert_eq!(parts[2].len(), CHECKSUM_LENGTH);
 
         let expected_len = *credentials.api_key_length() as usize;
         assert_eq!(
@@ -217,4 +376,154 @@ mod tests {
         assert!(valid);
     }
 
+    #[test]
+    fn test_extract_identifier_generated_key() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+
+        let api_key = verifier.generate();
+        let identifier = verifier.extract_identifier(&api_key).unwrap();
+
+        assert_eq!(identifier.len(), *mock_credentials().api_key_length() as usize);
+        assert!(api_key.starts_with(&format!("awr_test_{}_", identifier)));
+    }
+
+    #[test]
+    fn test_extract_identifier_typo_is_rejected() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+
+        let api_key = verifier.generate();
+        let (body, checksum) = api_key.rsplit_once('_').unwrap();
+        let secret_char = body.chars().last().unwrap();
+        let typo = if secret_char == 'A' { 'B' } else { 'A' };
+        let broken = format!("{}{}_{}", &body[..body.len() - 1], typo, checksum);
+
+        let result = verifier.extract_identifier(&broken);
+        assert!(matches!(result, Err(ApiKeyVerifierError::ChecksumMismatch)));
+    }
+
+    #[test]
+    fn test_extract_identifier_missing_checksum() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+
+        let result = verifier.extract_identifier("awr_test_ABCDEF_SECRET");
+        assert!(matches!(result, Err(ApiKeyVerifierError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_checksum_known_input() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+
+        // crc32("awr_test_ABC_DEF") = 0xb03f48be
+        assert_eq!(verifier.checksum("awr_test_ABC_DEF"), "DOHAey");
+        assert_eq!(verifier.checksum(""), "AAAAAA");
+    }
+
+    fn credentials_with(patch: serde_json::Value) -> Credentials {
+        let mut value = serde_json::to_value(mock_credentials()).unwrap();
+        for (k, v) in patch.as_object().unwrap() {
+            value[k] = v.clone();
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_hmac_hash_format() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+
+        let hash = verifier.create_hash("TestApiKey123").unwrap();
+
+        assert!(hash.starts_with(HMAC_HASH_PREFIX));
+        assert_eq!(hash, verifier.create_hash("TestApiKey123").unwrap());
+        assert!(!verifier.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn test_bcrypt_hash_is_verified_and_needs_rehash() {
+        let bcrypt_verifier = ApiKeyVerifier::new(credentials_with(serde_json::json!({"api_key_hash_secret": null})));
+        let hash = bcrypt_verifier.create_hash("TestApiKey123").unwrap();
+        assert!(hash.starts_with("$2"));
+        assert!(!bcrypt_verifier.needs_rehash(&hash));
+
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+        assert!(verifier.is_verified(&hash, "TestApiKey123").unwrap());
+        assert!(verifier.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn test_hmac_hash_with_other_secret_is_rejected() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+        let hash = verifier.create_hash("TestApiKey123").unwrap();
+
+        let other = ApiKeyVerifier::new(credentials_with(serde_json::json!({"api_key_hash_secret": "OTHER"})));
+        assert!(!other.is_verified(&hash, "TestApiKey123").unwrap());
+    }
+
+    #[test]
+    fn test_remembered_key_is_cached() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+        let auth_method = ExtendedAuthMethod::mock();
+
+        assert!(verifier.cached("TestApiKey123").is_none());
+        verifier.remember("TestApiKey123", &auth_method);
+
+        assert_eq!(verifier.cached("TestApiKey123"), Some(auth_method));
+        assert!(verifier.cached("OtherApiKey").is_none());
+    }
+
+    #[test]
+    fn test_forgotten_user_is_not_cached() {
+        let cache = ApiKeyCache::default();
+        let verifier = ApiKeyVerifier::new(mock_credentials()).with_cache(cache.clone());
+        let other = ApiKeyVerifier::new(mock_credentials()).with_cache(cache);
+        let auth_method = ExtendedAuthMethod::mock();
+        verifier.remember("TestApiKey123", &auth_method);
+        verifier.remember("OtherApiKey", &ExtendedAuthMethod::mock());
+
+        other.forget_user(*auth_method.user_id());
+
+        assert!(verifier.cached("TestApiKey123").is_none());
+        assert!(verifier.cached("OtherApiKey").is_some());
+    }
+
+    #[test]
+    fn test_cache_disabled() {
+        let verifier = ApiKeyVerifier::new(credentials_with(serde_json::json!({"api_key_cache_ttl": 0})));
+
+        verifier.remember("TestApiKey123", &ExtendedAuthMethod::mock());
+
+        assert!(verifier.cached("TestApiKey123").is_none());
+    }
+
+    /// cargo test --release bench_hash_throughput -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_hash_throughput() {
+        let verifier = ApiKeyVerifier::new(mock_credentials());
+        let api_key = verifier.generate();
+        let hmac_hash = verifier.create_hash(&api_key).unwrap();
+        let bcrypt_hash = bcrypt::hash(&api_key, bcrypt::DEFAULT_COST).unwrap();
+
+        let measure = |name: &str, iterations: u32, hash: &str| {
+            let started = std::time::Instant::now();
+            for _ in 0..iterations {
+                assert!(verifier.is_verified(hash, &api_key).unwrap());
+            }
+            let elapsed = started.elapsed();
+            println!(
+                "{}: {} verifications in {:?}, {:.0} ops/s",
+                name, iterations, elapsed, iterations as f64 / elapsed.as_secs_f64()
+            );
+        };
+
+        measure("bcrypt", 10, &bcrypt_hash);
+        measure("hmac-sha256", 100_000, &hmac_hash);
+
+        let started = std::time::Instant::now();
+        verifier.remember(&api_key, &ExtendedAuthMethod::mock());
+        for _ in 0..100_000 {
+            assert!(verifier.cached(&api_key).is_some());
+        }
+        println!("cache: 100000 lookups in {:?}", started.elapsed());
+    }
 }
+
@@ -41,18 +41,43 @@ pub enum ApiKeyVerifierError {
         #[source]
         source: bcrypt::BcryptError,
     },
-    #[error("Decryption Error")]
-    DecryptionError(String),
     #[error("Encryption Error")]
     EncryptionError(String),
+    #[error("Api key format invalid: {0}")]
+    InvalidFormat(String),
+    #[error("Api key checksum mismatch")]
+    ChecksumMismatch,
+    #[error("Request signature mismatch")]
+    SignatureMismatch,
+    #[error("Request timestamp {0} is outside the allowed window")]
+    StaleTimestamp(i64),
+    #[error("Request signature was already used")]
+    SignatureReplayed,
+    #[error("Request signature cache error: {0}")]
+    ReplayCacheError(String),
 }
 
 impl AppErrorInfo for ApiKeyVerifierError {
     fn client_message(&self) -> String {
-        "Internal Server Error".to_string()
+        match self {
+            ApiKeyVerifierError::InvalidFormat(_) | ApiKeyVerifierError::ChecksumMismatch => {
+                "Api key is not correct".to_string()
+            }
+            ApiKeyVerifierError::SignatureMismatch
+            | ApiKeyVerifierError::StaleTimestamp(_)
+            | ApiKeyVerifierError::SignatureReplayed => "Request signature is not valid".to_string(),
+            _ => "Internal Server Error".to_string(),
+        }
     }
     fn level(&self) -> ErrorLevel {
-        ErrorLevel::Error
+        match self {
+            ApiKeyVerifierError::InvalidFormat(_)
+            | ApiKeyVerifierError::ChecksumMismatch
+            | ApiKeyVerifierError::SignatureMismatch
+            | ApiKeyVerifierError::StaleTimestamp(_)
+            | ApiKeyVerifierError::SignatureReplayed => ErrorLevel::Info,
+            _ => ErrorLevel::Error,
+        }
     }
     fn log_message(&self) -> String {
         match self {
@@ -62,12 +87,42 @@ impl AppErrorInfo for ApiKeyVerifierError {
                     stage, source
                 )
             }
-            ApiKeyVerifierError::DecryptionError(msg) => {
-                format!("ApiKeyVerifierError::DecryptionError: {}", msg)
-            }
             ApiKeyVerifierError::EncryptionError(msg) => {
                 format!("ApiKeyVerifierError::EncryptionError: {}", msg)
             }
+            ApiKeyVerifierError::InvalidFormat(msg) => {
+                format!("ApiKeyVerifierError::InvalidFormat: {}", msg)
+            }
+            ApiKeyVerifierError::ChecksumMismatch => {
+                "ApiKeyVerifierError::ChecksumMismatch".to_string()
+            }
+            ApiKeyVerifierError::SignatureMismatch => {
+                "ApiKeyVerifierError::SignatureMismatch".to_string()
+            }
+            ApiKeyVerifierError::StaleTimestamp(timestamp) => {
+                format!("ApiKeyVerifierError::StaleTimestamp: {}", timestamp)
+            }
+            ApiKeyVerifierError::SignatureReplayed => {
+                "ApiKeyVerifierError::SignatureReplayed".to_string()
+            }
+            ApiKeyVerifierError::ReplayCacheError(msg) => {
+                format!("ApiKeyVerifierError::ReplayCacheError: {}", msg)
+            }
+        }
+    }
+    fn status_code(&self) -> u16 {
+        match self.level() {
+            ErrorLevel::Info => 401,
+            _ => 500,
+        }
+    }
+    fn error_code(&self) -> &'static str {
+        match self {
+            ApiKeyVerifierError::InvalidFormat(_) | ApiKeyVerifierError::ChecksumMismatch => "invalid_api_key",
+            ApiKeyVerifierError::SignatureMismatch
+            | ApiKeyVerifierError::StaleTimestamp(_)
+            | ApiKeyVerifierError::SignatureReplayed => "invalid_signature",
+            _ => "internal_error",
         }
     }
 }
@@ -76,16 +131,71 @@ impl AppErrorInfo for ApiKeyVerifierError {
 
 #[derive(Debug, Error)]
 pub enum TelegramVerifierError {
+    #[error("Telegram bot is not configured: {0}")]
+    UnknownBot(String),
+    #[error("Telegram hash is not a valid hex string: {0}")]
+    InvalidHash(String),
+    #[error("Telegram hash mismatch")]
+    HashMismatch,
+    #[error("Telegram auth_date {auth_date} is older than {max_age} seconds")]
+    Expired { auth_date: i64, max_age: i64 },
+    #[error("Telegram auth_date {0} is in the future")]
+    AuthDateInFuture(i64),
+    #[error("Telegram data has already been used")]
+    Replayed,
+    #[error("Failed to access replay cache: {0}")]
+    ReplayCacheError(String),
 }
 
 impl AppErrorInfo for TelegramVerifierError {
     fn client_message(&self) -> String {
-        "Internal Server Error".to_string()
+        match self {
+            TelegramVerifierError::ReplayCacheError(_) => self.internal_error(),
+            _ => "Telegram data is not verified".to_string(),
+        }
     }
     fn level(&self) -> ErrorLevel {
-        ErrorLevel::Error
+        match self {
+            TelegramVerifierError::ReplayCacheError(_) => ErrorLevel::Error,
+            _ => ErrorLevel::Info,
+        }
     }
     fn log_message(&self) -> String {
-        "Internal Server Error".to_string()
+        match self {
+            TelegramVerifierError::UnknownBot(bot) => {
+                format!("TelegramVerifierError::UnknownBot: {}", bot)
+            }
+            TelegramVerifierError::InvalidHash(hash) => {
+                format!("TelegramVerifierError::InvalidHash: {}", hash)
+            }
+            TelegramVerifierError::HashMismatch => {
+                "TelegramVerifierError::HashMismatch".to_string()
+            }
+            TelegramVerifierError::Expired { auth_date, max_age } => {
+                format!(
+                    "TelegramVerifierError::Expired auth_date: {} max_age: {}",
+                    auth_date, max_age
+                )
+            }
+            TelegramVerifierError::AuthDateInFuture(auth_date) => {
+                format!("TelegramVerifierError::AuthDateInFuture auth_date: {}", auth_date)
+            }
+            TelegramVerifierError::Replayed => "TelegramVerifierError::Replayed".to_string(),
+            TelegramVerifierError::ReplayCacheError(e) => {
+                format!("TelegramVerifierError::ReplayCacheError: {}", e)
+            }
+        }
+    }
+    fn status_code(&self) -> u16 {
+        match self {
+            TelegramVerifierError::ReplayCacheError(_) => 500,
+            _ => 401,
+        }
     }
-}
\ No newline at end of file
+    fn error_code(&self) -> &'static str {
+        match self {
+            TelegramVerifierError::ReplayCacheError(_) => "internal_error",
+            _ => "telegram_not_verified",
+        }
+    }
+}
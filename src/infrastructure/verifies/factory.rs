@@ -1,16 +1,18 @@
 use super::password_verifier::PasswordVerifier;
 use crate::domain::settings::model::Credentials;
 use crate::domain::verifies::factories::VerifiesProviderFactory;
-use crate::infrastructure::verifies::api_key_verifier::ApiKeyVerifier;
+use crate::infrastructure::verifies::api_key_verifier::{ApiKeyCache, ApiKeyVerifier};
 use super::telegram_verifier::TelegramVerifier;
 
 pub struct VerifiesProvider {
     credentials: Credentials,
+    /// Один кэш API ключей на все выданные verifier
+    api_key_cache: ApiKeyCache,
 }
 
 impl VerifiesProvider {
     pub fn new(credentials: Credentials) -> Self {
-        Self { credentials }
+        Self { credentials, api_key_cache: ApiKeyCache::default() }
     }
 }
 
@@ -19,7 +21,7 @@ impl VerifiesProviderFactory for VerifiesProvider {
     type PasswordVerifier = PasswordVerifier;
     type TelegramVerifierService = TelegramVerifier;
     fn api_key_verifier(&self) -> Self::ApiKeyVerifier {
-        ApiKeyVerifier::new(self.credentials.clone())
+        ApiKeyVerifier::new(self.credentials.clone()).with_cache(self.api_key_cache.clone())
     }
     fn password_verifier(&self) -> Self::PasswordVerifier {
         PasswordVerifier
@@ -2,4 +2,5 @@ pub mod api_key_verifier;
 pub mod errors;
 pub mod factory;
 pub mod password_verifier;
+pub mod request_signature;
 pub mod telegram_verifier;
@@ -0,0 +1,296 @@
+//! Подпись запросов API ключом (по мотивам AWS SigV4).
+//!
+//! Клиент отправляет
+//! `Authorization: AWR-HMAC-SHA256 Credential=<identifier>, Timestamp=<unix>, Signature=<hex>`, где
+//!
+//! ```text
+//! signing_key = HMAC-SHA256(key = api_key, "awr_request_signing")
+//! string_to_sign = "AWR-HMAC-SHA256\n<timestamp>\n<METHOD>\n<path?query>\n<hex(sha256(body))>"
+//! signature = hex(HMAC-SHA256(key = signing_key, string_to_sign))
+//! ```
+//!
+//! Сам ключ в запросе не передаётся. Сервер хранит signing_key зашифрованным
+//! (AES-256-GCM), потому что от API ключа у него есть только хэш.
+//! Примеры для проверки клиентов - tests/request_signing/vectors.json
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use rand::{rngs::OsRng, TryRngCore};
+use sha2::{Digest, Sha256};
+
+use crate::domain::verifies::model::{RequestSignature, SignedRequest};
+
+use super::errors::ApiKeyVerifierError;
+
+pub const SCHEME: &str = "AWR-HMAC-SHA256";
+const SIGNING_KEY_CONTEXT: &[u8] = b"awr_request_signing";
+const NONCE_LENGTH: usize = 12;
+
+lazy_static! {
+    /// signature -> момент, после которого запись можно забыть
+    static ref SEEN_SIGNATURES: Mutex<HashMap<String, i64>> = Mutex::new(HashMap::new());
+}
+
+pub(crate) fn signing_key(api_key: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(api_key.as_bytes()).unwrap();
+    mac.update(SIGNING_KEY_CONTEXT);
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub(crate) fn string_to_sign(timestamp: i64, request: &SignedRequest) -> String {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        SCHEME,
+        timestamp,
+        request.method.to_uppercase(),
+        request.path,
+        hex::encode(Sha256::digest(&request.body))
+    )
+}
+
+fn mac(signing_key: &[u8], string_to_sign: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key).unwrap();
+    mac.update(string_to_sign.as_bytes());
+    mac
+}
+
+/// Подпись на стороне клиента, сервер только сверяет через `verify`
+#[cfg(test)]
+pub(crate) fn sign(signing_key: &[u8], string_to_sign: &str) -> String {
+    hex::encode(mac(signing_key, string_to_sign).finalize().into_bytes())
+}
+
+pub(crate) fn parse(authorization: &str) -> Result<RequestSignature, ApiKeyVerifierError> {
+    let invalid = || ApiKeyVerifierError::InvalidFormat("Signature header format invalid".to_string());
+
+    let params = authorization
+        .strip_prefix(SCHEME)
+        .and_then(|v| v.strip_prefix(' '))
+        .ok_or_else(invalid)?;
+
+    let mut identifier = None;
+    let mut timestamp = None;
+    let mut signature = None;
+    for param in params.split(',') {
+        let (name, value) = param.trim().split_once('=').ok_or_else(invalid)?;
+        match name {
+            "Credential" => identifier = Some(value.to_string()),
+            "Timestamp" => timestamp = Some(value.parse::<i64>().map_err(|_| invalid())?),
+            "Signature" => signature = Some(value.to_string()),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(RequestSignature {
+        identifier: identifier.ok_or_else(invalid)?,
+        timestamp: timestamp.ok_or_else(invalid)?,
+        signature: signature.ok_or_else(invalid)?,
+    })
+}
+
+fn cipher(encryption_key: &str) -> Result<Aes256Gcm, ApiKeyVerifierError> {
+    let key = hex::decode(encryption_key)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(format!("request_signing_key: {}", e)))?;
+    <Aes256Gcm as aes_gcm::KeyInit>::new_from_slice(&key)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(format!("request_signing_key: {}", e)))
+}
+
+/// base64(nonce || ciphertext)
+pub(crate) fn encrypt(encryption_key: &str, signing_key: &[u8]) -> Result<String, ApiKeyVerifierError> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng
+        .try_fill_bytes(&mut nonce)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(e.to_string()))?;
+
+    let ciphertext = cipher(encryption_key)?
+        .encrypt(Nonce::from_slice(&nonce), signing_key)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(e.to_string()))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(ciphertext);
+    Ok(base64::engine::general_purpose::STANDARD.encode(sealed))
+}
+
+pub(crate) fn decrypt(encryption_key: &str, sealed: &str) -> Result<Vec<u8>, ApiKeyVerifierError> {
+    let sealed = base64::engine::general_purpose::STANDARD
+        .decode(sealed)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(e.to_string()))?;
+    if sealed.len() <= NONCE_LENGTH {
+        return Err(ApiKeyVerifierError::EncryptionError("signing secret is too short".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+
+    cipher(encryption_key)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|e| ApiKeyVerifierError::EncryptionError(e.to_string()))
+}
+
+pub(crate) fn verify(
+    signing_key: &[u8],
+    signature: &RequestSignature,
+    request: &SignedRequest,
+    max_skew: i64,
+    now: i64,
+) -> Result<bool, ApiKeyVerifierError> {
+    if (now - signature.timestamp).abs() > max_skew {
+        return Err(ApiKeyVerifierError::StaleTimestamp(signature.timestamp));
+    }
+
+    let expected = hex::decode(&signature.signature).map_err(|_| ApiKeyVerifierError::SignatureMismatch)?;
+
+    // verify_slice сравнивает за постоянное время
+    mac(signing_key, &string_to_sign(signature.timestamp, request))
+        .verify_slice(&expected)
+        .map_err(|_| ApiKeyVerifierError::SignatureMismatch)?;
+
+    remember(signature, max_skew, now)?;
+    Ok(true)
+}
+
+fn remember(signature: &RequestSignature, max_skew: i64, now: i64) -> Result<(), ApiKeyVerifierError> {
+    let mut seen = SEEN_SIGNATURES
+        .lock()
+        .map_err(|e| ApiKeyVerifierError::ReplayCacheError(e.to_string()))?;
+
+    // Старые подписи всё равно не пройдут проверку timestamp
+    seen.retain(|_, forget_at| *forget_at > now);
+
+    // hex::decode принимает оба регистра, поэтому ключ - подпись в нижнем регистре
+    let key = signature.signature.to_ascii_lowercase();
+    if seen.contains_key(&key) {
+        return Err(ApiKeyVerifierError::SignatureReplayed);
+    }
+
+    seen.insert(key, signature.timestamp + max_skew);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: &str = include_str!("../../../tests/request_signing/vectors.json");
+    const ENCRYPTION_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[derive(serde::Deserialize)]
+    struct Vector {
+        api_key: String,
+        method: String,
+        path: String,
+        body: String,
+        timestamp: i64,
+        signing_key: String,
+        string_to_sign: String,
+        signature: String,
+        authorization: String,
+    }
+
+    fn vectors() -> Vec<Vector> {
+        let value: serde_json::Value = serde_json::from_str(VECTORS).unwrap();
+        serde_json::from_value(value["vectors"].clone()).unwrap()
+    }
+
+    fn request(vector: &Vector) -> SignedRequest {
+        SignedRequest {
+            method: vector.method.clone(),
+            path: vector.path.clone(),
+            body: vector.body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn vectors_are_reproduced() {
+        for vector in vectors() {
+            let key = signing_key(&vector.api_key);
+            let string = string_to_sign(vector.timestamp, &request(&vector));
+
+            assert_eq!(hex::encode(&key), vector.signing_key);
+            assert_eq!(string, vector.string_to_sign);
+            assert_eq!(sign(&key, &string), vector.signature);
+
+            let parsed = parse(&vector.authorization).unwrap();
+            assert_eq!(parsed.timestamp, vector.timestamp);
+            assert_eq!(parsed.signature, vector.signature);
+        }
+    }
+
+    #[test]
+    fn vector_is_verified_once() {
+        let vector = vectors().remove(0);
+        let signature = parse(&vector.authorization).unwrap();
+        let key = signing_key(&vector.api_key);
+
+        let result = verify(&key, &signature, &request(&vector), 300, vector.timestamp + 10);
+        assert!(result.unwrap());
+
+        let result = verify(&key, &signature, &request(&vector), 300, vector.timestamp + 20);
+        assert!(matches!(result, Err(ApiKeyVerifierError::SignatureReplayed)));
+    }
+
+    #[test]
+    fn replayed_signature_in_upper_case_is_rejected() {
+        let vector = vectors().remove(0);
+        let key = signing_key(&vector.api_key);
+        let timestamp = vector.timestamp + 1000;
+        let mut signature = RequestSignature {
+            identifier: "replay".to_string(),
+            timestamp,
+            signature: sign(&key, &string_to_sign(timestamp, &request(&vector))),
+        };
+
+        let result = verify(&key, &signature, &request(&vector), 300, timestamp);
+        assert!(result.unwrap());
+
+        signature.signature = signature.signature.to_ascii_uppercase();
+        let result = verify(&key, &signature, &request(&vector), 300, timestamp);
+        assert!(matches!(result, Err(ApiKeyVerifierError::SignatureReplayed)));
+    }
+
+    #[test]
+    fn stale_timestamp_is_rejected() {
+        let vector = vectors().remove(1);
+        let signature = parse(&vector.authorization).unwrap();
+        let key = signing_key(&vector.api_key);
+
+        let result = verify(&key, &signature, &request(&vector), 300, vector.timestamp + 301);
+
+        assert!(matches!(result, Err(ApiKeyVerifierError::StaleTimestamp(_))));
+    }
+
+    #[test]
+    fn changed_body_is_rejected() {
+        let vector = vectors().remove(1);
+        let signature = parse(&vector.authorization).unwrap();
+        let key = signing_key(&vector.api_key);
+        let mut request = request(&vector);
+        request.body = b"{\"amount\":1000}".to_vec();
+
+        let result = verify(&key, &signature, &request, 300, vector.timestamp);
+
+        assert!(matches!(result, Err(ApiKeyVerifierError::SignatureMismatch)));
+    }
+
+    #[test]
+    fn malformed_header_is_rejected() {
+        assert!(parse("Bearer token").is_err());
+        assert!(parse("AWR-HMAC-SHA256 Credential=abc, Timestamp=now, Signature=00").is_err());
+        assert!(parse("AWR-HMAC-SHA256 Credential=abc, Signature=00").is_err());
+    }
+
+    #[test]
+    fn signing_key_survives_encryption() {
+        let key = signing_key("awr_test_ABC_DEF_AAAAAA");
+
+        let sealed = encrypt(ENCRYPTION_KEY, &key).unwrap();
+
+        assert_ne!(sealed, encrypt(ENCRYPTION_KEY, &key).unwrap());
+        assert_eq!(decrypt(ENCRYPTION_KEY, &sealed).unwrap(), key);
+        let other = "ff".repeat(32);
+        assert!(decrypt(&other, &sealed).is_err());
+    }
+}
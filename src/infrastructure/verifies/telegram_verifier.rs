@@ -1,6 +1,8 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
 
 use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
 use sha2::{Digest, Sha256};
 
 use crate::domain::settings::model::Credentials;
@@ -8,6 +10,14 @@ use crate::domain::verifies::service::TelegramVerifierService;
 use crate::domain::verifies::model::TelegramData;
 use super::errors::TelegramVerifierError;
 
+/// Допустимое расхождение часов между нами и Telegram (секунды)
+const AUTH_DATE_FUTURE_SKEW: i64 = 60;
+
+lazy_static! {
+    /// hash -> момент, после которого запись можно забыть
+    static ref SEEN_HASHES: Mutex<HashMap<String, i64>> = Mutex::new(HashMap::new());
+}
+
 pub struct TelegramVerifier{
     credentials: Credentials
 }
@@ -15,45 +25,278 @@ impl TelegramVerifier {
     pub fn new(credentials: Credentials) -> Self {
         Self { credentials }
     }
-}
 
+    pub(crate) fn data_check_string(telegram_data: &TelegramData) -> String {
+        let mut data_map: BTreeMap<String, String> = telegram_data.extra.clone();
 
-impl TelegramVerifierService for TelegramVerifier {
-    type Error = TelegramVerifierError;
-    fn is_verified(&self, telegram_data: TelegramData) -> Result<bool, Self::Error> {
-        let bot_token = self.credentials.bot_token().clone();
-        let mut data_map = BTreeMap::new();
-
-        data_map.insert("auth_date", telegram_data.auth_date.to_string());
-        data_map.insert("id", telegram_data.id.to_string());
-        data_map.insert("username", telegram_data.username.clone());
+        data_map.insert("auth_date".to_string(), telegram_data.auth_date.to_string());
+        data_map.insert("id".to_string(), telegram_data.id.to_string());
+        data_map.insert("username".to_string(), telegram_data.username.clone());
 
         if let Some(first_name) = &telegram_data.first_name {
-            data_map.insert("first_name", first_name.clone());
+            data_map.insert("first_name".to_string(), first_name.clone());
         }
         if let Some(last_name) = &telegram_data.last_name {
-            data_map.insert("last_name", last_name.clone());
+            data_map.insert("last_name".to_string(), last_name.clone());
         }
         if let Some(photo_url) = &telegram_data.photo_url {
-            data_map.insert("photo_url", photo_url.clone());
+            data_map.insert("photo_url".to_string(), photo_url.clone());
         }
 
-        let data_check_string = data_map
+        data_map
             .iter()
             .map(|(k, v)| format!("{}={}", k, v))
             .collect::<Vec<String>>()
-            .join("\n");
+            .join("\n")
+    }
 
+    pub(crate) fn mac(bot_token: &str, data_check_string: &str) -> Hmac<Sha256> {
         // Хэшируем bot_token
         let secret_key = Sha256::digest(bot_token.as_bytes());
 
-        // Подпись
+        // HMAC принимает ключ любой длины
         let mut mac = Hmac::<Sha256>::new_from_slice(&secret_key).unwrap();
         mac.update(data_check_string.as_bytes());
+        mac
+    }
+
+    fn check_signature(&self, bot: Option<&str>, telegram_data: &TelegramData) -> Result<(), TelegramVerifierError> {
+        let bot = self
+            .credentials
+            .telegram_bot(bot)
+            .ok_or_else(|| TelegramVerifierError::UnknownBot(bot.unwrap_or_default().to_string()))?;
+
+        let expected = hex::decode(&telegram_data.hash)
+            .map_err(|_| TelegramVerifierError::InvalidHash(telegram_data.hash.clone()))?;
+
+        let data_check_string = Self::data_check_string(telegram_data);
+        let mac = Self::mac(bot.token(), &data_check_string);
+
+        // verify_slice сравнивает за постоянное время
+        mac.verify_slice(&expected)
+            .map_err(|_| TelegramVerifierError::HashMismatch)
+    }
+
+    fn check_auth_date(&self, auth_date: i64, now: i64) -> Result<(), TelegramVerifierError> {
+        let max_age = *self.credentials.telegram_auth_max_age();
+
+        if auth_date > now + AUTH_DATE_FUTURE_SKEW {
+            return Err(TelegramVerifierError::AuthDateInFuture(auth_date));
+        }
+        if now - auth_date > max_age {
+            return Err(TelegramVerifierError::Expired { auth_date, max_age });
+        }
+        Ok(())
+    }
+
+    /// Блокирует кэш, забыв записи, которые всё равно не пройдут проверку auth_date
+    fn seen_hashes(now: i64) -> Result<MutexGuard<'static, HashMap<String, i64>>, TelegramVerifierError> {
+        let mut seen = SEEN_HASHES
+            .lock()
+            .map_err(|e| TelegramVerifierError::ReplayCacheError(e.to_string()))?;
+        seen.retain(|_, forget_at| *forget_at > now);
+        Ok(seen)
+    }
+
+    /// hex::decode принимает оба регистра: тот же hash в верхнем регистре - повтор
+    fn hash_key(telegram_data: &TelegramData) -> String {
+        telegram_data.hash.to_ascii_lowercase()
+    }
+}
+
+
+impl TelegramVerifierService for TelegramVerifier {
+    type Error = TelegramVerifierError;
+    fn is_verified(&self, bot: Option<&str>, telegram_data: TelegramData) -> Result<bool, Self::Error> {
+        let now = chrono::Utc::now().timestamp();
+
+        self.check_signature(bot, &telegram_data)?;
+        self.check_auth_date(telegram_data.auth_date, now)?;
+        if Self::seen_hashes(now)?.contains_key(&Self::hash_key(&telegram_data)) {
+            return Err(TelegramVerifierError::Replayed);
+        }
+
+        Ok(true)
+    }
+
+    fn remember(&self, telegram_data: &TelegramData) -> Result<(), Self::Error> {
+        let now = chrono::Utc::now().timestamp();
+        let max_age = *self.credentials.telegram_auth_max_age();
+
+        // Проверка и запись под одной блокировкой: из двух параллельных входов проходит один
+        let mut seen = Self::seen_hashes(now)?;
+        let hash = Self::hash_key(telegram_data);
+        if seen.contains_key(&hash) {
+            return Err(TelegramVerifierError::Replayed);
+        }
+        seen.insert(hash, telegram_data.auth_date + max_age);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::telegram::MockTelegramData;
+
+    fn verifier() -> TelegramVerifier {
+        TelegramVerifier::new(Credentials::mock())
+    }
+
+    fn bot_token() -> String {
+        Credentials::mock().telegram_bot(None).unwrap().token().clone()
+    }
+
+    #[test]
+    fn valid_signature_is_verified() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+
+        let result = verifier().is_verified(None, data);
+
+        assert!(result.is_ok());
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn wrong_hash_is_rejected() {
+        let mut data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+        data.username = "intruder".to_string();
+
+        let result = verifier().is_verified(None, data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::HashMismatch)));
+    }
+
+    #[test]
+    fn not_hex_hash_is_rejected() {
+        let mut data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+        data.hash = "not-a-hash".to_string();
+
+        let result = verifier().is_verified(None, data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::InvalidHash(_))));
+    }
+
+    #[test]
+    fn unknown_fields_are_signed() {
+        let mut dto = MockTelegramData::signed(&bot_token());
+        dto.extra.insert("allows_write_to_pm".to_string(), serde_json::json!(true));
+        let dto = MockTelegramData::sign(dto, &bot_token());
+        let data: TelegramData = dto.into();
+
+        assert!(TelegramVerifier::data_check_string(&data).starts_with("allows_write_to_pm=true\n"));
+        assert!(verifier().is_verified(None, data.clone()).is_ok());
+
+        let mut stripped = data;
+        stripped.extra.clear();
+        let result = verifier().is_verified(None, stripped);
+        assert!(matches!(result, Err(TelegramVerifierError::HashMismatch)));
+    }
+
+    #[test]
+    fn expired_auth_date_is_rejected() {
+        let max_age = *Credentials::mock().telegram_auth_max_age();
+        let mut dto = MockTelegramData::signed(&bot_token());
+        dto.auth_date -= max_age + 1;
+        let data: TelegramData = MockTelegramData::sign(dto, &bot_token()).into();
+
+        let result = verifier().is_verified(None, data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::Expired { .. })));
+    }
+
+    #[test]
+    fn future_auth_date_is_rejected() {
+        let mut dto = MockTelegramData::signed(&bot_token());
+        dto.auth_date += AUTH_DATE_FUTURE_SKEW + 60;
+        let data: TelegramData = MockTelegramData::sign(dto, &bot_token()).into();
+
+        let result = verifier().is_verified(None, data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::AuthDateInFuture(_))));
+    }
+
+    #[test]
+    fn replayed_data_is_rejected() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+
+        assert!(verifier().is_verified(None, data.clone()).is_ok());
+        assert!(verifier().remember(&data).is_ok());
+        let result = verifier().is_verified(None, data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::Replayed)));
+    }
+
+    #[test]
+    fn data_is_remembered_once() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+
+        assert!(verifier().remember(&data).is_ok());
+        let result = verifier().remember(&data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::Replayed)));
+    }
+
+    #[test]
+    fn data_is_accepted_until_login_succeeds() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+
+        // Вход не удался после проверки: повторить его можно
+        assert!(verifier().is_verified(None, data.clone()).is_ok());
+        assert!(verifier().is_verified(None, data).is_ok());
+    }
+
+    #[test]
+    fn replayed_data_in_upper_case_is_rejected() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+        let mut upper = data.clone();
+        upper.hash = upper.hash.to_ascii_uppercase();
+
+        assert!(verifier().is_verified(None, data.clone()).is_ok());
+        assert!(verifier().remember(&data).is_ok());
+        let result = verifier().is_verified(None, upper);
+
+        assert!(matches!(result, Err(TelegramVerifierError::Replayed)));
+    }
+
+    #[test]
+    fn unknown_bot_is_rejected() {
+        let data: TelegramData = MockTelegramData::signed(&bot_token()).into();
+
+        let result = verifier().is_verified(Some("unknown"), data);
+
+        assert!(matches!(result, Err(TelegramVerifierError::UnknownBot(_))));
+    }
+
+    #[test]
+    fn data_is_checked_against_selected_bot() {
+        let credentials: Credentials = serde_json::from_value(serde_json::json!({
+            "host": "TEST_HOST",
+            "port": 100,
+            "expiration_access_hours": 1,
+            "expiration_refresh_hours": 1,
+            "access_secret": "TEST",
+            "refresh_secret": "TEST",
+            "hasura_url": "URL",
+            "hasura_credentials": {"x_hasura_default_role": "TEST", "exp": 1, "x_hasura_user_id": "TEST"},
+            "new_user_role": {"with_email": "TEST", "with_telegram": "TEST"},
+            "api_key_length": 10,
+            "telegram_bots": [
+                {"name": "production", "token": "PRODUCTION_TOKEN"},
+                {"name": "staging", "token": "STAGING_TOKEN"}
+            ],
+            "telegram_auth_max_age": 60,
+            "telegram_api_url": "URL",
+            "telegram_login_ttl": 60
+        }))
+        .unwrap();
+        let verifier = TelegramVerifier::new(credentials);
 
-        let result = mac.finalize();
-        let calculated_hash = hex::encode(result.into_bytes());
+        let data: TelegramData = MockTelegramData::signed("STAGING_TOKEN").into();
+        let result = verifier.is_verified(Some("production"), data);
+        assert!(matches!(result, Err(TelegramVerifierError::HashMismatch)));
 
-        Ok(calculated_hash == telegram_data.hash)
+        let data: TelegramData = MockTelegramData::signed("STAGING_TOKEN").into();
+        assert!(verifier.is_verified(Some("staging"), data).is_ok());
     }
 }
@@ -1 +1,2 @@
-pub mod web;
+#[cfg(feature = "actix")]
+pub mod web;
\ No newline at end of file
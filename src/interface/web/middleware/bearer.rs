@@ -0,0 +1,361 @@
+use std::future::{ready, Future, Ready};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, ResponseError};
+use chrono::Utc;
+use futures_util::future::LocalBoxFuture;
+use uuid::Uuid;
+
+use crate::domain::errors::service::AppErrorInfo;
+use crate::domain::jwt::model::Claims;
+use crate::domain::jwt::service::TokenService;
+use crate::domain::user::models::status::UserStatus;
+use crate::domain::user::service::QueryUserService;
+
+use super::error::AuthMiddlewareError;
+
+/// Пользователь из проверенного access токена.
+/// Кладётся в extensions запроса middleware [`BearerAuth`]
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub user_id: Uuid,
+    /// x-hasura-allowed-roles
+    pub roles: Vec<String>,
+    pub default_role: String,
+    pub claims: Claims,
+}
+
+impl AuthenticatedUser {
+    pub fn from_claims(claims: Claims) -> Option<Self> {
+        let user_id = Uuid::from_str(&claims.sub).ok()?;
+        Some(Self {
+            user_id,
+            roles: claims.hasura_claims.x_hasura_allowed_roles.clone(),
+            default_role: claims.hasura_claims.x_hasura_default_role.clone(),
+            claims,
+        })
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|v| v == role)
+    }
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let user = req.extensions().get::<AuthenticatedUser>().cloned();
+        ready(user.ok_or_else(|| AuthMiddlewareError::MissingToken.into()))
+    }
+}
+
+/// Проверка пользователя из токена по базе, см. [`BearerAuth::with_user_check`]
+pub trait UserCheck {
+    fn check(&self, user: &AuthenticatedUser) -> impl Future<Output = Result<(), AuthMiddlewareError>>;
+}
+
+/// Без проверки: достаточно подписи и срока токена
+impl UserCheck for () {
+    async fn check(&self, _: &AuthenticatedUser) -> Result<(), AuthMiddlewareError> {
+        Ok(())
+    }
+}
+
+/// Пользователь существует и активен, токен выдан после принудительного выхода
+impl<Q> UserCheck for Q
+where
+    Q: QueryUserService,
+{
+    async fn check(&self, user: &AuthenticatedUser) -> Result<(), AuthMiddlewareError> {
+        let auth_methods = self.get_user_by_id(user.user_id).await.map_err(|e| {
+            tracing::error!("{} | Level: {:?}", e.log_message(), e.level());
+            AuthMiddlewareError::UserUnavailable
+        })?;
+        let Some(found) = auth_methods.first().map(|v| v.user()) else {
+            return Err(AuthMiddlewareError::NotCorrectAccessToken);
+        };
+
+        let status = found.status_at(Utc::now());
+        if status != UserStatus::Active {
+            return Err(AuthMiddlewareError::UserInactive(status));
+        }
+
+        // Как и в check_token: токены, выданные до принудительного выхода, недействительны
+        if let Some(logged_out_at) = found.logged_out_at() {
+            let issued_at = user.claims.iat.unwrap_or_default() as i64;
+            if issued_at <= logged_out_at.timestamp() {
+                return Err(AuthMiddlewareError::NotCorrectAccessToken);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Проверяет `Authorization: Bearer <token>` через [`TokenService`].
+/// Без валидного токена отвечает 401, обработчик не вызывается.
+///
+/// Сама по себе проверка не ходит в базу: отключённый пользователь или выход
+/// через `POST /auth/admin/users/{id}/logout` не отзывают уже выданный access токен
+/// до его истечения. Сервер подключает [`BearerAuth::with_user_check`]
+pub struct BearerAuth<TS, UC = ()> {
+    token_service: Arc<TS>,
+    user_check: Arc<UC>,
+}
+
+impl<TS> BearerAuth<TS> {
+    pub fn new(token_service: TS) -> Self {
+        Self { token_service: Arc::new(token_service), user_check: Arc::new(()) }
+    }
+}
+
+impl<TS, UC> BearerAuth<TS, UC> {
+    /// Дополнительно проверяет пользователя по базе на каждый запрос, см. [`UserCheck`]
+    pub fn with_user_check<Q>(self, user_check: Q) -> BearerAuth<TS, Q> {
+        BearerAuth { token_service: self.token_service, user_check: Arc::new(user_check) }
+    }
+}
+
+impl<TS, UC> Clone for BearerAuth<TS, UC> {
+    fn clone(&self) -> Self {
+        Self { token_service: self.token_service.clone(), user_check: self.user_check.clone() }
+    }
+}
+
+impl<S, B, TS, UC> Transform<S, ServiceRequest> for BearerAuth<TS, UC>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+    TS: TokenService + 'static,
+    UC: UserCheck + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = BearerAuthMiddleware<S, TS, UC>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(BearerAuthMiddleware {
+            service: Rc::new(service),
+            token_service: self.token_service.clone(),
+            user_check: self.user_check.clone(),
+        }))
+    }
+}
+
+pub struct BearerAuthMiddleware<S, TS, UC> {
+    service: Rc<S>,
+    token_service: Arc<TS>,
+    user_check: Arc<UC>,
+}
+
+impl<S, TS, UC> BearerAuthMiddleware<S, TS, UC>
+where
+    TS: TokenService,
+{
+    fn authenticate(&self, req: &ServiceRequest) -> Result<AuthenticatedUser, AuthMiddlewareError> {
+        let token = req
+            .headers()
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(|v| v.trim())
+            .ok_or(AuthMiddlewareError::MissingToken)?;
+
+        let claims = self.token_service.validate_access(token).map_err(|e| {
+            tracing::info!("{} | Level: {:?}", e.log_message(), e.level());
+            AuthMiddlewareError::NotCorrectAccessToken
+        })?;
+
+        AuthenticatedUser::from_claims(claims).ok_or(AuthMiddlewareError::NotCorrectAccessToken)
+    }
+}
+
+impl<S, B, TS, UC> Service<ServiceRequest> for BearerAuthMiddleware<S, TS, UC>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+    TS: TokenService + 'static,
+    UC: UserCheck + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let authenticated = self.authenticate(&req);
+        let service = self.service.clone();
+        let user_check = self.user_check.clone();
+
+        Box::pin(async move {
+            let checked = match authenticated {
+                Ok(user) => user_check.check(&user).await.map(|_| user),
+                Err(e) => Err(e),
+            };
+            match checked {
+                Ok(user) => {
+                    req.extensions_mut().insert(user);
+                    service.call(req).await.map(ServiceResponse::map_into_left_body)
+                }
+                Err(e) => Ok(req.into_response(e.error_response()).map_into_right_body()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::{get, test, web, App, HttpResponse, Responder};
+    use chrono::Utc;
+
+    use crate::domain::jwt::model::HasuraClaims;
+    use crate::domain::settings::model::Credentials;
+    use crate::domain::user::factories::UserProviderFactory;
+    use crate::infrastructure::jwt::token::TokenProvider;
+    use crate::infrastructure::user::user_manager::UserQuery;
+    use crate::interface::web::middleware::role::require_role;
+    use crate::mock::hasura_client::MockHasuraClientBuilder;
+    use crate::mock::http_client::MockHttpClient;
+    use crate::mock::user_provider::MockUserProvider;
+
+    const USER_ID: &str = "801bd045-a367-4683-9234-297586264e39";
+
+    fn token(roles: &[&str]) -> String {
+        token_issued_at(roles, Utc::now().timestamp() as usize)
+    }
+
+    fn token_issued_at(roles: &[&str], iat: usize) -> String {
+        let roles: Vec<String> = roles.iter().map(|v| v.to_string()).collect();
+        let claims = Claims::new(
+            USER_ID.to_string(),
+            false,
+            (Utc::now().timestamp() + 600) as usize,
+            HasuraClaims::new(roles[0].clone(), roles, USER_ID.to_string()),
+        )
+        .with_iat(iat);
+        TokenProvider::new(Credentials::mock()).generate_access(claims).unwrap()
+    }
+
+    #[get("/me")]
+    async fn me(user: AuthenticatedUser) -> impl Responder {
+        HttpResponse::Ok().body(user.user_id.to_string())
+    }
+
+    #[get("/admin")]
+    async fn admin() -> impl Responder {
+        HttpResponse::Ok().finish()
+    }
+
+    macro_rules! app {
+        () => {
+            app!(BearerAuth::new(TokenProvider::new(Credentials::mock())))
+        };
+        ($bearer_auth:expr) => {
+            test::init_service(
+                App::new().service(
+                    web::scope("")
+                        .wrap($bearer_auth)
+                        .service(me)
+                        .service(web::scope("/manage").wrap(require_role("admin")).service(admin)),
+                ),
+            )
+            .await
+        };
+    }
+
+    fn checked(hasura_client: &MockHasuraClientBuilder) -> BearerAuth<TokenProvider, UserQuery<MockHttpClient>> {
+        let user_provider_factory = MockUserProvider::new(Credentials::mock(), hasura_client.build());
+        BearerAuth::new(TokenProvider::new(Credentials::mock())).with_user_check(user_provider_factory.query_user())
+    }
+
+    #[actix_web::test]
+    async fn valid_token_is_extracted() {
+        let app = app!();
+        let req = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Authorization", format!("Bearer {}", token(&["user"]))))
+            .to_request();
+
+        let body = test::call_and_read_body(&app, req).await;
+
+        assert_eq!(body, USER_ID);
+    }
+
+    #[actix_web::test]
+    async fn missing_or_invalid_token_is_unauthorized() {
+        let app = app!();
+
+        let req = test::TestRequest::get().uri("/me").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+
+        let req = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Authorization", "Bearer not.a.token"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+    }
+
+    #[actix_web::test]
+    async fn role_guard() {
+        let app = app!();
+
+        let req = test::TestRequest::get()
+            .uri("/manage/admin")
+            .insert_header(("Authorization", format!("Bearer {}", token(&["user"]))))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+        let req = test::TestRequest::get()
+            .uri("/manage/admin")
+            .insert_header(("Authorization", format!("Bearer {}", token(&["user", "admin"]))))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    #[actix_web::test]
+    async fn disabled_user_is_rejected_with_user_check() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_disabled_user();
+        let app = app!(checked(&hasura_client));
+
+        let req = test::TestRequest::get()
+            .uri("/manage/admin")
+            .insert_header(("Authorization", format!("Bearer {}", token(&["user", "admin"]))))
+            .to_request();
+        let response = test::call_service(&app, req).await;
+
+        assert_eq!(response.status(), 403);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "account_disabled");
+    }
+
+    #[actix_web::test]
+    async fn token_issued_before_logout_is_rejected_with_user_check() {
+        let mut hasura_client = MockHasuraClientBuilder::new();
+        hasura_client.with_logged_out_user();
+        let app = app!(checked(&hasura_client));
+        let before_logout = chrono::DateTime::parse_from_rfc3339("2025-07-11T09:00:00+00:00").unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Authorization", format!("Bearer {}", token_issued_at(&["user"], before_logout.timestamp() as usize))))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+
+        let req = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Authorization", format!("Bearer {}", token(&["user"]))))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+}
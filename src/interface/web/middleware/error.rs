@@ -0,0 +1,85 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use thiserror::Error;
+
+use crate::domain::errors::locale::Locale;
+use crate::domain::errors::service::{AppError, AppErrorInfo, ErrorLevel};
+use crate::domain::user::models::status::UserStatus;
+
+#[derive(Debug, Error)]
+pub enum AuthMiddlewareError {
+    #[error("Missing Authorization header")]
+    MissingToken,
+    #[error("Access token is not verified")]
+    NotCorrectAccessToken,
+    #[error("Role {0} is required")]
+    RoleRequired(String),
+    #[error("User is {}", .0.as_str())]
+    UserInactive(UserStatus),
+    #[error("User lookup failed")]
+    UserUnavailable,
+}
+
+impl AppErrorInfo for AuthMiddlewareError {
+    fn client_message(&self) -> String {
+        match self {
+            AuthMiddlewareError::MissingToken => "Missing Authorization header".to_string(),
+            AuthMiddlewareError::NotCorrectAccessToken => "Unauthorized".to_string(),
+            AuthMiddlewareError::RoleRequired(_) => "Forbidden".to_string(),
+            AuthMiddlewareError::UserInactive(UserStatus::Locked) => "Account is locked".to_string(),
+            AuthMiddlewareError::UserInactive(UserStatus::PendingVerification) => "Account is not verified".to_string(),
+            AuthMiddlewareError::UserInactive(_) => "Account is disabled".to_string(),
+            AuthMiddlewareError::UserUnavailable => "Internal Server Error".to_string(),
+        }
+    }
+
+    fn level(&self) -> ErrorLevel {
+        match self {
+            AuthMiddlewareError::UserUnavailable => ErrorLevel::Error,
+            _ => ErrorLevel::Info,
+        }
+    }
+
+    fn log_message(&self) -> String {
+        format!("AuthMiddlewareError: {}", self)
+    }
+
+    fn status_code(&self) -> u16 {
+        match self {
+            AuthMiddlewareError::MissingToken | AuthMiddlewareError::NotCorrectAccessToken => 401,
+            AuthMiddlewareError::RoleRequired(_) => 403,
+            AuthMiddlewareError::UserInactive(UserStatus::Locked) => 423,
+            AuthMiddlewareError::UserInactive(_) => 403,
+            AuthMiddlewareError::UserUnavailable => 500,
+        }
+    }
+
+    fn error_code(&self) -> &'static str {
+        match self {
+            AuthMiddlewareError::MissingToken => "missing_token",
+            AuthMiddlewareError::NotCorrectAccessToken => "invalid_token",
+            AuthMiddlewareError::RoleRequired(_) => "role_required",
+            AuthMiddlewareError::UserInactive(UserStatus::Locked) => "account_locked",
+            AuthMiddlewareError::UserInactive(UserStatus::PendingVerification) => "account_not_verified",
+            AuthMiddlewareError::UserInactive(_) => "account_disabled",
+            AuthMiddlewareError::UserUnavailable => "internal_error",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AuthMiddlewareError::RoleRequired(role) => vec![("role", role.clone())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ResponseError for AuthMiddlewareError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(AppErrorInfo::status_code(self)).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        AppError::localized(self, Locale::current()).error_response()
+    }
+}
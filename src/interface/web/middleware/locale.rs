@@ -0,0 +1,118 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpRequest};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::domain::errors::locale::Locale;
+
+#[derive(serde::Deserialize)]
+struct LocaleClaim {
+    locale: Option<String>,
+}
+
+/// Выбирает язык ошибок запроса: атрибут `locale` пользователя из access токена,
+/// затем `Accept-Language`, иначе английский. Подключается самым внешним middleware
+pub async fn negotiate_locale(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let locale = request_locale(req.request());
+    let http_req = req.request().clone();
+
+    locale
+        .scope(async move {
+            match next.call(req).await {
+                Ok(response) => Ok(response.map_into_boxed_body()),
+                // Ошибки внутренних middleware рендерим здесь, пока язык запроса ещё выбран
+                Err(e) => Ok(ServiceResponse::new(http_req, e.error_response())),
+            }
+        })
+        .await
+}
+
+fn request_locale(req: &HttpRequest) -> Locale {
+    token_locale(req)
+        .or_else(|| {
+            req.headers()
+                .get("Accept-Language")
+                .and_then(|v| v.to_str().ok())
+                .and_then(Locale::negotiate)
+        })
+        .unwrap_or_default()
+}
+
+/// Подпись токена здесь не проверяется: язык ответа не даёт никакого доступа,
+/// а сам токен проверит BearerAuth или use case
+fn token_locale(req: &HttpRequest) -> Option<Locale> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))?;
+    let payload = URL_SAFE_NO_PAD.decode(token.trim().split('.').nth(1)?).ok()?;
+    let claim: LocaleClaim = serde_json::from_slice(&payload).ok()?;
+    Locale::from_tag(&claim.locale?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::middleware::from_fn;
+    use actix_web::{get, test, App};
+    use chrono::Utc;
+
+    use crate::domain::jwt::model::{Claims, HasuraClaims};
+    use crate::domain::jwt::service::TokenService;
+    use crate::domain::settings::model::Credentials;
+    use crate::infrastructure::jwt::token::TokenProvider;
+    use crate::interface::web::middleware::bearer::BearerAuth;
+
+    const USER_ID: &str = "801bd045-a367-4683-9234-297586264e39";
+
+    #[get("/me")]
+    async fn me() -> &'static str {
+        "ok"
+    }
+
+    async fn detail(req: test::TestRequest) -> String {
+        let app = test::init_service(
+            App::new()
+                .wrap(BearerAuth::new(TokenProvider::new(Credentials::mock())))
+                .wrap(from_fn(negotiate_locale))
+                .service(me),
+        )
+        .await;
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req.to_request()).await;
+        body["detail"].as_str().unwrap().to_string()
+    }
+
+    #[actix_web::test]
+    async fn accept_language() {
+        let req = test::TestRequest::get().uri("/me");
+        assert_eq!(detail(req).await, "Missing Authorization header");
+
+        let req = test::TestRequest::get().uri("/me").insert_header(("Accept-Language", "ru-RU,ru;q=0.9"));
+        assert_eq!(detail(req).await, "Не передан заголовок Authorization");
+    }
+
+    #[actix_web::test]
+    async fn token_locale_wins() {
+        let claims = Claims::new(
+            USER_ID.to_string(),
+            false,
+            (Utc::now().timestamp() - 600) as usize,
+            HasuraClaims::new("user".to_string(), vec!["user".to_string()], USER_ID.to_string()),
+        )
+        .with_locale(Some("ru".to_string()));
+        let token = TokenProvider::new(Credentials::mock()).generate_access(claims).unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/me")
+            .insert_header(("Accept-Language", "en"))
+            .insert_header(("Authorization", format!("Bearer {}", token)));
+        assert_eq!(detail(req).await, "Требуется авторизация");
+    }
+}
@@ -0,0 +1,6 @@
+pub mod bearer;
+pub mod error;
+pub mod locale;
+#[cfg(feature = "server")]
+pub mod request_signature;
+pub mod role;
\ No newline at end of file
@@ -0,0 +1,45 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::web::{self, Bytes};
+use actix_web::{Error, HttpMessage, HttpResponse, ResponseError};
+
+use crate::application::usecase::integration::signed_request::dto::{SignedRequestDto, SignedRequestResponseDto};
+use crate::infrastructure::verifies::request_signature::SCHEME;
+use crate::interface::web::state::AppState;
+
+/// Проверяет подпись `Authorization: AWR-HMAC-SHA256 ...` для `/auth/integration/*`.
+/// Проверенный способ входа кладётся в extensions запроса, остальные схемы
+/// (например API ключ в заголовке) проходят без изменений
+pub async fn verify_request_signature(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let authorization = match req.headers().get("Authorization").and_then(|v| v.to_str().ok()) {
+        Some(v) if v.starts_with(SCHEME) => v.to_string(),
+        _ => return next.call(req).await.map(ServiceResponse::map_into_boxed_body),
+    };
+
+    let Some(data) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return Ok(req.into_response(HttpResponse::InternalServerError().finish()));
+    };
+
+    // Тело нужно и для подписи, и обработчику - читаем и возвращаем обратно
+    let body = req.extract::<Bytes>().await?;
+    req.set_payload(Payload::from(body.clone()));
+
+    let dto = SignedRequestDto {
+        authorization,
+        method: req.method().to_string(),
+        path: req.uri().path_and_query().map(|v| v.to_string()).unwrap_or_default(),
+        body: body.to_vec(),
+    };
+
+    match data.verify_signed_request_use_case.execute(dto).await {
+        Ok(SignedRequestResponseDto::Success { auth_method }) => {
+            req.extensions_mut().insert(*auth_method);
+            next.call(req).await.map(ServiceResponse::map_into_boxed_body)
+        }
+        Err(e) => Ok(req.into_response(e.error_response())),
+    }
+}
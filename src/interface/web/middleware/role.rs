@@ -0,0 +1,73 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpMessage, ResponseError};
+use futures_util::future::LocalBoxFuture;
+
+use super::bearer::AuthenticatedUser;
+use super::error::AuthMiddlewareError;
+
+/// Пропускает только пользователей с ролью в x-hasura-allowed-roles.
+/// Должен стоять внутри [`super::bearer::BearerAuth`]
+pub fn require_role(role: &str) -> RequireRole {
+    RequireRole { role: Rc::from(role) }
+}
+
+#[derive(Clone)]
+pub struct RequireRole {
+    role: Rc<str>,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireRole
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RequireRoleMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireRoleMiddleware { service, role: self.role.clone() }))
+    }
+}
+
+pub struct RequireRoleMiddleware<S> {
+    service: S,
+    role: Rc<str>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireRoleMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let allowed = match req.extensions().get::<AuthenticatedUser>() {
+            Some(user) if user.has_role(&self.role) => Ok(()),
+            Some(_) => Err(AuthMiddlewareError::RoleRequired(self.role.to_string())),
+            None => Err(AuthMiddlewareError::MissingToken),
+        };
+
+        match allowed {
+            Ok(()) => {
+                let response = self.service.call(req);
+                Box::pin(async move { response.await.map(ServiceResponse::map_into_left_body) })
+            }
+            Err(e) => {
+                let response = req.into_response(e.error_response()).map_into_right_body();
+                Box::pin(ready(Ok(response)))
+            }
+        }
+    }
+}
@@ -1,2 +1,6 @@
+pub mod middleware;
+pub mod problem;
+#[cfg(feature = "server")]
 pub mod routes;
-pub mod state;
+#[cfg(feature = "server")]
+pub mod state;
\ No newline at end of file
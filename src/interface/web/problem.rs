@@ -0,0 +1,94 @@
+//! Ошибки в формате RFC 7807 (`application/problem+json`):
+//!
+//! ```json
+//! {"type": "about:blank", "title": "Unauthorized", "status": 401,
+//!  "code": "invalid_credentials", "detail": "Not correct credentials"}
+//! ```
+//!
+//! `code` стабилен, по нему клиенты и различают ошибки. Ошибки отдельных
+//! полей приходят в `errors`: `[{"field": "username", "code": "not_unique"}]`.
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+
+use crate::domain::errors::service::AppError;
+
+const CONTENT_TYPE: &str = "application/problem+json";
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+        let mut body = serde_json::json!({
+            "type": "about:blank",
+            "title": status.canonical_reason().unwrap_or_default(),
+            "status": status.as_u16(),
+            "code": self.code,
+            "detail": self.message,
+        });
+        if !self.errors.is_empty() {
+            body["errors"] = serde_json::json!(self.errors);
+        }
+        HttpResponse::build(status)
+            .content_type(CONTENT_TYPE)
+            .json(body)
+    }
+}
+
+/// Ошибки разбора тела, пути и query тоже отдаём problem+json, а не текстом actix
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .error_handler(|e: JsonPayloadError, _: &HttpRequest| invalid_request(e.to_string()).into())
+}
+
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default()
+        .error_handler(|e: PathError, _: &HttpRequest| invalid_request(e.to_string()).into())
+}
+
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default()
+        .error_handler(|e: QueryPayloadError, _: &HttpRequest| invalid_request(e.to_string()).into())
+}
+
+fn invalid_request(message: String) -> AppError {
+    AppError::new(400, "invalid_request", &message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::body::to_bytes;
+
+    use crate::domain::errors::service::FieldError;
+
+    #[actix_web::test]
+    async fn problem_json_body() {
+        let response = AppError::new(409, "email_taken", "This email already is busy").error_response();
+
+        assert_eq!(response.status(), 409);
+        assert_eq!(response.headers().get("content-type").unwrap(), CONTENT_TYPE);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["title"], "Conflict");
+        assert_eq!(body["status"], 409);
+        assert_eq!(body["code"], "email_taken");
+        assert_eq!(body["detail"], "This email already is busy");
+        assert!(body.get("errors").is_none());
+    }
+
+    #[actix_web::test]
+    async fn problem_json_field_errors() {
+        let mut error = AppError::new(422, "invalid_attributes", "Attributes are not valid");
+        error.errors = vec![FieldError::new("email", "required")];
+
+        let body = to_bytes(error.error_response().into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["errors"], serde_json::json!([{"field": "email", "code": "required"}]));
+    }
+}
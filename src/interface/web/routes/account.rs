@@ -0,0 +1,106 @@
+use crate::application::usecase::account_usecase::dto::{DeleteAccountRequestDto, UpdateProfileRequestDto};
+use crate::application::usecase::sign_up_usecase::dto::ApiKeyOptionsDto;
+use crate::domain::errors::service::AppError;
+use crate::interface::web::middleware::bearer::AuthenticatedUser;
+use crate::interface::web::state::AppState;
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use uuid::Uuid;
+
+// Пути относительно `/auth/me` и `/auth/apikeys`: обе области закрыты BearerAuth
+
+#[get("")]
+pub async fn get_profile(user: AuthenticatedUser, data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.get_profile_use_case.execute(user.claims).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[patch("")]
+pub async fn update_profile(
+    user: AuthenticatedUser,
+    data: web::Data<AppState>,
+    payload: web::Json<UpdateProfileRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .update_profile_use_case
+        .execute(user.claims, payload.into_inner())
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Тело с паролем необязательно: без него нужен недавний вход
+#[delete("")]
+pub async fn delete_account(
+    user: AuthenticatedUser,
+    data: web::Data<AppState>,
+    payload: Option<web::Json<DeleteAccountRequestDto>>,
+) -> Result<HttpResponse, AppError> {
+    let dto = payload.map(|v| v.into_inner()).unwrap_or_default();
+    let result = data.delete_account_use_case.execute(user.claims, dto).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("/export")]
+pub async fn export_account(user: AuthenticatedUser, data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.export_account_use_case.execute(user.claims).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("/identities")]
+pub async fn list_identities(user: AuthenticatedUser, data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.list_identities_use_case.execute(user.claims).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[delete("/identities/{id}")]
+pub async fn unlink_identity(
+    user: AuthenticatedUser,
+    data: web::Data<AppState>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .unlink_identity_use_case
+        .execute(user.claims, id.into_inner())
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("")]
+pub async fn list_api_keys(user: AuthenticatedUser, data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.list_api_keys_use_case.execute(user.claims).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("")]
+pub async fn create_api_key(
+    user: AuthenticatedUser,
+    data: web::Data<AppState>,
+    payload: web::Json<ApiKeyOptionsDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .create_api_key_use_case
+        .execute_with_claims(user.claims, payload.into_inner())
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[delete("/{identifier}")]
+pub async fn revoke_api_key(
+    user: AuthenticatedUser,
+    data: web::Data<AppState>,
+    identifier: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .revoke_api_key_use_case
+        .execute(user.claims, identifier.into_inner())
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
@@ -0,0 +1,149 @@
+//! Управление пользователями. Монтируется в `/auth/admin` за `BearerAuth`
+//! и `require_role(admin_role)`, поэтому токен здесь уже проверен
+use crate::application::usecase::admin_usecase::dto::{DisableUserRequestDto, ListUsersRequestDto, RoleRequestDto};
+use crate::application::usecase::invite_usecase::dto::CreateInviteRequestDto;
+use crate::application::usecase::role_usecase::dto::CreateRoleRequestDto;
+use crate::domain::errors::service::AppError;
+use crate::interface::web::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use uuid::Uuid;
+
+#[get("/users")]
+pub async fn list_users(
+    data: web::Data<AppState>,
+    query: web::Query<ListUsersRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data.list_users_use_case.execute(query.into_inner()).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("/users/{id}")]
+pub async fn get_user(data: web::Data<AppState>, id: web::Path<Uuid>) -> Result<HttpResponse, AppError> {
+    let result = data.get_user_use_case.execute(id.into_inner()).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/users/{id}/roles")]
+pub async fn grant_role(
+    data: web::Data<AppState>,
+    id: web::Path<Uuid>,
+    payload: web::Json<RoleRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .grant_role_use_case
+        .execute(id.into_inner(), payload.into_inner().role)
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[delete("/users/{id}/roles/{role}")]
+pub async fn revoke_role(
+    data: web::Data<AppState>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, AppError> {
+    let (id, role) = path.into_inner();
+    let result = data.revoke_role_use_case.execute(id, role).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[put("/users/{id}/default-role")]
+pub async fn set_default_role(
+    data: web::Data<AppState>,
+    id: web::Path<Uuid>,
+    payload: web::Json<RoleRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .set_default_role_use_case
+        .execute(id.into_inner(), payload.into_inner().role)
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Тело с причиной необязательно
+#[post("/users/{id}/disable")]
+pub async fn disable_user(
+    data: web::Data<AppState>,
+    id: web::Path<Uuid>,
+    payload: Option<web::Json<DisableUserRequestDto>>,
+) -> Result<HttpResponse, AppError> {
+    let reason = payload.and_then(|v| v.into_inner().reason);
+    let result = data.set_user_disabled_use_case.execute(id.into_inner(), true, reason).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/users/{id}/enable")]
+pub async fn enable_user(data: web::Data<AppState>, id: web::Path<Uuid>) -> Result<HttpResponse, AppError> {
+    let result = data.set_user_disabled_use_case.execute(id.into_inner(), false, None).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/users/{id}/logout")]
+pub async fn logout_user(data: web::Data<AppState>, id: web::Path<Uuid>) -> Result<HttpResponse, AppError> {
+    let result = data.force_logout_use_case.execute(id.into_inner()).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Сразу, без срока `account_deletion_grace_days`
+#[delete("/users/{id}")]
+pub async fn delete_user(data: web::Data<AppState>, id: web::Path<Uuid>) -> Result<HttpResponse, AppError> {
+    let result = data.delete_user_use_case.execute(id.into_inner()).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Удаляет аккаунты с истёкшим сроком после `DELETE /auth/me`
+#[post("/users/purge")]
+pub async fn purge_users(data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.purge_deleted_users_use_case.execute().await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("/roles")]
+pub async fn list_roles(data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.list_roles_use_case.execute().await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/roles")]
+pub async fn create_role(
+    data: web::Data<AppState>,
+    payload: web::Json<CreateRoleRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data.create_role_use_case.execute(payload.into_inner()).await;
+
+    Ok(HttpResponse::Created().json(result?))
+}
+
+#[get("/roles/{role}")]
+pub async fn get_role(data: web::Data<AppState>, role: web::Path<String>) -> Result<HttpResponse, AppError> {
+    let result = data.get_role_use_case.execute(role.into_inner()).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[get("/invites")]
+pub async fn list_invites(data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.list_invites_use_case.execute().await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/invites")]
+pub async fn create_invite(
+    data: web::Data<AppState>,
+    payload: web::Json<CreateInviteRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data.create_invite_use_case.execute(payload.into_inner()).await;
+
+    Ok(HttpResponse::Created().json(result?))
+}
@@ -5,61 +5,47 @@ use crate::application::usecase::auth_usecase::dto::{
     LoginApiKeyRequestDto, LoginEmailPasRequestDto, RefreshTokenRequestDto,
 };
 use crate::interface::web::state::AppState;
-use actix_web::{post, web, HttpResponse, Responder};
+use crate::domain::errors::service::AppError;
+use actix_web::{post, web, HttpResponse};
 
 #[post("/login")]
 pub async fn login(
     data: web::Data<AppState>,
     payload: web::Json<LoginEmailPasRequestDto>,
-) -> impl Responder {
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
     let result = data.login_with_email_passwd_use_case.clone().execute(dto).await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
 
 #[post("/refresh")]
 pub async fn refresh(
     data: web::Data<AppState>,
     payload: web::Json<RefreshTokenRequestDto>,
-) -> impl Responder {
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
     let result = data.refresh_token_use_case.clone().execute(dto).await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
 
 #[post("/loginapikey")]
 pub async fn loginapikey(
     data: web::Data<AppState>,
     payload: web::Json<LoginApiKeyRequestDto>,
-) -> impl Responder {
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
     let result = data.login_with_api_key_use_case.clone().execute(dto).await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
 
 #[post("/createapikey")]
 pub async fn createapikey(
     data: web::Data<AppState>,
     payload: web::Json<CreateApiKeyRequestDto>,
-) -> impl Responder {
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
     let result = data
         .create_api_key_use_case
@@ -67,12 +53,5 @@ pub async fn createapikey(
         .execute(dto)
         .await;
 
-    match result {
-        Ok(v) => {
-            return HttpResponse::Ok().json(v);
-        }
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
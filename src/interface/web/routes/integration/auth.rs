@@ -1,25 +1,48 @@
 use crate::application::usecase::integration::telegram::dto::TelegramDataDTO;
+use crate::domain::errors::service::AppError;
 use crate::interface::web::state::AppState;
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{post, web, HttpResponse};
 use serde::Deserialize;
 
+/// Приглашение и токен гостя передаются в query: поля тела входят в подпись Telegram
+#[derive(Deserialize)]
+pub struct AuthTelegramQuery {
+    #[serde(default)]
+    invite: Option<String>,
+    #[serde(default)]
+    token: Option<String>,
+}
 
+/// Вход через бота по умолчанию
 #[post("/telegram/auth")]
 pub async fn auth_telegram(
     data: web::Data<AppState>,
     payload: web::Json<TelegramDataDTO>,
-) -> impl Responder {
-    let dto = payload.into_inner();
+    query: web::Query<AuthTelegramQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth_telegram_with(data, payload.into_inner(), None, query.into_inner()).await
+}
 
+#[post("/telegram/{bot}/auth")]
+pub async fn auth_telegram_bot(
+    data: web::Data<AppState>,
+    payload: web::Json<TelegramDataDTO>,
+    query: web::Query<AuthTelegramQuery>,
+    bot: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    auth_telegram_with(data, payload.into_inner(), Some(bot.into_inner()), query.into_inner()).await
+}
+
+async fn auth_telegram_with(
+    data: web::Data<AppState>,
+    dto: TelegramDataDTO,
+    bot: Option<String>,
+    query: AuthTelegramQuery,
+) -> Result<HttpResponse, AppError> {
     let result = data
         .auth_telegram_use_case
-        .execute(dto)
+        .execute(dto, bot, query.invite, query.token)
         .await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
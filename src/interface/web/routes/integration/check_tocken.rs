@@ -1,37 +1,36 @@
 use crate::application::usecase::integration::check_token::dto::CheckTokenRequestDto;
+use crate::domain::user::models::extended::ExtendedAuthMethod;
+use crate::domain::errors::service::AppError;
+use crate::interface::web::middleware::error::AuthMiddlewareError;
 use crate::interface::web::state::AppState;
-use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
-use serde::Deserialize;
+use actix_web::{post, web, HttpRequest, HttpResponse};
 
 
 #[post("/checkjwt")]
 pub async fn check_token(
     req: HttpRequest,
     data: web::Data<AppState>,
-    payload: web::Json<CheckTokenRequestDto>
-) -> impl Responder {
+    payload: web::Json<CheckTokenRequestDto>,
+    signed: Option<web::ReqData<ExtendedAuthMethod>>
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
-    let api_key = match req.headers().get("Authorization") {
-        Some(header_value) => match header_value.to_str() {
-            Ok(s) => s.to_string(),
-            Err(_) => return HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid Authorization header"
-            })),
-        },
-        None => return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Missing Authorization header"
-        })),
+    // Запрос подписан ключом - подпись уже проверил middleware
+    if let Some(auth_method) = signed {
+        let result = data
+            .check_token_use_case
+            .execute_signed(dto, auth_method.into_inner())
+            .await;
+        return Ok(HttpResponse::Ok().json(result?));
+    }
+    let Some(api_key) = req.headers().get("Authorization").and_then(|v| v.to_str().ok()) else {
+        return Err(AuthMiddlewareError::MissingToken.into());
     };
+    let api_key = api_key.to_string();
 
     let result = data
         .check_token_use_case
         .execute(dto, api_key)
         .await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
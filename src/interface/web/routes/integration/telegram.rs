@@ -1,6 +1,9 @@
-use crate::application::usecase::integration::telegram::dto::TelegramDataDTO;
+use crate::application::usecase::integration::telegram::dto::{
+    TelegramDataDTO, TelegramLoginExchangeRequestDto, TelegramUpdateDTO,
+};
+use crate::domain::errors::service::AppError;
 use crate::interface::web::state::AppState;
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{post, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -8,24 +11,125 @@ pub struct TelegramQuery {
     token: String,
 }
 
+/// Привязка через бота по умолчанию
 #[post("/telegram/link")]
 pub async fn link_telegram(
     data: web::Data<AppState>,
     payload: web::Json<TelegramDataDTO>,
     query: web::Query<TelegramQuery>,
-) -> impl Responder {
-    let dto = payload.into_inner();
-    let token = &query.token;
+) -> Result<HttpResponse, AppError> {
+    link_telegram_with(data, payload.into_inner(), query.into_inner(), None).await
+}
+
+#[post("/telegram/{bot}/link")]
+pub async fn link_telegram_bot(
+    data: web::Data<AppState>,
+    payload: web::Json<TelegramDataDTO>,
+    query: web::Query<TelegramQuery>,
+    bot: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    link_telegram_with(data, payload.into_inner(), query.into_inner(), Some(bot.into_inner())).await
+}
 
+async fn link_telegram_with(
+    data: web::Data<AppState>,
+    dto: TelegramDataDTO,
+    query: TelegramQuery,
+    bot: Option<String>,
+) -> Result<HttpResponse, AppError> {
     let result = data
         .link_telegram_account_use_case
-        .execute(dto, token.clone())
+        .execute(dto, query.token, bot)
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Слияние с пользователем, которому уже принадлежит Telegram
+#[post("/telegram/merge")]
+pub async fn merge_telegram(
+    data: web::Data<AppState>,
+    payload: web::Json<TelegramDataDTO>,
+    query: web::Query<TelegramQuery>,
+) -> Result<HttpResponse, AppError> {
+    merge_telegram_with(data, payload.into_inner(), query.into_inner(), None).await
+}
+
+#[post("/telegram/{bot}/merge")]
+pub async fn merge_telegram_bot(
+    data: web::Data<AppState>,
+    payload: web::Json<TelegramDataDTO>,
+    query: web::Query<TelegramQuery>,
+    bot: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    merge_telegram_with(data, payload.into_inner(), query.into_inner(), Some(bot.into_inner())).await
+}
+
+async fn merge_telegram_with(
+    data: web::Data<AppState>,
+    dto: TelegramDataDTO,
+    query: TelegramQuery,
+    bot: Option<String>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .merge_telegram_account_use_case
+        .execute(dto, query.token, bot)
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Вход через deep link бота по умолчанию
+#[post("/telegram/login")]
+pub async fn create_telegram_login(data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    create_telegram_login_with(data, None).await
+}
+
+#[post("/telegram/{bot}/login")]
+pub async fn create_telegram_login_bot(
+    data: web::Data<AppState>,
+    bot: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    create_telegram_login_with(data, Some(bot.into_inner())).await
+}
+
+async fn create_telegram_login_with(data: web::Data<AppState>, bot: Option<String>) -> Result<HttpResponse, AppError> {
+    let result = data.create_telegram_login_use_case.execute(bot).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/telegram/login/exchange")]
+pub async fn exchange_telegram_login(
+    data: web::Data<AppState>,
+    payload: web::Json<TelegramLoginExchangeRequestDto>,
+) -> Result<HttpResponse, AppError> {
+    let result = data
+        .exchange_telegram_login_use_case
+        .execute(payload.into_inner())
+        .await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+/// Адрес для setWebhook, secret_token приходит в заголовке
+#[post("/telegram/{bot}/webhook")]
+pub async fn telegram_webhook(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Json<TelegramUpdateDTO>,
+    bot: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let secret = req
+        .headers()
+        .get("X-Telegram-Bot-Api-Secret-Token")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    let result = data
+        .telegram_webhook_use_case
+        .execute(bot.into_inner(), secret, payload.into_inner())
         .await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
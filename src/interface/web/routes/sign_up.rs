@@ -1,19 +1,35 @@
 use crate::application::usecase::sign_up_usecase::dto::SignUpRequestDto;
+use crate::domain::errors::service::AppError;
 use crate::interface::web::state::AppState;
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{post, web, HttpRequest, HttpResponse};
 
+/// Access token из `Authorization: Bearer <token>`.
+/// Токен гостя необязателен, поэтому `/signup` не закрыт BearerAuth
+fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("Authorization")?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|v| v.trim().to_string())
+}
+
+/// С токеном гостя в `Authorization` учётные данные добавляются гостю
 #[post("/signup")]
 pub async fn signup(
+    req: HttpRequest,
     data: web::Data<AppState>,
     payload: web::Json<SignUpRequestDto>,
-) -> impl Responder {
+) -> Result<HttpResponse, AppError> {
     let dto = payload.into_inner();
-    let result = data.sign_up_with_email_use_case.clone().execute(dto).await;
+    let result = data.sign_up_with_email_use_case.clone().execute(dto, bearer_token(&req)).await;
+
+    Ok(HttpResponse::Ok().json(result?))
+}
+
+#[post("/anonymous")]
+pub async fn anonymous(data: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let result = data.create_guest_use_case.execute().await;
 
-    match result {
-        Ok(v) => HttpResponse::Ok().json(v),
-        Err(_) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "Internal error"
-        })),
-    }
+    Ok(HttpResponse::Ok().json(result?))
 }
@@ -1,4 +1,35 @@
 use crate::application::usecase::{
+    account_usecase::{
+        api_keys::ListApiKeysUseCase,
+        delete_account::DeleteAccountUseCase,
+        export::ExportAccountUseCase,
+        identities::ListIdentitiesUseCase,
+        profile::GetProfileUseCase,
+        revoke_api_key::RevokeApiKeyUseCase,
+        unlink::UnlinkIdentityUseCase,
+        update_profile::UpdateProfileUseCase
+    },
+    admin_usecase::{
+        delete_user::DeleteUserUseCase,
+        get_user::GetUserUseCase,
+        grant_role::GrantRoleUseCase,
+        list_users::ListUsersUseCase,
+        logout::ForceLogoutUseCase,
+        purge_users::PurgeDeletedUsersUseCase,
+        revoke_role::RevokeRoleUseCase,
+        set_default_role::SetDefaultRoleUseCase,
+        set_disabled::SetUserDisabledUseCase
+    },
+    guest_usecase::anonymous::CreateGuestUseCase,
+    invite_usecase::{
+        create_invite::CreateInviteUseCase,
+        list_invites::ListInvitesUseCase
+    },
+    role_usecase::{
+        create_role::CreateRoleUseCase,
+        get_role::GetRoleUseCase,
+        list_roles::ListRolesUseCase
+    },
     auth_usecase::{
         refresh::RefreshTokenUseCase,
         email_passwd::LoginWithEmailPasswdUseCase,
@@ -11,9 +42,14 @@ use crate::application::usecase::{
     integration::{
         telegram::{
             link_account::LinkTelegramAccountUseCase,
-            auth::AuthTelegramUseCase
+            merge_account::MergeTelegramAccountUseCase,
+            auth::AuthTelegramUseCase,
+            create_login::CreateTelegramLoginUseCase,
+            webhook::TelegramWebhookUseCase,
+            exchange_login::ExchangeTelegramLoginUseCase
         },
-        check_token::user::CheckTokenUseCase
+        check_token::user::CheckTokenUseCase,
+        signed_request::verify::VerifySignedRequestUseCase
     }
 
 };
@@ -24,6 +60,8 @@ use crate::infrastructure::verifies::api_key_verifier::ApiKeyVerifier;
 use crate::infrastructure::verifies::telegram_verifier::TelegramVerifier;
 use crate::infrastructure::jwt::claims::ClaimsProvider;
 use crate::infrastructure::jwt::token::TokenProvider;
+use crate::infrastructure::telegram::bot_client::TelegramBotClient;
+use crate::infrastructure::telegram::login_session::LoginSessionStore;
 
 use crate::infrastructure::network::http::client::HttpClient;
 
@@ -32,7 +70,7 @@ use crate::infrastructure::network::http::client::HttpClient;
 use std::sync::Arc;
 
 type LoginWithEmailPasswdUseCaseConcrete = LoginWithEmailPasswdUseCase<
-    UserQuery<HttpClient>, PasswordVerifier, ClaimsProvider, TokenProvider
+    UserCommand<HttpClient>, UserQuery<HttpClient>, PasswordVerifier, ClaimsProvider, TokenProvider
 >;
 
 type RefreshTokenUseCaseConcrete = RefreshTokenUseCase<
@@ -40,20 +78,76 @@ type RefreshTokenUseCaseConcrete = RefreshTokenUseCase<
 >;
 
 type LoginWithApiKeyUseCaseConcrete = LoginWithApiKeyUseCase<
-    UserQuery<HttpClient>, ApiKeyVerifier, ClaimsProvider, TokenProvider
+    UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier, ClaimsProvider, TokenProvider
 >;
 
 type CreateApiKeyUseCaseConcrete = CreateApiKeyUseCase<
     UserCommand<HttpClient>, UserQuery<HttpClient>, PasswordVerifier, ApiKeyVerifier
 >;
 
-type SignUpWithEmailUseCaseConcrete = SignUpWithEmailUseCase<UserCommand<HttpClient>, PasswordVerifier>;
+type SignUpWithEmailUseCaseConcrete = SignUpWithEmailUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, PasswordVerifier, TokenProvider>;
+
+type CreateGuestUseCaseConcrete = CreateGuestUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ClaimsProvider, TokenProvider>;
 
 type LinkTelegramAccountUseCaseConcrete = LinkTelegramAccountUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, TelegramVerifier, ClaimsProvider, TokenProvider>;
 
+type MergeTelegramAccountUseCaseConcrete = MergeTelegramAccountUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, TelegramVerifier, ApiKeyVerifier, ClaimsProvider, TokenProvider>;
+
 type AuthTelegramUseCaseConcrete = AuthTelegramUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, TelegramVerifier, ClaimsProvider, TokenProvider>;
 
-type CheckTokenUseCaseConcrete = CheckTokenUseCase<UserQuery<HttpClient>,TokenProvider, ApiKeyVerifier>;
+type CreateTelegramLoginUseCaseConcrete = CreateTelegramLoginUseCase<LoginSessionStore>;
+
+type TelegramWebhookUseCaseConcrete = TelegramWebhookUseCase<LoginSessionStore, TelegramBotClient<HttpClient>>;
+
+type ExchangeTelegramLoginUseCaseConcrete = ExchangeTelegramLoginUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, LoginSessionStore, ClaimsProvider, TokenProvider>;
+
+type ListIdentitiesUseCaseConcrete = ListIdentitiesUseCase<UserQuery<HttpClient>>;
+
+type UnlinkIdentityUseCaseConcrete = UnlinkIdentityUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type ListApiKeysUseCaseConcrete = ListApiKeysUseCase<UserQuery<HttpClient>>;
+
+type RevokeApiKeyUseCaseConcrete = RevokeApiKeyUseCase<UserCommand<HttpClient>, ApiKeyVerifier>;
+
+type CheckTokenUseCaseConcrete = CheckTokenUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, TokenProvider, ApiKeyVerifier>;
+
+type GetProfileUseCaseConcrete = GetProfileUseCase<UserQuery<HttpClient>>;
+
+type UpdateProfileUseCaseConcrete = UpdateProfileUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>>;
+
+type DeleteAccountUseCaseConcrete = DeleteAccountUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, PasswordVerifier, ApiKeyVerifier>;
+
+type ExportAccountUseCaseConcrete = ExportAccountUseCase<UserQuery<HttpClient>>;
+
+type ListUsersUseCaseConcrete = ListUsersUseCase<UserQuery<HttpClient>>;
+
+type GetUserUseCaseConcrete = GetUserUseCase<UserQuery<HttpClient>>;
+
+type GrantRoleUseCaseConcrete = GrantRoleUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type RevokeRoleUseCaseConcrete = RevokeRoleUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type SetDefaultRoleUseCaseConcrete = SetDefaultRoleUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type SetUserDisabledUseCaseConcrete = SetUserDisabledUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type ForceLogoutUseCaseConcrete = ForceLogoutUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type DeleteUserUseCaseConcrete = DeleteUserUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>, ApiKeyVerifier>;
+
+type PurgeDeletedUsersUseCaseConcrete = PurgeDeletedUsersUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>>;
+
+type ListRolesUseCaseConcrete = ListRolesUseCase<UserQuery<HttpClient>>;
+
+type GetRoleUseCaseConcrete = GetRoleUseCase<UserQuery<HttpClient>>;
+
+type CreateRoleUseCaseConcrete = CreateRoleUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>>;
+
+type ListInvitesUseCaseConcrete = ListInvitesUseCase<UserQuery<HttpClient>>;
+
+type CreateInviteUseCaseConcrete = CreateInviteUseCase<UserCommand<HttpClient>, UserQuery<HttpClient>>;
+
+type VerifySignedRequestUseCaseConcrete = VerifySignedRequestUseCase<UserQuery<HttpClient>, ApiKeyVerifier>;
 
 
 
@@ -64,8 +158,36 @@ pub struct AppState {
     pub login_with_api_key_use_case: Arc<LoginWithApiKeyUseCaseConcrete>,
     pub create_api_key_use_case: Arc<CreateApiKeyUseCaseConcrete>,
     pub sign_up_with_email_use_case: Arc<SignUpWithEmailUseCaseConcrete>,
+    pub create_guest_use_case: Arc<CreateGuestUseCaseConcrete>,
     pub link_telegram_account_use_case: Arc<LinkTelegramAccountUseCaseConcrete>,
+    pub merge_telegram_account_use_case: Arc<MergeTelegramAccountUseCaseConcrete>,
     pub auth_telegram_use_case: Arc<AuthTelegramUseCaseConcrete>,
-    pub check_token_use_case: Arc<CheckTokenUseCaseConcrete>
+    pub create_telegram_login_use_case: Arc<CreateTelegramLoginUseCaseConcrete>,
+    pub telegram_webhook_use_case: Arc<TelegramWebhookUseCaseConcrete>,
+    pub exchange_telegram_login_use_case: Arc<ExchangeTelegramLoginUseCaseConcrete>,
+    pub check_token_use_case: Arc<CheckTokenUseCaseConcrete>,
+    pub list_identities_use_case: Arc<ListIdentitiesUseCaseConcrete>,
+    pub unlink_identity_use_case: Arc<UnlinkIdentityUseCaseConcrete>,
+    pub list_api_keys_use_case: Arc<ListApiKeysUseCaseConcrete>,
+    pub revoke_api_key_use_case: Arc<RevokeApiKeyUseCaseConcrete>,
+    pub get_profile_use_case: Arc<GetProfileUseCaseConcrete>,
+    pub update_profile_use_case: Arc<UpdateProfileUseCaseConcrete>,
+    pub delete_account_use_case: Arc<DeleteAccountUseCaseConcrete>,
+    pub export_account_use_case: Arc<ExportAccountUseCaseConcrete>,
+    pub list_users_use_case: Arc<ListUsersUseCaseConcrete>,
+    pub get_user_use_case: Arc<GetUserUseCaseConcrete>,
+    pub grant_role_use_case: Arc<GrantRoleUseCaseConcrete>,
+    pub revoke_role_use_case: Arc<RevokeRoleUseCaseConcrete>,
+    pub set_default_role_use_case: Arc<SetDefaultRoleUseCaseConcrete>,
+    pub set_user_disabled_use_case: Arc<SetUserDisabledUseCaseConcrete>,
+    pub force_logout_use_case: Arc<ForceLogoutUseCaseConcrete>,
+    pub delete_user_use_case: Arc<DeleteUserUseCaseConcrete>,
+    pub purge_deleted_users_use_case: Arc<PurgeDeletedUsersUseCaseConcrete>,
+    pub list_roles_use_case: Arc<ListRolesUseCaseConcrete>,
+    pub get_role_use_case: Arc<GetRoleUseCaseConcrete>,
+    pub create_role_use_case: Arc<CreateRoleUseCaseConcrete>,
+    pub list_invites_use_case: Arc<ListInvitesUseCaseConcrete>,
+    pub create_invite_use_case: Arc<CreateInviteUseCaseConcrete>,
+    pub verify_signed_request_use_case: Arc<VerifySignedRequestUseCaseConcrete>
 }
 
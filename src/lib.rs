@@ -0,0 +1,37 @@
+//! Сервис авторизации с ролями Hasura.
+//!
+//! Доменные трейты, JWT и проверки доступны всегда, остальное - за features:
+//!
+//! - `actix` - [`BearerAuth`], [`AuthenticatedUser`], [`require_role`];
+//! - `hasura` - `UserQuery`/`UserCommand` поверх Hasura GraphQL;
+//! - `telegram` - бот, вход через deep link и use case Telegram;
+//! - `server` (по умолчанию) - маршруты и `AppState` самого сервера.
+//!
+//! Проверка наших access токенов в другом actix сервисе (`default-features = false, features = ["actix"]`):
+//!
+//! ```ignore
+//! use auth_with_role::{require_role, AuthenticatedUser, BearerAuth};
+//!
+//! App::new().service(
+//!     web::scope("/admin")
+//!         .wrap(require_role("admin"))
+//!         .wrap(BearerAuth::new(TokenProvider::new(Credentials::with_jwt_secrets(access, refresh))))
+//!         .service(handler),
+//! )
+//! ```
+//!
+//! Так проверяются только подпись и срок токена. С доступом к базе пользователей
+//! `BearerAuth::new(..).with_user_check(query_user)` дополнительно отклоняет
+//! отключённых пользователей и токены, выданные до принудительного выхода.
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+pub mod interface;
+// Моки Hasura и Telegram - тесты запускаются с features по умолчанию
+#[cfg(all(test, feature = "server"))]
+mod mock;
+
+#[cfg(feature = "actix")]
+pub use interface::web::middleware::bearer::{AuthenticatedUser, BearerAuth};
+#[cfg(feature = "actix")]
+pub use interface::web::middleware::role::{require_role, RequireRole};
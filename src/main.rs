@@ -1,10 +1,36 @@
-mod application;
-mod domain;
-mod infrastructure;
-mod interface;
-mod mock;
-
-use crate::application::usecase::{
+use auth_with_role::application::usecase::{
+    account_usecase::{
+        api_keys::ListApiKeysUseCase,
+        delete_account::DeleteAccountUseCase,
+        export::ExportAccountUseCase,
+        identities::ListIdentitiesUseCase,
+        profile::GetProfileUseCase,
+        revoke_api_key::RevokeApiKeyUseCase,
+        unlink::UnlinkIdentityUseCase,
+        update_profile::UpdateProfileUseCase
+    },
+    admin_usecase::{
+        delete_user::DeleteUserUseCase,
+        get_user::GetUserUseCase,
+        grant_role::GrantRoleUseCase,
+        list_users::ListUsersUseCase,
+        logout::ForceLogoutUseCase,
+        purge_users::PurgeDeletedUsersUseCase,
+        revoke_role::RevokeRoleUseCase,
+        set_default_role::SetDefaultRoleUseCase,
+        set_disabled::SetUserDisabledUseCase
+    },
+    guest_usecase::anonymous::CreateGuestUseCase,
+    invite_usecase::{
+        create_invite::CreateInviteUseCase,
+        list_invites::ListInvitesUseCase
+    },
+    role_usecase::{
+        create_role::CreateRoleUseCase,
+        get_role::GetRoleUseCase,
+        list_roles::ListRolesUseCase,
+        validate_roles::ValidateRolesUseCase
+    },
     auth_usecase::{
         refresh::RefreshTokenUseCase,
         email_passwd::LoginWithEmailPasswdUseCase,
@@ -17,30 +43,55 @@ use crate::application::usecase::{
     integration::{
         telegram::{
             link_account::LinkTelegramAccountUseCase,
+            merge_account::MergeTelegramAccountUseCase,
             auth::AuthTelegramUseCase,
+            create_login::CreateTelegramLoginUseCase,
+            webhook::TelegramWebhookUseCase,
+            exchange_login::ExchangeTelegramLoginUseCase,
         },
         check_token::user::CheckTokenUseCase,
+        signed_request::verify::VerifySignedRequestUseCase,
     }
 };
 
-use crate::domain::settings::service::CredentialsService as _;
-use crate::infrastructure::config::credentials_provider::CredentialsProvider;
-
-use crate::infrastructure::jwt::factory::JWTProvider;
-use crate::infrastructure::user::factory::UserProvider;
-use crate::infrastructure::verifies::factory::VerifiesProvider;
-use crate::infrastructure::network::client_manager::HasuraClientManager;
-
-use actix_web::{web, App, HttpServer};
-use interface::web::routes::auth::createapikey;
-use interface::web::routes::auth::{login, loginapikey, refresh};
-use interface::web::routes::sign_up::signup;
-use interface::web::routes::integration::{
-    telegram::link_telegram,
-    auth::auth_telegram,
+use auth_with_role::domain::settings::service::CredentialsService as _;
+use auth_with_role::domain::user::factories::UserProviderFactory as _;
+use auth_with_role::infrastructure::config::credentials_provider::CredentialsProvider;
+
+use auth_with_role::infrastructure::jwt::factory::JWTProvider;
+use auth_with_role::infrastructure::jwt::token::TokenProvider;
+use auth_with_role::infrastructure::user::factory::UserProvider;
+use auth_with_role::infrastructure::verifies::factory::VerifiesProvider;
+use auth_with_role::infrastructure::telegram::factory::TelegramProvider;
+use auth_with_role::infrastructure::network::client_manager::HasuraClientManager;
+
+use actix_web::{middleware::from_fn, web, App, HttpServer};
+use auth_with_role::interface::web::routes::account::{
+    create_api_key, delete_account, export_account, get_profile, list_api_keys, list_identities, revoke_api_key, unlink_identity, update_profile,
+};
+use auth_with_role::interface::web::routes::admin::{
+    create_invite, create_role, delete_user, disable_user, enable_user, get_role, get_user, grant_role, list_invites, list_roles,
+    list_users, logout_user, purge_users, revoke_role, set_default_role,
+};
+use auth_with_role::interface::web::routes::auth::createapikey;
+use auth_with_role::interface::web::routes::auth::{login, loginapikey, refresh};
+use auth_with_role::interface::web::routes::sign_up::{anonymous, signup};
+use auth_with_role::interface::web::routes::integration::{
+    telegram::{
+        link_telegram, link_telegram_bot,
+        merge_telegram, merge_telegram_bot,
+        create_telegram_login, create_telegram_login_bot,
+        exchange_telegram_login, telegram_webhook
+    },
+    auth::{auth_telegram, auth_telegram_bot},
     check_tocken::check_token
 };
-use interface::web::state::AppState;
+use auth_with_role::interface::web::middleware::bearer::BearerAuth;
+use auth_with_role::interface::web::middleware::locale::negotiate_locale;
+use auth_with_role::interface::web::middleware::role::require_role;
+use auth_with_role::interface::web::middleware::request_signature::verify_request_signature;
+use auth_with_role::interface::web::state::AppState;
+use auth_with_role::interface::web::problem;
 use std::sync::Arc;
 
 #[actix_web::main]
@@ -59,6 +110,13 @@ async fn main() -> std::io::Result<()> {
     let jwtprovider_factory = JWTProvider::new(credentials.clone());
     let verifies_provider_factory = VerifiesProvider::new(credentials.clone());
     let user_provider_factory = UserProvider::new(credentials.clone(), hasura_client.clone());
+    let telegram_provider_factory = TelegramProvider::new(credentials.clone());
+
+    // new_user_role и остальные роли из настроек должны существовать до первой регистрации
+    ValidateRolesUseCase::new(credentials.clone(), &user_provider_factory)
+        .execute()
+        .await
+        .expect("Configured roles are missing in users.role");
 
     let login_with_email_passwd_use_case = LoginWithEmailPasswdUseCase::new(
         &user_provider_factory,
@@ -79,6 +137,7 @@ async fn main() -> std::io::Result<()> {
     );
 
     let create_api_key_use_case = CreateApiKeyUseCase::new(
+        credentials.clone(),
         &user_provider_factory,
         &verifies_provider_factory
     );
@@ -86,7 +145,14 @@ async fn main() -> std::io::Result<()> {
     let sign_up_with_email_use_case = SignUpWithEmailUseCase::new(
         credentials.clone(),
         &verifies_provider_factory,
-        &user_provider_factory
+        &user_provider_factory,
+        &jwtprovider_factory
+    );
+
+    let create_guest_use_case = CreateGuestUseCase::new(
+        credentials.clone(),
+        &user_provider_factory,
+        &jwtprovider_factory
     );
 
     let link_telegram_account_use_case = LinkTelegramAccountUseCase::new(
@@ -96,6 +162,13 @@ async fn main() -> std::io::Result<()> {
         &jwtprovider_factory
     );
 
+    let merge_telegram_account_use_case = MergeTelegramAccountUseCase::new(
+        credentials.clone(),
+        &user_provider_factory,
+        &verifies_provider_factory,
+        &jwtprovider_factory
+    );
+
     let auth_telegram_use_case = AuthTelegramUseCase::new(
         credentials.clone(),
         &user_provider_factory,
@@ -103,6 +176,23 @@ async fn main() -> std::io::Result<()> {
         &jwtprovider_factory
     );
 
+    let create_telegram_login_use_case = CreateTelegramLoginUseCase::new(
+        credentials.clone(),
+        &telegram_provider_factory
+    );
+
+    let telegram_webhook_use_case = TelegramWebhookUseCase::new(
+        credentials.clone(),
+        &telegram_provider_factory
+    );
+
+    let exchange_telegram_login_use_case = ExchangeTelegramLoginUseCase::new(
+        credentials.clone(),
+        &user_provider_factory,
+        &telegram_provider_factory,
+        &jwtprovider_factory
+    );
+
     let check_token_use_case = CheckTokenUseCase::new(
         credentials.clone(),
         &user_provider_factory,
@@ -110,34 +200,181 @@ async fn main() -> std::io::Result<()> {
         &jwtprovider_factory
     );
 
+    let verify_signed_request_use_case = VerifySignedRequestUseCase::new(
+        &user_provider_factory,
+        &verifies_provider_factory
+    );
+
+    let list_identities_use_case = ListIdentitiesUseCase::new(&user_provider_factory);
+
+    let unlink_identity_use_case = UnlinkIdentityUseCase::new(
+        &user_provider_factory,
+        &verifies_provider_factory
+    );
+
+    let list_api_keys_use_case = ListApiKeysUseCase::new(&user_provider_factory);
+
+    let revoke_api_key_use_case = RevokeApiKeyUseCase::new(
+        &user_provider_factory,
+        &verifies_provider_factory
+    );
+
+    let get_profile_use_case = GetProfileUseCase::new(&user_provider_factory);
+
+    let update_profile_use_case = UpdateProfileUseCase::new(
+        credentials.clone(),
+        &user_provider_factory
+    );
+
+    let delete_account_use_case = DeleteAccountUseCase::new(
+        credentials.clone(),
+        &user_provider_factory,
+        &verifies_provider_factory
+    );
+
+    let export_account_use_case = ExportAccountUseCase::new(&user_provider_factory);
+
+    let list_users_use_case = ListUsersUseCase::new(&user_provider_factory);
+    let get_user_use_case = GetUserUseCase::new(&user_provider_factory);
+    let grant_role_use_case = GrantRoleUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let revoke_role_use_case = RevokeRoleUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let set_default_role_use_case = SetDefaultRoleUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let set_user_disabled_use_case = SetUserDisabledUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let force_logout_use_case = ForceLogoutUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let delete_user_use_case = DeleteUserUseCase::new(&user_provider_factory, &verifies_provider_factory);
+    let purge_deleted_users_use_case = PurgeDeletedUsersUseCase::new(&user_provider_factory);
+    let list_roles_use_case = ListRolesUseCase::new(credentials.clone(), &user_provider_factory);
+    let get_role_use_case = GetRoleUseCase::new(credentials.clone(), &user_provider_factory);
+    let create_role_use_case = CreateRoleUseCase::new(credentials.clone(), &user_provider_factory);
+    let list_invites_use_case = ListInvitesUseCase::new(&user_provider_factory);
+    let create_invite_use_case = CreateInviteUseCase::new(&user_provider_factory);
+
     let app_state = AppState{
         login_with_email_passwd_use_case: Arc::new(login_with_email_passwd_use_case),
         refresh_token_use_case: Arc::new(refresh_token_use_case),
         login_with_api_key_use_case: Arc::new(login_with_api_key_use_case),
         create_api_key_use_case: Arc::new(create_api_key_use_case),
         sign_up_with_email_use_case: Arc::new(sign_up_with_email_use_case),
+        create_guest_use_case: Arc::new(create_guest_use_case),
         link_telegram_account_use_case: Arc::new(link_telegram_account_use_case),
+        merge_telegram_account_use_case: Arc::new(merge_telegram_account_use_case),
         auth_telegram_use_case: Arc::new(auth_telegram_use_case),
-        check_token_use_case: Arc::new(check_token_use_case)
+        create_telegram_login_use_case: Arc::new(create_telegram_login_use_case),
+        telegram_webhook_use_case: Arc::new(telegram_webhook_use_case),
+        exchange_telegram_login_use_case: Arc::new(exchange_telegram_login_use_case),
+        check_token_use_case: Arc::new(check_token_use_case),
+        list_identities_use_case: Arc::new(list_identities_use_case),
+        unlink_identity_use_case: Arc::new(unlink_identity_use_case),
+        list_api_keys_use_case: Arc::new(list_api_keys_use_case),
+        revoke_api_key_use_case: Arc::new(revoke_api_key_use_case),
+        get_profile_use_case: Arc::new(get_profile_use_case),
+        update_profile_use_case: Arc::new(update_profile_use_case),
+        delete_account_use_case: Arc::new(delete_account_use_case),
+        export_account_use_case: Arc::new(export_account_use_case),
+        list_users_use_case: Arc::new(list_users_use_case),
+        get_user_use_case: Arc::new(get_user_use_case),
+        grant_role_use_case: Arc::new(grant_role_use_case),
+        revoke_role_use_case: Arc::new(revoke_role_use_case),
+        set_default_role_use_case: Arc::new(set_default_role_use_case),
+        set_user_disabled_use_case: Arc::new(set_user_disabled_use_case),
+        force_logout_use_case: Arc::new(force_logout_use_case),
+        delete_user_use_case: Arc::new(delete_user_use_case),
+        purge_deleted_users_use_case: Arc::new(purge_deleted_users_use_case),
+        list_roles_use_case: Arc::new(list_roles_use_case),
+        get_role_use_case: Arc::new(get_role_use_case),
+        create_role_use_case: Arc::new(create_role_use_case),
+        list_invites_use_case: Arc::new(list_invites_use_case),
+        create_invite_use_case: Arc::new(create_invite_use_case),
+        verify_signed_request_use_case: Arc::new(verify_signed_request_use_case)
     };
 
+    // Аккаунты с истёкшим account_deletion_grace_days удаляются здесь, если не отключено
+    let purge_interval_minutes = *credentials.account_purge_interval_minutes();
+    if purge_interval_minutes > 0 {
+        let purge_deleted_users_use_case = app_state.purge_deleted_users_use_case.clone();
+        actix_web::rt::spawn(async move {
+            let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(purge_interval_minutes * 60));
+            loop {
+                interval.tick().await;
+                // Ошибка уже записана в лог, следующая попытка - на следующем тике
+                let _ = purge_deleted_users_use_case.execute().await;
+            }
+        });
+    }
+
+    // Отключённые и принудительно разлогиненные пользователи не проходят и с ещё живым токеном
+    let bearer_auth = BearerAuth::new(TokenProvider::new(credentials.clone()))
+        .with_user_check(user_provider_factory.query_user());
+
     let host: String = credentials.host().clone();
     let port = credentials.port().clone();
 
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(app_state.clone()))
+            .app_data(problem::json_config())
+            .app_data(problem::path_config())
+            .app_data(problem::query_config())
+            .wrap(from_fn(negotiate_locale))
             .service(
                 web::scope("/auth")
                     .service(login)
                     .service(loginapikey)
                     .service(refresh)
                     .service(signup)
+                    .service(anonymous)
                     .service(createapikey)
+                    .service(
+                        web::scope("/me")
+                            .wrap(bearer_auth.clone())
+                            .service(get_profile)
+                            .service(update_profile)
+                            .service(delete_account)
+                            .service(export_account)
+                            .service(list_identities)
+                            .service(unlink_identity)
+                    )
+                    .service(
+                        web::scope("/apikeys")
+                            .wrap(bearer_auth.clone())
+                            .service(list_api_keys)
+                            .service(create_api_key)
+                            .service(revoke_api_key)
+                    )
+                    .service(
+                        // Последний wrap - внешний: сначала BearerAuth, затем проверка роли
+                        web::scope("/admin")
+                            .wrap(require_role(credentials.admin_role()))
+                            .wrap(bearer_auth.clone())
+                            .service(list_users)
+                            .service(purge_users)
+                            .service(get_user)
+                            .service(grant_role)
+                            .service(revoke_role)
+                            .service(set_default_role)
+                            .service(disable_user)
+                            .service(enable_user)
+                            .service(logout_user)
+                            .service(delete_user)
+                            .service(list_roles)
+                            .service(create_role)
+                            .service(get_role)
+                            .service(list_invites)
+                            .service(create_invite)
+                    )
                     .service(
                         web::scope("/integration")
+                            .wrap(from_fn(verify_request_signature))
                             .service(link_telegram)
+                            .service(link_telegram_bot)
+                            .service(merge_telegram)
+                            .service(merge_telegram_bot)
                             .service(auth_telegram)
+                            .service(auth_telegram_bot)
+                            .service(exchange_telegram_login)
+                            .service(create_telegram_login)
+                            .service(create_telegram_login_bot)
+                            .service(telegram_webhook)
                             .service(check_token)
                     )
             )
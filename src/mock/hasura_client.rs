@@ -1,7 +1,7 @@
 use include_dir::{Dir, include_dir};
 
 use crate::infrastructure::network::hasura::client::HasuraClient;
-use super::http_client::{MockHttpClient, ResponseMode, ResponseFile};
+use super::http_client::{MockHttpClient, MockHttpClientResponse, ResponseMode, ResponseFile};
 
 static RESPONSE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/tests/mock_gql/response/");
 
@@ -18,8 +18,13 @@ impl MockHasuraClientBuilder {
     }
 
     /// Sets default insert responses: user, roles, auth method, attributes
+    /// and the nested insert of a user with all of them
     pub fn with_user_creation(&mut self) -> &mut Self {
         self.http_client
+            .set_file_response(
+                "InsertUserWith".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "insert_user_with.json"),
+            )
             .set_file_response(
                 "InsertUsersAuthMethod".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "insert_user_auth_method.json"),
@@ -35,6 +40,10 @@ impl MockHasuraClientBuilder {
             .set_file_response(
                 "InsertMultipleAttributes".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "insert_user_attributes.json"),
+            )
+            .set_file_response(
+                "LinkCredentials".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "link_credentials.json"),
             );
         self
     }
@@ -49,6 +58,62 @@ impl MockHasuraClientBuilder {
             .set_file_response(
                 "GetAuthMethodByUserId".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email.json"),
+            )
+            .set_file_response(
+                "GetAuthMethodByProvider".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email.json"),
+            );
+        self
+    }
+
+    /// Simulates an email user after a forced logout on 2025-07-11
+    pub fn with_logged_out_user(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email_logged_out.json"),
+            );
+        self
+    }
+
+    /// Simulates an email user disabled by an administrator
+    pub fn with_disabled_user(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email_disabled.json"),
+            )
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email_disabled.json"),
+            );
+        self
+    }
+
+    /// Simulates an email user locked until 2099
+    pub fn with_locked_user(&mut self) -> &mut Self {
+        self.with_email_user_response("query_auth_methods_email_locked.json")
+    }
+
+    /// Simulates an email user whose lock expired on 2025-07-11
+    pub fn with_lock_expired_user(&mut self) -> &mut Self {
+        self.with_email_user_response("query_auth_methods_email_lock_expired.json")
+    }
+
+    /// Simulates an email user who has not confirmed the email yet
+    pub fn with_pending_user(&mut self) -> &mut Self {
+        self.with_email_user_response("query_auth_methods_email_pending.json")
+    }
+
+    fn with_email_user_response(&mut self, file: &'static str) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), file),
+            )
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), file),
             );
         self
     }
@@ -63,6 +128,77 @@ impl MockHasuraClientBuilder {
             .set_file_response(
                 "GetAuthMethodByUserId".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey.json"),
+            )
+            .set_file_response(
+                "GetAuthMethodByProvider".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey.json"),
+            );
+        self
+    }
+
+    /// Simulates that the found API key is revoked
+    pub fn with_revoked_apikey_auth_method(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey_revoked.json"),
+            );
+        self
+    }
+
+    /// Simulates that the found API key is limited to the `reports` role
+    pub fn with_scoped_apikey_auth_method(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey_scoped.json"),
+            );
+        self
+    }
+
+    /// Simulates that the found API key can sign requests
+    pub fn with_signed_apikey_auth_method(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey_signed.json"),
+            );
+        self
+    }
+
+    /// Simulates that the found API key is expired
+    pub fn with_expired_apikey_auth_method(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_apikey_expired.json"),
+            );
+        self
+    }
+
+    pub fn with_auth_method_touch(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "TouchUsersAuthMethod".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_auth_method_last_used.json"),
+            );
+        self
+    }
+
+    pub fn with_auth_method_secret_update(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "UpdateUsersAuthMethodSecret".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_auth_method_secret.json"),
+            );
+        self
+    }
+
+    pub fn with_auth_method_revoke(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "RevokeUsersAuthMethod".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "revoke_user_auth_method.json"),
             );
         self
     }
@@ -107,6 +243,10 @@ impl MockHasuraClientBuilder {
 
     pub fn with_error_insert_user(&mut self) -> &mut Self {
         self.http_client
+            .set_file_response(
+                "InsertUserWith".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "hasura_error.json"),
+            )
             .set_file_response(
                 "InsertUser".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "hasura_error.json"),
@@ -133,10 +273,269 @@ impl MockHasuraClientBuilder {
             .set_file_response(
                 "GetAuthMethodByUserId".to_string(),
                 ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_empty.json"),
+            )
+            .set_file_response(
+                "GetAuthMethodByProvider".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_empty.json"),
+            );
+        self
+    }
+
+    /// Simulates a user with email and a linked Telegram account
+    pub fn with_linked_telegram(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email_telegram.json"),
+            );
+        self
+    }
+
+    /// Simulates that the user has an active and a revoked API key,
+    /// both listed before the email method
+    pub fn with_user_api_keys(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_email_apikeys.json"),
+            );
+        self
+    }
+
+    /// Simulates that the Telegram id belongs to another user
+    pub fn with_telegram_of_another_user(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByIdentifier".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_telegram.json"),
+            )
+            .set_file_response(
+                "GetAuthMethodByProvider".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_telegram.json"),
+            );
+        self
+    }
+
+    pub fn with_users_merge(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "MergeUsers".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "merge_users.json"),
             );
         self
     }
 
+    pub fn with_auth_method_removal(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "DeleteUsersAuthMethod".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "delete_user_auth_method.json"),
+            );
+        self
+    }
+
+    /// Simulates the removal of the active API key from `with_user_api_keys`
+    pub fn with_api_key_removal(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "DeleteUsersAuthMethod".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "delete_user_api_key.json"),
+            );
+        self
+    }
+
+    pub fn with_attribute_update(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "UpdateUserAttributes".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user_attribute.json"),
+            );
+        self
+    }
+
+    /// Sets the users.attribute schema (email required, email/username/telegram_username
+    /// unique, typed email and phone, username pattern) with all values free
+    pub fn with_attribute_schema(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetAttribute".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_attribute.json"),
+            )
+            .set_file_response(
+                "CheckAttributeValue".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_attribute_value_free.json"),
+            );
+        self
+    }
+
+    /// Simulates that every unique attribute value belongs to another user
+    pub fn with_taken_attribute_value(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "CheckAttributeValue".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_attribute_value_taken.json"),
+            );
+        self
+    }
+
+    /// Simulates the admin view of a user with roles "test" (default) and "user"
+    pub fn with_user(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetUserById".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_user_by_id.json"),
+            )
+            .set_file_response(
+                "ListUsers".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_users.json"),
+            );
+        self
+    }
+
+    pub fn with_user_not_found(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetUserById".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_user_by_id_empty.json"),
+            )
+            .set_file_response(
+                "UpdateUserStatus".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user_empty.json"),
+            )
+            .set_file_response(
+                "UpdateUserLoggedOut".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user_empty.json"),
+            );
+        self
+    }
+
+    /// Simulates a guest (role "guest", anonymous auth method) and the single
+    /// mutation that attaches credentials to it
+    pub fn with_guest(&mut self) -> &mut Self {
+        self.with_user();
+        self.http_client
+            .set_file_response(
+                "GetAuthMethodByUserId".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_auth_methods_anonymous.json"),
+            )
+            .set_file_response(
+                "UpgradeGuest".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "upgrade_guest.json"),
+            );
+        self
+    }
+
+    /// Sets role removal, default role and disable/logout responses
+    pub fn with_user_management(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "DeleteUsersRole".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "delete_user_role.json"),
+            )
+            .set_file_response(
+                "UpdateDefaultRole".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_default_role.json"),
+            )
+            .set_file_response(
+                "UpdateUserStatus".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user.json"),
+            )
+            .set_file_response(
+                "UpdateUserLoggedOut".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user.json"),
+            )
+            .set_file_response(
+                "InsertUsersRole".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "insert_user_role.json"),
+            );
+        self
+    }
+
+    /// Sets the role catalogue: admin, default, test, user and the new user roles
+    pub fn with_roles(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "ListRoles".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_roles.json"),
+            )
+            .set_file_response(
+                "CountRoleUsers".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "count_role_users.json"),
+            )
+            .set_file_response(
+                "InsertRole".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "insert_role.json"),
+            );
+        self
+    }
+
+    /// Sets scheduled deletion, account deletion and due users responses
+    pub fn with_user_deletion(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "ScheduleUserDeletion".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "update_user.json"),
+            )
+            .set_file_response(
+                "DeleteUser".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "delete_user.json"),
+            )
+            .set_file_response(
+                "ListUsersToDelete".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_users_to_delete.json"),
+            );
+        self
+    }
+
+    /// Sets the invite list and invite creation
+    pub fn with_invites(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "ListInvites".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_invites.json"),
+            )
+            .set_file_response(
+                "InsertInvite".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "insert_invite.json"),
+            );
+        self
+    }
+
+    /// Sets a valid invite "beta-tester" with role "test"
+    pub fn with_valid_invite(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetInvite".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_invite_valid.json"),
+            );
+        self
+    }
+
+    /// Simulates an invite that can't be used: `file` is the GetInvite response
+    /// (query_invite_expired.json, query_invite_exhausted.json or query_invite_empty.json)
+    pub fn with_unusable_invite(&mut self, file: &'static str) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "GetInvite".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), file),
+            );
+        self
+    }
+
+    pub fn with_roles_without_admin(&mut self) -> &mut Self {
+        self.http_client
+            .set_file_response(
+                "ListRoles".to_string(),
+                ResponseFile::new(RESPONSE_DIR.clone(), "query_roles_without_admin.json"),
+            );
+        self
+    }
+
+    /// Body of the last request sent through the built clients
+    pub fn recorder(&self) -> MockHttpClientResponse {
+        self.http_client.recorder()
+    }
+
     pub fn build(&self) -> HasuraClient<MockHttpClient> {
         HasuraClient::new(Box::new(self.http_client.clone()))
     }
@@ -1,4 +1,5 @@
 pub mod http_client;
 pub mod user_provider;
 pub mod hasura_client;
-pub mod user;
\ No newline at end of file
+pub mod user;
+pub mod telegram;
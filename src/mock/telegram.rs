@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use crate::application::usecase::integration::telegram::dto::TelegramDataDTO;
+use crate::domain::verifies::model::TelegramData;
+use crate::infrastructure::verifies::telegram_verifier::TelegramVerifier;
+
+pub struct MockTelegramData;
+
+impl MockTelegramData {
+    /// Свежий payload Login Widget, подписанный `bot_token`.
+    /// id случайный, чтобы тесты не упирались в кэш повторов.
+    pub fn signed(bot_token: &str) -> TelegramDataDTO {
+        let dto = TelegramDataDTO {
+            id: rand::random_range(1..i64::MAX),
+            first_name: Some("Mock".to_string()),
+            last_name: None,
+            username: "mock_user".to_string(),
+            photo_url: None,
+            auth_date: chrono::Utc::now().timestamp(),
+            hash: String::new(),
+            extra: BTreeMap::new(),
+        };
+        Self::sign(dto, bot_token)
+    }
+
+    /// Пересчитывает hash после изменения полей
+    pub fn sign(mut dto: TelegramDataDTO, bot_token: &str) -> TelegramDataDTO {
+        let data: TelegramData = dto.clone().into();
+        let data_check_string = TelegramVerifier::data_check_string(&data);
+        let mac = TelegramVerifier::mac(bot_token, &data_check_string);
+        dto.hash = hex::encode(hmac::Mac::finalize(mac).into_bytes());
+        dto
+    }
+}
@@ -1,17 +1,19 @@
 use crate::domain::settings::model::Credentials;
 use crate::domain::user::factories::UserProviderFactory;
 use crate::infrastructure::network::hasura::client::HasuraClient;
+use crate::infrastructure::user::schema_cache::AttributeSchemaCache;
 use crate::infrastructure::user::user_manager::{UserCommand, UserQuery};
 use crate::mock::http_client::MockHttpClient;
 
 
 pub struct MockUserProvider {
     credentials: Credentials,
-    hasura_client: HasuraClient<MockHttpClient>
+    hasura_client: HasuraClient<MockHttpClient>,
+    attribute_schema: AttributeSchemaCache,
 }
 impl MockUserProvider {
     pub fn new(credentials: Credentials, hasura_client: HasuraClient<MockHttpClient>) -> Self {
-        Self { credentials, hasura_client }
+        Self { credentials, hasura_client, attribute_schema: AttributeSchemaCache::default() }
     }
 }
 
@@ -24,5 +26,6 @@ impl UserProviderFactory for MockUserProvider {
     }
     fn query_user(&self) -> Self::QueryUser {
         UserQuery::new(self.credentials.clone(), self.hasura_client.clone())
+            .with_attribute_schema_cache(self.attribute_schema.clone())
     }
 }
\ No newline at end of file